@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
+use crate::module::sat::{ColorPalette, DEFAULT_AMSAT_API_URL, DEFAULT_TEMPLATE_PATH, LegendPosition, RenderLayout, SortOrder};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendConfig {
@@ -11,6 +12,307 @@ pub struct BackendConfig {
     
     #[serde(default = "default_log_level")]
     pub log_level: String,
+
+    /// Deadline (in seconds) for handling a single gRPC request
+    #[serde(default = "default_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+
+    #[serde(default)]
+    pub satellite: SatelliteConfig,
+
+    #[serde(default)]
+    pub http: HttpApiConfig,
+
+    #[serde(default)]
+    pub render: RenderConfig,
+
+    #[serde(default)]
+    pub reports: ReportLimitConfig,
+
+    #[serde(default)]
+    pub watchlist: WatchlistConfig,
+
+    #[serde(default)]
+    pub daily_post: DailyPostConfig,
+
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+}
+
+/// Satellite data processing settings (`[satellite]` section)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SatelliteConfig {
+    /// How far into the future (in minutes) a report timestamp may be
+    /// before it is treated as clock skew and dropped
+    #[serde(default = "default_future_report_tolerance_minutes")]
+    pub future_report_tolerance_minutes: i64,
+
+    /// Hours of AMSAT history requested on each regular update
+    #[serde(default = "default_fetch_window_hours")]
+    pub fetch_window_hours: u64,
+
+    /// Hours of history requested for quiet satellites: inactive, not heard
+    /// recently or on a non-voice mode (at least `fetch_window_hours`)
+    #[serde(default = "default_quiet_fetch_window_hours")]
+    pub quiet_fetch_window_hours: u64,
+
+    /// Upper bound for the wider window requested after downtime
+    #[serde(default = "default_max_catch_up_hours")]
+    pub max_catch_up_hours: u64,
+
+    /// Hours of report blocks kept per satellite; fetch windows are capped
+    /// at this, since older reports would be dropped right away
+    #[serde(default = "default_retention_hours")]
+    pub retention_hours: u64,
+
+    /// AMSAT status API endpoint (override for mirrors or a local mock)
+    #[serde(default = "default_amsat_api_url")]
+    pub amsat_api_url: String,
+
+    /// Read the satellite list and reports from saved AMSAT responses in this
+    /// directory instead of the network (offline demos and CI)
+    #[serde(default)]
+    pub fixture_dir: Option<String>,
+
+    /// Only track these satellites, by name or base name such as "ISS"
+    /// (empty = all)
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+
+    /// Never track these satellites, by name or base name; wins over the
+    /// allowlist
+    #[serde(default)]
+    pub denylist: Vec<String>,
+
+    /// Upstream requests allowed per update cycle, shared by all satellites,
+    /// per-satellite retries and retries of the whole update
+    #[serde(default = "default_max_requests_per_cycle")]
+    pub max_requests_per_cycle: u32,
+}
+
+/// Proxy for outbound requests to AMSAT and other upstreams (`[proxy]` section)
+///
+/// Without a `url`, the `HTTP_PROXY`/`HTTPS_PROXY`/`ALL_PROXY` environment
+/// variables are honored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    /// Proxy for all outbound requests, e.g. "http://proxy:3128" or
+    /// "socks5://proxy:1080"
+    #[serde(default)]
+    pub url: Option<String>,
+
+    /// Username for proxy authentication
+    #[serde(default)]
+    pub username: Option<String>,
+
+    /// Password for proxy authentication
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// Read-only HTTP JSON API settings (`[http]` section)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpApiConfig {
+    /// Whether to start the HTTP API alongside gRPC
+    #[serde(default)]
+    pub enable: bool,
+
+    /// Port for the HTTP API (bound on the same host as gRPC)
+    #[serde(default = "default_http_port")]
+    pub port: u16,
+}
+
+/// Satellites whose status changes are pushed to frontends (`[watchlist]` section)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchlistConfig {
+    /// Watched satellite names or aliases (empty = notifications off)
+    #[serde(default)]
+    pub satellites: Vec<String>,
+
+    /// Minutes a new status must persist before it is announced
+    #[serde(default = "default_watch_debounce_minutes")]
+    pub debounce_minutes: u64,
+}
+
+/// User report submission limits (`[reports]` section)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportLimitConfig {
+    /// Reports one user may submit per window (0 = unlimited)
+    #[serde(default = "default_max_reports_per_user")]
+    pub max_per_user: usize,
+
+    /// Reports one group may submit per window across all its users (0 = unlimited)
+    #[serde(default = "default_max_reports_per_group")]
+    pub max_per_group: usize,
+
+    /// Length of the sliding window in minutes
+    #[serde(default = "default_report_window_minutes")]
+    pub window_minutes: u64,
+}
+
+/// Daily "satellite of the day" broadcast (`[daily_post]` section)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyPostConfig {
+    /// Whether to post a satellite of the day to frontends
+    #[serde(default)]
+    pub enable: bool,
+
+    /// Local time of the post, "HH:MM"
+    #[serde(default = "default_daily_post_time")]
+    pub post_time: String,
+
+    /// Timezone of `post_time` as a fixed UTC offset, e.g. "+08:00"
+    #[serde(default = "default_daily_post_utc_offset")]
+    pub utc_offset: String,
+}
+
+/// Image rendering settings (`[render]` section)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderConfig {
+    /// Whether to draw the footer band at the bottom of rendered images
+    #[serde(default = "default_true")]
+    pub footer_enabled: bool,
+
+    /// Branding text shown in the footer before the render timestamp
+    #[serde(default = "default_footer_text")]
+    pub footer_text: String,
+
+    /// Message shown when a query produced no satellites
+    #[serde(default = "default_empty_message")]
+    pub empty_message: String,
+
+    /// Message shown in a satellite block that has no reports
+    #[serde(default = "default_no_reports_message")]
+    pub no_reports_message: String,
+
+    /// Prefix for the suggestion line in the empty state
+    #[serde(default = "default_suggestion_prefix")]
+    pub suggestion_prefix: String,
+
+    /// Maximum satellites rendered for one query; extra matches are dropped
+    #[serde(default = "default_max_satellites_per_query")]
+    pub max_satellites_per_query: usize,
+
+    /// Note shown when matches were dropped; `{count}` is replaced by their number
+    #[serde(default = "default_more_results_message")]
+    pub more_results_message: String,
+
+    /// Maximum number of images rasterized at the same time
+    #[serde(default = "default_max_concurrent_renders")]
+    pub max_concurrent_renders: usize,
+
+    /// Recompress rendered PNGs with oxipng at this level, 0 (fast) to 6
+    /// (smallest); costs CPU per image (unset = tiny-skia's encoding as is)
+    #[serde(default)]
+    pub png_compression_level: Option<u8>,
+
+    /// Maidenhead locator of the observer (e.g. "OM89")
+    #[serde(default)]
+    pub observer_grid: Option<String>,
+
+    /// Whether to add a "Dist/Brg" column computed from `observer_grid`
+    #[serde(default)]
+    pub show_distance: bool,
+
+    /// Show only the prefix of each callsign, e.g. "BG5…" for "BG5FNA"
+    #[serde(default)]
+    pub redact_callsigns: bool,
+
+    /// Cut grids to 4 characters ("OM89"); distances use the cut grid too
+    #[serde(default)]
+    pub redact_grids: bool,
+
+    /// Layout for satellite query images: "full", "compact" or "merged"
+    #[serde(default)]
+    pub layout: RenderLayout,
+
+    /// Collapse consecutive reports with the same callsign, grid and status
+    /// within this many minutes into one row with a count (0 = off)
+    #[serde(default)]
+    pub collapse_window_minutes: u64,
+
+    /// Sort report rows by grid square instead of time
+    #[serde(default)]
+    pub sort_by_grid: bool,
+
+    /// Days after being added during which a satellite shows a "NEW" badge
+    /// (0 = no badge)
+    #[serde(default = "default_new_badge_days")]
+    pub new_badge_days: u64,
+
+    /// Mark report rows that arrived since the group's previous image of the
+    /// same query with an accent bar
+    #[serde(default)]
+    pub highlight_new_reports: bool,
+
+    /// Maximum rows in the "merged" layout across all satellites
+    #[serde(default = "default_merged_max_rows")]
+    pub merged_max_rows: usize,
+
+    /// In the full layout, satellites with fewer report rows than this get
+    /// one line in a compact table instead of a full block (0 = off)
+    #[serde(default)]
+    pub min_reports_per_block: usize,
+
+    /// Note under a merged table cut by `merged_max_rows`; `{count}` is
+    /// replaced by the number of reports left out
+    #[serde(default = "default_merged_more_message")]
+    pub merged_more_message: String,
+
+    /// Status colors: "default" or "cvd" (color-vision-deficiency friendly)
+    #[serde(default)]
+    pub palette: ColorPalette,
+
+    /// Status color legend: "off", "top" or "bottom"
+    #[serde(default)]
+    pub legend: LegendPosition,
+
+    /// Satellite order in multi-satellite images:
+    /// "relevance", "alphabetical", "norad", "recency" or "severity"
+    #[serde(default)]
+    pub sort_order: SortOrder,
+
+    /// Name query images by a hash of their content, so identical data
+    /// always maps to the same URL. Images then leave out everything tied to
+    /// render time: report ages and age colors, NEW badges and the footer's
+    /// render timestamp (absolute report times are still shown)
+    #[serde(default)]
+    pub content_hash_filenames: bool,
+
+    /// Report age (hours) at or below which the time marker is fully green
+    #[serde(default)]
+    pub time_color_min_hours: f64,
+
+    /// Report age (hours) at or above which the time marker is fully red
+    #[serde(default = "default_time_color_max_hours")]
+    pub time_color_max_hours: f64,
+
+    /// UTC offset (e.g. "+08:00") of a local time shown next to each UTC
+    /// report time, as "09:30Z / 17:30 BJT" (unset = raw UTC only)
+    #[serde(default)]
+    pub local_time_offset: Option<String>,
+
+    /// Label after the local report time
+    #[serde(default = "default_local_time_label")]
+    pub local_time_label: String,
+
+    /// Overlay column guides, text baselines and block boundaries on
+    /// rendered images (for tuning the layout constants)
+    #[serde(default)]
+    pub debug_overlay: bool,
+
+    /// SVG template for satellite images, relative to the working directory
+    #[serde(default = "default_template_path")]
+    pub template_path: String,
+
+    /// Reload the SVG template when it changes on disk (for template development)
+    #[serde(default)]
+    pub watch_templates: bool,
+
+    /// Directory rendered images are written to, created at startup
+    /// (the media server's `media_directory` should point here)
+    #[serde(default = "default_output_dir")]
+    pub output_dir: String,
 }
 
 fn default_host() -> String {
@@ -21,16 +323,239 @@ fn default_port() -> u16 {
     50051
 }
 
+fn default_http_port() -> u16 {
+    50052
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_footer_text() -> String {
+    "Powered by Rinko".to_string()
+}
+
+fn default_empty_message() -> String {
+    "No satellite data available.".to_string()
+}
+
+fn default_no_reports_message() -> String {
+    "No reports available.".to_string()
+}
+
+fn default_suggestion_prefix() -> String {
+    "Did you mean:".to_string()
+}
+
+fn default_max_satellites_per_query() -> usize {
+    5
+}
+
+fn default_more_results_message() -> String {
+    "...and {count} more, refine your query".to_string()
+}
+
+fn default_time_color_max_hours() -> f64 {
+    12.0
+}
+
+fn default_local_time_label() -> String {
+    "BJT".to_string()
+}
+
+fn default_new_badge_days() -> u64 {
+    7
+}
+
+fn default_merged_max_rows() -> usize {
+    15
+}
+
+fn default_merged_more_message() -> String {
+    "…and {count} more reports".to_string()
+}
+
+fn default_template_path() -> String {
+    DEFAULT_TEMPLATE_PATH.to_string()
+}
+
+fn default_output_dir() -> String {
+    "data/satellite_cache/rendered_images".to_string()
+}
+
+fn default_max_concurrent_renders() -> usize {
+    crate::module::sat::DEFAULT_MAX_CONCURRENT_RENDERS
+}
+
 fn default_log_level() -> String {
     "info".to_string()
 }
 
+fn default_max_reports_per_user() -> usize {
+    5
+}
+
+fn default_max_reports_per_group() -> usize {
+    30
+}
+
+fn default_report_window_minutes() -> u64 {
+    10
+}
+
+fn default_daily_post_time() -> String {
+    "20:00".to_string()
+}
+
+fn default_daily_post_utc_offset() -> String {
+    "+08:00".to_string()
+}
+
+fn default_watch_debounce_minutes() -> u64 {
+    30
+}
+
+fn default_request_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_future_report_tolerance_minutes() -> i64 {
+    5
+}
+
+fn default_fetch_window_hours() -> u64 {
+    1
+}
+
+fn default_quiet_fetch_window_hours() -> u64 {
+    6
+}
+
+fn default_max_catch_up_hours() -> u64 {
+    48
+}
+
+fn default_retention_hours() -> u64 {
+    48
+}
+
+fn default_max_requests_per_cycle() -> u32 {
+    500
+}
+
+fn default_amsat_api_url() -> String {
+    DEFAULT_AMSAT_API_URL.to_string()
+}
+
 impl Default for BackendConfig {
     fn default() -> Self {
         Self {
             host: default_host(),
             port: default_port(),
             log_level: default_log_level(),
+            request_timeout_seconds: default_request_timeout_seconds(),
+            satellite: SatelliteConfig::default(),
+            http: HttpApiConfig::default(),
+            render: RenderConfig::default(),
+            reports: ReportLimitConfig::default(),
+            watchlist: WatchlistConfig::default(),
+            daily_post: DailyPostConfig::default(),
+            proxy: ProxyConfig::default(),
+        }
+    }
+}
+
+impl Default for RenderConfig {
+    fn default() -> Self {
+        Self {
+            footer_enabled: true,
+            footer_text: default_footer_text(),
+            empty_message: default_empty_message(),
+            no_reports_message: default_no_reports_message(),
+            suggestion_prefix: default_suggestion_prefix(),
+            max_satellites_per_query: default_max_satellites_per_query(),
+            more_results_message: default_more_results_message(),
+            max_concurrent_renders: default_max_concurrent_renders(),
+            png_compression_level: None,
+            observer_grid: None,
+            show_distance: false,
+            redact_callsigns: false,
+            redact_grids: false,
+            layout: RenderLayout::default(),
+            collapse_window_minutes: 0,
+            sort_by_grid: false,
+            new_badge_days: default_new_badge_days(),
+            highlight_new_reports: false,
+            merged_max_rows: default_merged_max_rows(),
+            min_reports_per_block: 0,
+            merged_more_message: default_merged_more_message(),
+            palette: ColorPalette::default(),
+            legend: LegendPosition::default(),
+            sort_order: SortOrder::default(),
+            content_hash_filenames: false,
+            time_color_min_hours: 0.0,
+            time_color_max_hours: default_time_color_max_hours(),
+            local_time_offset: None,
+            local_time_label: default_local_time_label(),
+            debug_overlay: false,
+            template_path: default_template_path(),
+            watch_templates: false,
+            output_dir: default_output_dir(),
+        }
+    }
+}
+
+impl Default for DailyPostConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            post_time: default_daily_post_time(),
+            utc_offset: default_daily_post_utc_offset(),
+        }
+    }
+}
+
+impl Default for WatchlistConfig {
+    fn default() -> Self {
+        Self {
+            satellites: Vec::new(),
+            debounce_minutes: default_watch_debounce_minutes(),
+        }
+    }
+}
+
+impl Default for ReportLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_per_user: default_max_reports_per_user(),
+            max_per_group: default_max_reports_per_group(),
+            window_minutes: default_report_window_minutes(),
+        }
+    }
+}
+
+impl Default for HttpApiConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            port: default_http_port(),
+        }
+    }
+}
+
+impl Default for SatelliteConfig {
+    fn default() -> Self {
+        Self {
+            future_report_tolerance_minutes: default_future_report_tolerance_minutes(),
+            fetch_window_hours: default_fetch_window_hours(),
+            quiet_fetch_window_hours: default_quiet_fetch_window_hours(),
+            max_catch_up_hours: default_max_catch_up_hours(),
+            retention_hours: default_retention_hours(),
+            amsat_api_url: default_amsat_api_url(),
+            fixture_dir: None,
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            max_requests_per_cycle: default_max_requests_per_cycle(),
         }
     }
 }
@@ -57,4 +582,12 @@ impl BackendConfig {
     pub fn server_address(&self) -> String {
         format!("{}:{}", self.host, self.port)
     }
+
+    pub fn request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.request_timeout_seconds)
+    }
+
+    pub fn http_address(&self) -> String {
+        format!("{}:{}", self.host, self.http.port)
+    }
 }
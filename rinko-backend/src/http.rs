@@ -0,0 +1,219 @@
+//! Read-only HTTP JSON API for tooling that cannot speak gRPC
+//!
+//! Endpoints:
+//! - `GET /health` - 503 when satellite data is stale; lists unrecovered source errors
+//! - `GET /satellites` - all known satellites
+//! - `GET /satellites/search?q=` - search by name, alias or keyword
+//! - `GET /satellites/{api_name}` - a single satellite by AMSAT name
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::module::sat::{SatelliteManager, SourceError};
+
+#[derive(Deserialize)]
+struct SearchParams {
+    #[serde(default)]
+    q: String,
+}
+
+/// Build the HTTP API router
+pub fn router(satellite_manager: Arc<SatelliteManager>) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/satellites", get(list_satellites))
+        .route("/satellites/search", get(search_satellites))
+        .route("/satellites/{api_name}", get(get_satellite))
+        .with_state(satellite_manager)
+}
+
+/// Serve the HTTP API on the given address until the listener fails
+pub async fn serve(satellite_manager: Arc<SatelliteManager>, addr: &str) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(satellite_manager)).await?;
+    Ok(())
+}
+
+/// Body of `GET /health`
+#[derive(Debug, Serialize)]
+struct HealthStatus {
+    healthy: bool,
+    last_update: Option<DateTime<Utc>>,
+    /// Seconds since `last_update` (absent when no data is loaded)
+    staleness_seconds: Option<i64>,
+    /// Staleness above which the instance is reported unhealthy
+    max_staleness_seconds: i64,
+    /// Last failure of each data source that has not recovered since
+    source_errors: Vec<SourceError>,
+}
+
+impl HealthStatus {
+    /// Data is healthy while the last update is within two update intervals
+    fn evaluate(last_update: Option<DateTime<Utc>>, interval_minutes: i64, now: DateTime<Utc>) -> Self {
+        let max_staleness_seconds = 2 * interval_minutes * 60;
+        let staleness_seconds = last_update.map(|t| (now - t).num_seconds().max(0));
+        Self {
+            healthy: staleness_seconds.is_some_and(|s| s <= max_staleness_seconds),
+            last_update,
+            staleness_seconds,
+            max_staleness_seconds,
+            source_errors: Vec::new(),
+        }
+    }
+}
+
+/// GET /health
+async fn health(State(manager): State<Arc<SatelliteManager>>) -> Response {
+    let status = HealthStatus {
+        source_errors: manager.source_errors().await,
+        ..HealthStatus::evaluate(manager.last_update().await, manager.update_interval_minutes(), Utc::now())
+    };
+    let code = if status.healthy {
+        StatusCode::OK
+    } else {
+        tracing::warn!("Health check failed: satellite data is stale ({:?})", status);
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (code, Json(status)).into_response()
+}
+
+/// GET /satellites
+async fn list_satellites(State(manager): State<Arc<SatelliteManager>>) -> Response {
+    let mut satellites = manager.get_all_satellites().await;
+    satellites.sort_by(|a, b| a.name.cmp(&b.name));
+    Json(satellites).into_response()
+}
+
+/// GET /satellites/search?q=
+async fn search_satellites(
+    State(manager): State<Arc<SatelliteManager>>,
+    Query(params): Query<SearchParams>,
+) -> Response {
+    let query = params.q.trim();
+    if query.is_empty() {
+        return error_response(StatusCode::BAD_REQUEST, "Missing query parameter 'q'");
+    }
+
+    match manager.search_satellites(query).await {
+        Ok(satellites) => Json(satellites).into_response(),
+        Err(e) => {
+            tracing::error!("HTTP search for '{}' failed: {}", query, e);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, format!("Search failed: {}", e))
+        }
+    }
+}
+
+/// GET /satellites/{api_name}
+async fn get_satellite(
+    State(manager): State<Arc<SatelliteManager>>,
+    Path(api_name): Path<String>,
+) -> Response {
+    match manager.query_satellite(&api_name).await {
+        Ok(Some(satellite)) => Json(satellite).into_response(),
+        Ok(None) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("Satellite '{}' not found", api_name),
+        ),
+        Err(e) => {
+            tracing::error!("HTTP query for '{}' failed: {}", api_name, e);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, format!("Query failed: {}", e))
+        }
+    }
+}
+
+/// JSON error body: `{"error": "..."}`
+fn error_response(status: StatusCode, message: impl Into<String>) -> Response {
+    (status, Json(serde_json::json!({ "error": message.into() }))).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::sat::test_support::manager_with;
+    use crate::module::sat::{SatelliteEntry, SatelliteInfo};
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    async fn create_test_manager(dir_name: &str) -> Arc<SatelliteManager> {
+        let cache = vec![SatelliteInfo::new("ISS-FM"), SatelliteInfo::new("AO-91")];
+        create_test_manager_with_cache(dir_name, cache).await
+    }
+
+    async fn create_test_manager_with_cache(
+        dir_name: &str,
+        cache: Vec<SatelliteInfo>,
+    ) -> Arc<SatelliteManager> {
+        let mut iss = SatelliteEntry::new("ISS-FM");
+        iss.aliases = vec!["ISS".to_string()];
+        manager_with(dir_name, vec![iss, SatelliteEntry::new("AO-91")], cache).await
+    }
+
+    async fn get_json(app: Router, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_search_endpoint() {
+        let manager = create_test_manager("rinko_test_http_search").await;
+
+        let (status, body) = get_json(router(manager), "/satellites/search?q=iss").await;
+        assert_eq!(status, StatusCode::OK);
+        let results = body.as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["name"], "ISS-FM");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_satellite_is_not_found() {
+        let manager = create_test_manager("rinko_test_http_not_found").await;
+
+        let (status, body) = get_json(router(manager), "/satellites/NOPE-1").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(body["error"].as_str().unwrap().contains("NOPE-1"));
+    }
+
+    #[tokio::test]
+    async fn test_health_fresh_data() {
+        let manager = create_test_manager("rinko_test_http_health_fresh").await;
+
+        let (status, body) = get_json(router(manager), "/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["healthy"], true);
+        assert_eq!(body["max_staleness_seconds"], 1200);
+    }
+
+    #[tokio::test]
+    async fn test_health_stale_data() {
+        let mut stale = SatelliteInfo::new("AO-91");
+        stale.last_updated = Utc::now() - chrono::Duration::hours(1);
+        let manager = create_test_manager_with_cache("rinko_test_http_health_stale", vec![stale]).await;
+
+        let (status, body) = get_json(router(manager), "/health").await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["healthy"], false);
+        assert!(body["staleness_seconds"].as_i64().unwrap() >= 3600);
+    }
+
+    #[test]
+    fn test_health_without_data_is_unhealthy() {
+        let status = HealthStatus::evaluate(None, 10, Utc::now());
+        assert!(!status.healthy);
+        assert_eq!(status.staleness_seconds, None);
+    }
+}
@@ -0,0 +1,158 @@
+//! Shared outbound HTTP client
+//!
+//! `reqwest::Client` keeps its connection pool internally, so creating a
+//! client per request throws away every kept-alive connection. All AMSAT
+//! API calls and scrapes go through this one lazily built client instead.
+//! A proxy from the config must be set with [`configure_proxy`] before the
+//! first request.
+use crate::config::ProxyConfig;
+use anyhow::{Context, Result};
+use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
+
+const REQUEST_TIMEOUT_SECONDS: u64 = 60;
+
+static PROXY: OnceLock<ProxyConfig> = OnceLock::new();
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    build_client(PROXY.get().unwrap_or(&ProxyConfig::default()))
+        .expect("Failed to build shared HTTP client")
+});
+
+/// Route the shared client through the configured proxy
+///
+/// Validates the proxy by building a client, so a bad URL fails at startup
+/// rather than on the first fetch. Has no effect after the first request.
+pub fn configure_proxy(config: &ProxyConfig) -> Result<()> {
+    build_client(config)?;
+    if let Some(url) = &config.url {
+        tracing::info!("Outbound requests go through proxy {}", redact_proxy_url(url));
+    }
+    if PROXY.set(config.clone()).is_err() {
+        tracing::warn!("HTTP proxy already configured, ignoring the new setting");
+    }
+    Ok(())
+}
+
+/// Build a client with the shared settings from `rinko_common`
+///
+/// Without a configured URL reqwest reads the proxy environment variables.
+fn build_client(proxy: &ProxyConfig) -> Result<reqwest::Client> {
+    let proxy = match &proxy.url {
+        Some(url) => {
+            let mut all = reqwest::Proxy::all(url)
+                .with_context(|| format!("Invalid proxy URL '{}'", redact_proxy_url(url)))?;
+            if let Some(username) = &proxy.username {
+                all = all.basic_auth(username, proxy.password.as_deref().unwrap_or_default());
+            }
+            Some(all)
+        }
+        None => None,
+    };
+    rinko_common::http_client::build_client(Duration::from_secs(REQUEST_TIMEOUT_SECONDS), proxy)
+        .context("Failed to build HTTP client")
+}
+
+/// Proxy URL without credentials, for logs
+fn redact_proxy_url(url: &str) -> String {
+    match reqwest::Url::parse(url) {
+        Ok(mut parsed) if !parsed.username().is_empty() || parsed.password().is_some() => {
+            let _ = parsed.set_username("");
+            let _ = parsed.set_password(None);
+            parsed.to_string()
+        }
+        _ => url.to_string(),
+    }
+}
+
+/// The process-wide HTTP client
+///
+/// Cloning it is cheap and clones share the same connection pool.
+pub fn shared_client() -> &'static reqwest::Client {
+    &CLIENT
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, extract::ConnectInfo, routing::get};
+    use std::collections::HashSet;
+    use std::net::SocketAddr;
+    use std::sync::{Arc, Mutex};
+
+    #[tokio::test]
+    async fn test_connections_are_reused() {
+        // Record the client port of every request: one port = one connection
+        let peers = Arc::new(Mutex::new(HashSet::new()));
+        let recorded = peers.clone();
+        let app = Router::new().route(
+            "/",
+            get(move |ConnectInfo(peer): ConnectInfo<SocketAddr>| {
+                recorded.lock().unwrap().insert(peer);
+                async { "ok" }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                .await
+                .unwrap()
+        });
+
+        for _ in 0..5 {
+            let body = shared_client().get(&url).send().await.unwrap().text().await.unwrap();
+            assert_eq!(body, "ok");
+        }
+
+        assert_eq!(peers.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_requests_go_through_configured_proxy() {
+        use axum::http::{HeaderMap, Uri};
+
+        // A plain HTTP proxy receives the absolute target URL
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let app = Router::new().fallback(move |uri: Uri, headers: HeaderMap| {
+            let auth = headers
+                .get("proxy-authorization")
+                .map(|v| v.to_str().unwrap().to_string());
+            recorded.lock().unwrap().push((uri.to_string(), auth));
+            async { "proxied" }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let client = build_client(&ProxyConfig {
+            url: Some(proxy_url),
+            username: Some("rinko".to_string()),
+            password: Some("secret".to_string()),
+        })
+        .unwrap();
+        let body = client
+            .get("http://amsat.invalid/status/api/v1/sat_info.php")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+
+        assert_eq!(body, "proxied");
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].0, "http://amsat.invalid/status/api/v1/sat_info.php");
+        // base64("rinko:secret")
+        assert_eq!(seen[0].1.as_deref(), Some("Basic cmlua286c2VjcmV0"));
+    }
+
+    #[test]
+    fn test_invalid_proxy_and_redaction() {
+        let invalid = ProxyConfig { url: Some("not a url".to_string()), ..Default::default() };
+        assert!(build_client(&invalid).is_err());
+        assert_eq!(redact_proxy_url("socks5://user:pw@proxy:1080"), "socks5://proxy:1080");
+        assert_eq!(redact_proxy_url("http://proxy:3128"), "http://proxy:3128");
+    }
+}
@@ -1,4 +1,6 @@
 pub mod module;
 pub mod service;
+pub mod http;
+pub mod http_client;
 pub mod config;
 pub mod logging;
@@ -1,7 +1,10 @@
 use rinko_backend::config;
 use rinko_backend::service;
-use rinko_backend::module::sat::SatelliteManager;
+use rinko_backend::module::sat::{RenderLimiter, SatelliteManager, ensure_images_dir, watch_templates};
 use rinko_backend::module::scheduled::{ScheduledTaskManager, ScheduledTaskConfig};
+use rinko_backend::module::daily_post::DailyPoster;
+use rinko_backend::module::subscription::{SubscriptionPoster, SubscriptionStore};
+use rinko_backend::module::watchlist::{StatusNotifier, StatusWatcher};
 
 use anyhow::Result;
 use tonic::transport::Server;
@@ -25,12 +28,37 @@ async fn main() -> Result<()> {
     tracing::info!("Rinko Backend starting...");
     tracing::info!("Server will listen on {}", config.server_address());
 
+    // Outbound proxy, before anything fetches from upstream
+    rinko_backend::http_client::configure_proxy(&config.proxy)?;
+
+    // Bound CPU-heavy image rendering
+    RenderLimiter::init_global(config.render.max_concurrent_renders);
+    tracing::info!(
+        "Image rendering limited to {} concurrent renders",
+        config.render.max_concurrent_renders
+    );
+
+    // Reload SVG templates on change when developing them
+    let _template_watcher = if config.render.watch_templates {
+        Some(watch_templates(std::path::Path::new(&config.render.template_path))?)
+    } else {
+        None
+    };
+
+    // Renderers write straight into the output directory, so create it once here
+    ensure_images_dir(std::path::Path::new(&config.render.output_dir)).await?;
+    tracing::info!("Rendered images go to {}", config.render.output_dir);
+
     // Initialize satellite manager
     tracing::info!("Initializing satellite manager...");
     let cache_dir = "data/satellite_cache";
     let update_interval_minutes = 10; // Update every 10 minutes
     
-    let satellite_manager = SatelliteManager::new(cache_dir, update_interval_minutes as i64)?;
+    let satellite_manager = SatelliteManager::with_config(
+        cache_dir,
+        update_interval_minutes as i64,
+        config.satellite.clone(),
+    )?;
     
     // Initialize satellite manager (load cache and configuration)
     satellite_manager.initialize().await?;
@@ -41,16 +69,76 @@ async fn main() -> Result<()> {
         satellite_update_interval_minutes: update_interval_minutes,
         image_cleanup_interval_hours: 24, // Clean images daily
         image_retention_days: 1, // Keep images for 1 day
-        cache_dir: cache_dir.to_string(),
+        images_dir: config.render.output_dir.clone(),
         perform_initial_update: true, // Perform initial update immediately
     };
     
     let mut task_manager = ScheduledTaskManager::new(task_config, satellite_manager.clone());
+
+    // Status-change notifications for watched satellites
+    let status_changes = if config.watchlist.satellites.is_empty() {
+        None
+    } else {
+        tracing::info!("Watching satellites: {}", config.watchlist.satellites.join(", "));
+        let (tx, rx) = tokio::sync::mpsc::channel(32);
+        let watcher = StatusWatcher::new(&config.watchlist);
+        task_manager = task_manager.with_status_notifier(StatusNotifier::new(watcher, tx));
+        Some(rx)
+    };
+
+    // Optional satellite of the day
+    let daily_posts = if config.daily_post.enable {
+        tracing::info!(
+            "Posting a satellite of the day at {} (UTC{})",
+            config.daily_post.post_time,
+            config.daily_post.utc_offset
+        );
+        let (tx, rx) = tokio::sync::mpsc::channel(4);
+        let poster = DailyPoster::new(config.daily_post.clone(), config.render.clone(), tx);
+        task_manager = task_manager.with_daily_poster(poster);
+        Some(rx)
+    } else {
+        None
+    };
+
+    // Periodic posts for groups' /subscribe subscriptions
+    let subscriptions = std::sync::Arc::new(
+        SubscriptionStore::load(std::path::Path::new(cache_dir).join("subscriptions.json")).await?,
+    );
+    let (subscription_tx, subscription_posts) = tokio::sync::mpsc::channel(16);
+    task_manager = task_manager.with_subscription_poster(SubscriptionPoster::new(
+        subscriptions.clone(),
+        config.render.clone(),
+        subscription_tx,
+    ));
+
     task_manager.start_all().await?;
     tracing::info!("All scheduled tasks started successfully");
 
+    // Start the optional HTTP JSON API
+    if config.http.enable {
+        let http_manager = satellite_manager.clone();
+        let http_addr = config.http_address();
+        tracing::info!("HTTP API starting on {}", http_addr);
+        tokio::spawn(async move {
+            if let Err(e) = rinko_backend::http::serve(http_manager, &http_addr).await {
+                tracing::error!("HTTP API server error: {}", e);
+            }
+        });
+    }
+
     // Create gRPC service with satellite manager
-    let bot_service = BotBackendService::new(satellite_manager);
+    let bot_service = BotBackendService::new(satellite_manager, config.render.clone())
+        .with_request_timeout(config.request_timeout())
+        .with_report_limits(config.reports.clone())
+        .with_subscriptions(subscriptions);
+    bot_service.forward_subscription_posts(subscription_posts);
+    if let Some(rx) = status_changes {
+        bot_service.forward_status_changes(rx);
+    }
+    if let Some(rx) = daily_posts {
+        bot_service.forward_daily_posts(rx);
+    }
     let server_addr = config.server_address().parse()?;
 
     tracing::info!("gRPC server starting on {}", server_addr);
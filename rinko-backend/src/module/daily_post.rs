@@ -0,0 +1,185 @@
+//! "Satellite of the day" posts
+//!
+//! Once a day at the configured local time one active satellite is picked,
+//! rotating through the active list by date, and its status image is
+//! broadcast to frontends with a short blurb. The pick only depends on the
+//! date and the active list, so every restart agrees on today's satellite.
+use chrono::{DateTime, Datelike, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+use tokio::sync::mpsc;
+
+use super::sat::{SatelliteInfo, SatelliteManager, SatelliteRenderer};
+use crate::config::{DailyPostConfig, RenderConfig};
+
+/// A rendered satellite of the day, ready to broadcast
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyPost {
+    pub satellite: String,
+    pub message: String,
+    /// Local path of the status image, if rendering succeeded
+    pub image: Option<String>,
+}
+
+/// Satellite of the day among the active satellites
+///
+/// Active satellites are taken in name order and the day number picks one,
+/// so consecutive days walk through the list.
+pub fn pick_satellite(satellites: &[SatelliteInfo], date: NaiveDate) -> Option<&SatelliteInfo> {
+    let mut active: Vec<&SatelliteInfo> = satellites.iter().filter(|s| s.is_active).collect();
+    if active.is_empty() {
+        return None;
+    }
+    active.sort_by(|a, b| a.name.cmp(&b.name));
+    let index = date.num_days_from_ce().rem_euclid(active.len() as i32) as usize;
+    Some(active[index])
+}
+
+/// Short text sent with the image
+pub fn blurb(sat: &SatelliteInfo) -> String {
+    let mut message = format!("🛰️ Satellite of the day: {}", sat.name);
+    if !sat.aliases.is_empty() {
+        message.push_str(&format!("\nAlso known as: {}", sat.aliases.join(", ")));
+    }
+    if let Some(catalog) = &sat.catalog_number {
+        message.push_str(&format!("\nCatalog: {}", catalog));
+    }
+    match sat.latest_status() {
+        Some(status) => message.push_str(&format!("\nLatest status: {}", status)),
+        None => message.push_str("\nNo recent reports, be the first to try a pass!"),
+    }
+    message.push_str(&format!("\nSend /q {} for the latest reports", sat.name));
+    message
+}
+
+/// Renders the daily post and hands it to the service for broadcasting
+#[derive(Clone)]
+pub struct DailyPoster {
+    config: DailyPostConfig,
+    render_config: RenderConfig,
+    tx: mpsc::Sender<DailyPost>,
+}
+
+impl DailyPoster {
+    pub fn new(config: DailyPostConfig, render_config: RenderConfig, tx: mpsc::Sender<DailyPost>) -> Self {
+        Self { config, render_config, tx }
+    }
+
+    /// Configured post time and timezone, or None if either is invalid
+    fn schedule(&self) -> Option<(NaiveTime, FixedOffset)> {
+        let time = NaiveTime::parse_from_str(&self.config.post_time, "%H:%M").ok();
+        let offset = self.config.utc_offset.parse::<FixedOffset>().ok();
+        time.zip(offset)
+    }
+
+    /// Next post strictly after `now`, with the local date it is for
+    ///
+    /// Returns None, and logs, when the post time or offset is invalid.
+    pub fn next_post(&self, now: DateTime<Utc>) -> Option<(DateTime<Utc>, NaiveDate)> {
+        let Some((time, offset)) = self.schedule() else {
+            tracing::warn!(
+                "Daily post disabled: invalid post time '{}' or UTC offset '{}'",
+                self.config.post_time,
+                self.config.utc_offset
+            );
+            return None;
+        };
+
+        let mut date = now.with_timezone(&offset).date_naive();
+        loop {
+            let at = offset.from_local_datetime(&date.and_time(time)).single()?.with_timezone(&Utc);
+            if at > now {
+                return Some((at, date));
+            }
+            date += Duration::days(1);
+        }
+    }
+
+    /// Pick, render and send the post for `date`
+    pub async fn post(&self, manager: &SatelliteManager, date: NaiveDate) {
+        let satellites = manager.get_all_satellites().await;
+        let Some(sat) = pick_satellite(&satellites, date) else {
+            tracing::info!("No active satellites, skipping the daily post for {}", date);
+            return;
+        };
+
+        let renderer = SatelliteRenderer::with_config(&self.render_config.output_dir, self.render_config.clone());
+        let image = match renderer.render_satellites(std::slice::from_ref(sat), self.render_config.layout).await {
+            Ok(path) => Some(path.to_string_lossy().to_string()),
+            Err(e) => {
+                tracing::warn!("Daily post image for {} failed, sending text only: {}", sat.name, e);
+                None
+            }
+        };
+
+        let post = DailyPost {
+            satellite: sat.name.clone(),
+            message: blurb(sat),
+            image,
+        };
+        tracing::info!("Satellite of the day for {}: {}", date, post.satellite);
+        if let Err(e) = self.tx.send(post).await {
+            tracing::warn!("Dropping daily post: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satellites() -> Vec<SatelliteInfo> {
+        let mut inactive = SatelliteInfo::new("AO-07");
+        inactive.is_active = false;
+        vec![
+            SatelliteInfo::new("SO-50"),
+            inactive,
+            SatelliteInfo::new("AO-91"),
+            SatelliteInfo::new("ISS-FM"),
+        ]
+    }
+
+    #[test]
+    fn test_pick_is_deterministic_and_rotates_daily() {
+        let satellites = satellites();
+        let day = NaiveDate::from_ymd_opt(2026, 2, 16).unwrap();
+        let pick = |date| pick_satellite(&satellites, date).unwrap().name.clone();
+
+        assert_eq!(pick(day), pick(day));
+        // Order of the input list does not matter
+        let mut reversed = satellites.clone();
+        reversed.reverse();
+        assert_eq!(pick_satellite(&reversed, day).unwrap().name, pick(day));
+
+        // Consecutive days walk through the active satellites by name
+        let active = ["AO-91", "ISS-FM", "SO-50"];
+        let today = active.iter().position(|name| *name == pick(day)).unwrap();
+        for offset in 1..=3 {
+            let next = pick(day + Duration::days(offset));
+            assert_eq!(next, active[(today + offset as usize) % active.len()]);
+        }
+
+        assert!(pick_satellite(&[], day).is_none());
+    }
+
+    #[test]
+    fn test_next_post_in_local_time() {
+        let (tx, _rx) = mpsc::channel(1);
+        let poster = DailyPoster::new(DailyPostConfig::default(), RenderConfig::default(), tx);
+        let at = |rfc3339: &str| DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc);
+
+        // 20:00 BJT is 12:00Z
+        assert_eq!(
+            poster.next_post(at("2026-02-16T11:59:00Z")),
+            Some((at("2026-02-16T12:00:00Z"), NaiveDate::from_ymd_opt(2026, 2, 16).unwrap()))
+        );
+        assert_eq!(
+            poster.next_post(at("2026-02-16T12:00:00Z")),
+            Some((at("2026-02-17T12:00:00Z"), NaiveDate::from_ymd_opt(2026, 2, 17).unwrap()))
+        );
+
+        let invalid = DailyPoster {
+            config: DailyPostConfig { post_time: "8pm".to_string(), ..Default::default() },
+            ..poster
+        };
+        assert_eq!(invalid.next_post(at("2026-02-16T11:59:00Z")), None);
+    }
+}
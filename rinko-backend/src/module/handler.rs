@@ -1,20 +1,54 @@
-///! Handles requests related to the model.
-use rinko_common::proto::{UnifiedMessage, MessageResponse, ContentType};
+//! Handles requests related to the model.
+use rinko_common::proto::{UnifiedMessage, MessageResponse, ContentType, ImageResponse, ReportSubmission};
 use regex::Regex;
 use anyhow::Result;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-use super::sat::{SatelliteManager, SatelliteInfo, SatelliteRenderer};
+use super::sat::{AmsatReport, RenderLayout, ReportHistory, SatelliteManager, SatelliteInfo, SatelliteRenderer};
+use super::subscription::{Cadence, SubscribeOutcome, SubscriptionStore, MAX_SUBSCRIPTIONS_PER_GROUP};
+use crate::config::RenderConfig;
+
+/// Reply to a query with no satellite name, kept apart from "not found"
+const QUERY_USAGE: &str = "Usage: /q <satellite name>, e.g. /q AO-91";
+
+/// Reply to a comparison without exactly two satellite names
+const COMPARE_USAGE: &str = "Usage: /compare <satellite> <satellite>, e.g. /compare AO-91 SO-50";
+
+/// Reply to a subscription command without a satellite name
+const SUBSCRIBE_USAGE: &str = "Usage: /subscribe <satellite> [hourly|daily], e.g. /subscribe AO-91 hourly";
+const UNSUBSCRIBE_USAGE: &str = "Usage: /unsubscribe <satellite>, e.g. /unsubscribe AO-91";
+
+/// Reply to subscription commands sent outside a group
+const SUBSCRIPTIONS_GROUPS_ONLY: &str = "Subscriptions are only available in groups.";
 
 /// Message handler with satellite manager
+#[derive(Clone)]
 pub struct MessageHandler {
     satellite_manager: Arc<SatelliteManager>,
+    render_config: RenderConfig,
+    /// Reports in each conversation's last image of each query, for `highlight_new_reports`
+    report_history: Arc<ReportHistory>,
+    /// Groups' periodic status posts, managed with `/subscribe`
+    subscriptions: Arc<SubscriptionStore>,
 }
 
 impl MessageHandler {
     /// Create a new message handler
-    pub fn new(satellite_manager: Arc<SatelliteManager>) -> Self {
-        Self { satellite_manager }
+    pub fn new(satellite_manager: Arc<SatelliteManager>, render_config: RenderConfig) -> Self {
+        Self {
+            report_history: Arc::new(ReportHistory::new(satellite_manager.retention_hours())),
+            satellite_manager,
+            render_config,
+            subscriptions: Arc::new(SubscriptionStore::default()),
+        }
+    }
+
+    /// Use `store` for `/subscribe` instead of an in-memory one
+    pub fn with_subscriptions(mut self, store: Arc<SubscriptionStore>) -> Self {
+        self.subscriptions = store;
+        self
     }
     
     /// Handle incoming message
@@ -25,7 +59,7 @@ impl MessageHandler {
         let content = &msg.content;
         let message_id = uuid::Uuid::now_v7().to_string();
 
-        match parse_command(&content) {
+        match parse_command(content) {
             Some((command, args)) => {
                 if command.is_empty() {
                     // No command found
@@ -52,15 +86,160 @@ impl MessageHandler {
         }
     }
     
+    /// Check a report submitted by a user
+    ///
+    /// Returns the report to record, or the reply rejecting it. Reports
+    /// without a callsign are rejected; the platform submitter ID is never
+    /// published in its place.
+    pub async fn validate_report(
+        &self,
+        submission: &ReportSubmission,
+    ) -> std::result::Result<AmsatReport, MessageResponse> {
+        let callsign = submission.callsign.trim().to_uppercase();
+        self.satellite_manager
+            .validate_user_report(
+                &submission.satellite,
+                &submission.status,
+                &submission.grid_square,
+                &callsign,
+            )
+            .await
+            .map_err(|e| text_reply(false, format!("Report rejected: {}", e)))
+    }
+
+    /// Record a report that passed [`Self::validate_report`]
+    pub async fn record_report(&self, report: AmsatReport) -> Result<MessageResponse> {
+        let (callsign, grid) = (report.callsign.clone(), report.grid_square.clone());
+        let response = match self.satellite_manager.record_user_report(report).await {
+            Ok(name) => text_reply(
+                true,
+                format!("Thanks {}! Report for {} from {} recorded.", callsign, name, grid),
+            ),
+            Err(e) => text_reply(false, format!("Report rejected: {}", e)),
+        };
+        Ok(response)
+    }
+
+    /// Answer several queries with one merged image
+    ///
+    /// Each query is searched on its own; satellites matched by more than
+    /// one query appear once.
+    pub async fn query_satellites(&self, queries: &[String]) -> Result<MessageResponse> {
+        let queries: Vec<&str> = queries
+            .iter()
+            .map(|q| q.trim())
+            .filter(|q| !is_blank_query(q))
+            .collect();
+        if queries.is_empty() {
+            return Ok(MessageResponse {
+                success: false,
+                message: QUERY_USAGE.to_string(),
+                message_id: uuid::Uuid::now_v7().to_string(),
+                content_type: ContentType::Text as i32,
+            });
+        }
+
+        let mut satellites: Vec<SatelliteInfo> = Vec::new();
+        for &query in &queries {
+            for sat in self.satellite_manager.search_satellites(query).await? {
+                if !satellites.iter().any(|s| s.name == sat.name) {
+                    satellites.push(sat);
+                }
+            }
+        }
+
+        if satellites.is_empty() {
+            return Ok(MessageResponse {
+                success: false,
+                message: format!("No satellites found for: {}", queries.join(", ")),
+                message_id: uuid::Uuid::now_v7().to_string(),
+                content_type: ContentType::Text as i32,
+            });
+        }
+
+        let (mut limited_satellites, omitted) =
+            limit_results(satellites, self.render_config.max_satellites_per_query);
+        self.render_config.sort_order.apply(&mut limited_satellites);
+        let renderer = SatelliteRenderer::with_config(&self.render_config.output_dir, self.render_config.clone())
+            .with_omitted(omitted);
+
+        let rendered = renderer.render_satellites(&limited_satellites, RenderLayout::Merged).await;
+        Ok(image_or_text_response(true, rendered, || {
+            let mut response_text = format_multiple_satellites(&limited_satellites);
+            if omitted > 0 {
+                response_text.push_str(&renderer.more_results_text());
+            }
+            response_text
+        }))
+    }
+
+    /// Render one query's image and return the PNG bytes
+    ///
+    /// Nothing is written to disk, so deployments without a media server
+    /// can send the image directly. `options` may set "layout"; queries
+    /// without matches get the empty-state image with `success: false`.
+    pub async fn render_image(&self, query: &str, options: &HashMap<String, String>) -> Result<ImageResponse> {
+        let failure = |message: String| ImageResponse {
+            success: false,
+            message,
+            ..Default::default()
+        };
+
+        let query = query.trim();
+        if is_blank_query(query) {
+            return Ok(failure(QUERY_USAGE.to_string()));
+        }
+        let layout = match options.get("layout").map(|l| l.trim().to_lowercase()).as_deref() {
+            None | Some("") => self.render_config.layout,
+            Some("full") => RenderLayout::Full,
+            Some("compact") => RenderLayout::Compact,
+            Some("merged") => RenderLayout::Merged,
+            Some(other) => {
+                return Ok(failure(format!(
+                    "Unknown layout '{}', expected full, compact or merged",
+                    other
+                )));
+            }
+        };
+
+        let satellites = self.satellite_manager.search_satellites(query).await?;
+        let renderer = SatelliteRenderer::with_config(&self.render_config.output_dir, self.render_config.clone());
+
+        if satellites.is_empty() {
+            let suggestions = self.satellite_manager.suggest_similar(query, 3).await;
+            return Ok(ImageResponse {
+                image: renderer.render_no_results_bytes(&suggestions).await?,
+                content_type: "image/png".to_string(),
+                ..failure(format!("Satellite '{}' not found", query))
+            });
+        }
+
+        let (mut limited_satellites, omitted) =
+            limit_results(satellites, self.render_config.max_satellites_per_query);
+        self.render_config.sort_order.apply(&mut limited_satellites);
+        let renderer = renderer.with_omitted(omitted);
+
+        Ok(ImageResponse {
+            success: true,
+            image: renderer.render_satellites_bytes(&limited_satellites, layout).await?,
+            content_type: "image/png".to_string(),
+            message: String::new(),
+        })
+    }
+
     /// Route commands to appropriate handlers
     async fn router(
         &self,
-        _msg: &UnifiedMessage,
+        msg: &UnifiedMessage,
         command: &str,
         args: &str,
     ) -> Result<MessageResponse> {
         match command {
-            "q" | "query" => self.amsat_query(args).await,
+            "q" | "query" => self.amsat_query(msg, args).await,
+            "compare" | "cmp" => self.amsat_compare(args).await,
+            "subscribe" | "sub" => self.subscribe(msg, args).await,
+            "unsubscribe" | "unsub" => self.unsubscribe(msg, args).await,
+            "subscriptions" | "subs" => self.list_subscriptions(msg).await,
             _ => {
                 Ok(MessageResponse {
                     success: false,
@@ -73,13 +252,24 @@ impl MessageHandler {
     }
     
     /// Query satellite information
-    async fn amsat_query(&self, query: &str) -> Result<MessageResponse> {
-        let query = query.trim();
+    async fn amsat_query(&self, msg: &UnifiedMessage, args: &str) -> Result<MessageResponse> {
+        let args = match parse_query_args(args, self.satellite_manager.retention_hours()) {
+            Ok(args) => args,
+            Err(message) => {
+                return Ok(MessageResponse {
+                    success: false,
+                    message,
+                    message_id: uuid::Uuid::now_v7().to_string(),
+                    content_type: ContentType::Text as i32,
+                });
+            }
+        };
+        let query = args.query.as_str();
         
-        if query.is_empty() {
+        if is_blank_query(query) {
             return Ok(MessageResponse {
                 success: false,
-                message: "Please provide a satellite name to query. Example: /q AO-91".to_string(),
+                message: QUERY_USAGE.to_string(),
                 message_id: uuid::Uuid::now_v7().to_string(),
                 content_type: ContentType::Text as i32,
             });
@@ -88,53 +278,318 @@ impl MessageHandler {
         // Search for satellites
         let satellites = self.satellite_manager.search_satellites(query).await?;
         
+        let renderer = SatelliteRenderer::with_config(&self.render_config.output_dir, self.render_config.clone());
+
         if satellites.is_empty() {
+            let suggestions = self.satellite_manager.suggest_similar(query, 3).await;
+            let rendered = renderer.render_no_results(&suggestions).await;
+            return Ok(image_or_text_response(false, rendered, || {
+                let mut message = format!("Satellite '{}' not found. Try searching by name or alias.", query);
+                if !suggestions.is_empty() {
+                    message.push_str(&format!(
+                        "\n{} {}",
+                        self.render_config.suggestion_prefix,
+                        suggestions.join(", ")
+                    ));
+                }
+                message
+            }));
+        }
+        
+        // Cap the satellites per query so broad matches stay readable
+        let (mut limited_satellites, omitted) =
+            limit_results(satellites, self.render_config.max_satellites_per_query);
+        self.render_config.sort_order.apply(&mut limited_satellites);
+        let renderer = renderer.with_omitted(omitted);
+        let renderer = match args.hours {
+            Some(hours) => renderer.with_report_window(hours, args.page),
+            None => renderer,
+        };
+        let renderer = match conversation(msg) {
+            Some(conversation) if self.render_config.highlight_new_reports => {
+                renderer.with_report_history(self.report_history.clone(), &conversation)
+            }
+            _ => renderer,
+        };
+        
+        // Try to render as image, falling back to text
+        let rendered = renderer.render_satellites(&limited_satellites, self.render_config.layout).await;
+        Ok(image_or_text_response(true, rendered, || {
+            let mut response_text = if limited_satellites.len() == 1 {
+                format_satellite_info(&limited_satellites[0])
+            } else {
+                format_multiple_satellites(&limited_satellites)
+            };
+            if omitted > 0 {
+                response_text.push_str(&renderer.more_results_text());
+            }
+            response_text
+        }))
+    }
+
+    /// Render two satellites side by side
+    async fn amsat_compare(&self, args: &str) -> Result<MessageResponse> {
+        let Some([first, second]) = parse_compare_args(args) else {
             return Ok(MessageResponse {
                 success: false,
-                message: format!("Satellite '{}' not found. Try searching by name or alias.", query),
+                message: COMPARE_USAGE.to_string(),
                 message_id: uuid::Uuid::now_v7().to_string(),
                 content_type: ContentType::Text as i32,
             });
-        }
-        
-        // Limit to 5 satellites per query
-        let limited_satellites: Vec<_> = satellites.into_iter().take(5).collect();
-        
-        // Try to render as image
-        let cache_dir = self.satellite_manager.cache_dir();
-        let images_dir = cache_dir.join("rendered_images");
-        let renderer = SatelliteRenderer::new(&images_dir);
-        
-        match renderer.render_satellites(&limited_satellites).await {
-            Ok(image_path) => {
-                // Return image path
-                let path_str = image_path.to_string_lossy().to_string();
-                Ok(MessageResponse {
-                    success: true,
-                    message: format!("file:///{}", path_str.replace("\\", "/")),
-                    message_id: uuid::Uuid::now_v7().to_string(),
-                    content_type: ContentType::Image as i32,
+        };
+
+        // Best match for each side, same lookup as /q
+        let first_match = self.satellite_manager.search_satellites(&first).await?.into_iter().next();
+        let second_match = self.satellite_manager.search_satellites(&second).await?.into_iter().next();
+        let found = first_match.is_some() && second_match.is_some();
+
+        let renderer = SatelliteRenderer::with_config(&self.render_config.output_dir, self.render_config.clone());
+        let sides = [(first.as_str(), first_match.as_ref()), (second.as_str(), second_match.as_ref())];
+        let rendered = renderer.render_comparison(sides).await;
+        Ok(image_or_text_response(found, rendered, || {
+            sides
+                .iter()
+                .map(|(query, sat)| match sat {
+                    Some(sat) => format_satellite_info(sat),
+                    None => format!("Satellite '{}' not found.\n", query),
                 })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }))
+    }
+
+    /// Subscribe the sending group to periodic posts of a satellite
+    async fn subscribe(&self, msg: &UnifiedMessage, args: &str) -> Result<MessageResponse> {
+        let Some(group) = subscriber_group(msg) else {
+            return Ok(text_reply(false, SUBSCRIPTIONS_GROUPS_ONLY.to_string()));
+        };
+        let Some((query, cadence)) = parse_subscribe_args(args) else {
+            return Ok(text_reply(false, SUBSCRIBE_USAGE.to_string()));
+        };
+        let Some(sat) = self.satellite_manager.search_satellites(&query).await?.into_iter().next() else {
+            return Ok(text_reply(false, format!("Satellite '{}' not found.", query)));
+        };
+
+        let outcome = self
+            .subscriptions
+            .subscribe(group, &sat.name, cadence, chrono::Utc::now())
+            .await?;
+        Ok(match outcome {
+            SubscribeOutcome::Added => {
+                text_reply(true, format!("Subscribed: {} status will be posted here {}.", sat.name, cadence))
             }
-            Err(e) => {
-                // Fallback to text format if rendering fails
-                tracing::warn!("Image rendering failed, falling back to text: {}", e);
-                
-                let response_text = if limited_satellites.len() == 1 {
-                    format_satellite_info(&limited_satellites[0])
-                } else {
-                    format_multiple_satellites(&limited_satellites)
-                };
-                
-                Ok(MessageResponse {
-                    success: true,
-                    message: response_text,
-                    message_id: uuid::Uuid::now_v7().to_string(),
-                    content_type: ContentType::Text as i32,
-                })
+            SubscribeOutcome::Updated => {
+                text_reply(true, format!("Updated: {} status will now be posted here {}.", sat.name, cadence))
             }
+            SubscribeOutcome::LimitReached => text_reply(
+                false,
+                format!(
+                    "This group already has {} subscriptions. Send /unsubscribe <satellite> to make room.",
+                    MAX_SUBSCRIPTIONS_PER_GROUP
+                ),
+            ),
+        })
+    }
+
+    /// Stop the sending group's posts of a satellite
+    async fn unsubscribe(&self, msg: &UnifiedMessage, args: &str) -> Result<MessageResponse> {
+        let Some(group) = subscriber_group(msg) else {
+            return Ok(text_reply(false, SUBSCRIPTIONS_GROUPS_ONLY.to_string()));
+        };
+        let query = args.trim();
+        if is_blank_query(query) {
+            return Ok(text_reply(false, UNSUBSCRIBE_USAGE.to_string()));
+        }
+
+        // Match the stored name, falling back to a search for aliases
+        let subscribed = self.subscriptions.list(group).await;
+        let name = match subscribed.iter().find(|s| s.satellite.eq_ignore_ascii_case(query)) {
+            Some(subscription) => Some(subscription.satellite.clone()),
+            None => self
+                .satellite_manager
+                .search_satellites(query)
+                .await?
+                .into_iter()
+                .next()
+                .map(|sat| sat.name),
+        };
+
+        let removed = match &name {
+            Some(name) => self.subscriptions.unsubscribe(group, name).await?,
+            None => false,
+        };
+        Ok(if removed {
+            text_reply(true, format!("Unsubscribed from {}.", name.unwrap_or_default()))
+        } else {
+            text_reply(false, format!("This group is not subscribed to '{}'.", query))
+        })
+    }
+
+    /// List the sending group's subscriptions
+    async fn list_subscriptions(&self, msg: &UnifiedMessage) -> Result<MessageResponse> {
+        let Some(group) = subscriber_group(msg) else {
+            return Ok(text_reply(false, SUBSCRIPTIONS_GROUPS_ONLY.to_string()));
+        };
+        let subscriptions = self.subscriptions.list(group).await;
+        if subscriptions.is_empty() {
+            return Ok(text_reply(true, format!("No subscriptions in this group. {}", SUBSCRIBE_USAGE)));
+        }
+
+        let mut message = format!("🛰️ {} subscription(s):", subscriptions.len());
+        for subscription in subscriptions {
+            message.push_str(&format!("\n{} - {}", subscription.satellite, subscription.cadence));
+        }
+        Ok(text_reply(true, message))
+    }
+}
+
+/// Group a message came from, for subscriptions
+fn subscriber_group(msg: &UnifiedMessage) -> Option<&str> {
+    msg.metadata
+        .get("group_openid")
+        .map(String::as_str)
+        .filter(|group| !group.is_empty())
+}
+
+/// Group or channel a message came from, keying new-report highlights
+///
+/// `None` for messages from neither, which are rendered without highlights.
+fn conversation(msg: &UnifiedMessage) -> Option<String> {
+    let id = |key: &str| msg.metadata.get(key).filter(|id| !id.is_empty());
+    id("group_openid")
+        .map(|group| format!("group:{}", group))
+        .or_else(|| id("channel_id").map(|channel| format!("channel:{}", channel)))
+}
+
+/// Split `/subscribe` arguments into the satellite and an optional cadence
+fn parse_subscribe_args(args: &str) -> Option<(String, Cadence)> {
+    let mut tokens: Vec<&str> = args.split_whitespace().collect();
+    let cadence = match tokens.last().and_then(|last| Cadence::parse(last)) {
+        Some(cadence) if tokens.len() > 1 => {
+            tokens.pop();
+            cadence
         }
+        _ => Cadence::default(),
+    };
+    let query = tokens.join(" ");
+    if is_blank_query(&query) {
+        return None;
     }
+    Some((query, cadence))
+}
+
+/// Plain text reply
+fn text_reply(success: bool, message: String) -> MessageResponse {
+    MessageResponse {
+        success,
+        message,
+        message_id: uuid::Uuid::now_v7().to_string(),
+        content_type: ContentType::Text as i32,
+    }
+}
+
+/// A `file:///` image reply for a rendered image, or the `fallback` text
+/// if rendering failed
+fn image_or_text_response(
+    success: bool,
+    rendered: Result<PathBuf>,
+    fallback: impl FnOnce() -> String,
+) -> MessageResponse {
+    match rendered {
+        Ok(image_path) => MessageResponse {
+            success,
+            message: format!("file:///{}", image_path.to_string_lossy().replace("\\", "/")),
+            message_id: uuid::Uuid::now_v7().to_string(),
+            content_type: ContentType::Image as i32,
+        },
+        Err(e) => {
+            tracing::warn!("Image rendering failed, falling back to text: {}", e);
+            text_reply(success, fallback())
+        }
+    }
+}
+
+/// Split `/compare` arguments into two satellite names
+///
+/// Names are separated by a comma when there is one, so names with spaces
+/// work ("ISS FM, SO-50"); otherwise by whitespace.
+fn parse_compare_args(args: &str) -> Option<[String; 2]> {
+    let names: Vec<&str> = if args.contains(',') {
+        args.split(',').map(str::trim).collect()
+    } else {
+        args.split_whitespace().collect()
+    };
+    match names.as_slice() {
+        [first, second] if !is_blank_query(first) && !is_blank_query(second) => {
+            Some([first.to_string(), second.to_string()])
+        }
+        _ => None,
+    }
+}
+
+/// Query text plus the optional `--hours N` and `--page N` flags
+#[derive(Debug, PartialEq)]
+struct QueryArgs {
+    query: String,
+    hours: Option<u64>,
+    page: usize,
+}
+
+/// Split `/q` arguments into the query and its flags
+///
+/// `--hours` may not exceed `max_hours`, the report retention, since older
+/// reports are not kept. Errors are user-facing messages.
+fn parse_query_args(args: &str, max_hours: u64) -> std::result::Result<QueryArgs, String> {
+    let mut query = Vec::new();
+    let mut hours = None;
+    let mut page = 1;
+
+    let mut tokens = args.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "--hours" => {
+                let value = tokens.next().and_then(|v| v.parse::<u64>().ok());
+                match value {
+                    Some(h) if (1..=max_hours).contains(&h) => hours = Some(h),
+                    _ => {
+                        return Err(format!(
+                            "--hours needs a number of hours between 1 and {}. Example: /q AO-91 --hours 24",
+                            max_hours
+                        ));
+                    }
+                }
+            }
+            "--page" => match tokens.next().and_then(|v| v.parse::<usize>().ok()) {
+                Some(p) if p >= 1 => page = p,
+                _ => return Err("--page needs a page number starting at 1".to_string()),
+            },
+            _ => query.push(token),
+        }
+    }
+
+    Ok(QueryArgs {
+        query: query.join(" "),
+        hours,
+        page,
+    })
+}
+
+/// Whether a query holds no satellite name at all
+///
+/// Chat clients sometimes pad messages with zero-width characters, which
+/// `split_whitespace` keeps, so those count as blank too.
+fn is_blank_query(query: &str) -> bool {
+    query
+        .chars()
+        .all(|c| c.is_whitespace() || matches!(c, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}'))
+}
+
+/// Keep at most `max` results (at least one), returning how many were dropped
+fn limit_results(mut satellites: Vec<SatelliteInfo>, max: usize) -> (Vec<SatelliteInfo>, usize) {
+    let max = max.max(1);
+    let omitted = satellites.len().saturating_sub(max);
+    satellites.truncate(max);
+    (satellites, omitted)
 }
 
 /// Parse command from message content
@@ -232,4 +687,87 @@ mod tests {
         
         assert_eq!(parse_command("no command here"), None);
     }
+
+    #[test]
+    fn test_conversation_keys_groups_and_channels() {
+        let msg = |key: &str, id: &str| UnifiedMessage {
+            metadata: HashMap::from([(key.to_string(), id.to_string())]),
+            ..Default::default()
+        };
+        assert_eq!(conversation(&msg("group_openid", "g1")).as_deref(), Some("group:g1"));
+        assert_eq!(conversation(&msg("channel_id", "c1")).as_deref(), Some("channel:c1"));
+        assert_eq!(conversation(&msg("channel_id", "")), None);
+        assert_eq!(conversation(&UnifiedMessage::default()), None);
+    }
+
+    #[test]
+    fn test_parse_query_args() {
+        assert_eq!(
+            parse_query_args("ao-91 --hours 24", 48),
+            Ok(QueryArgs { query: "ao-91".to_string(), hours: Some(24), page: 1 })
+        );
+        assert_eq!(
+            parse_query_args("--page 2 ISS FM --hours 6", 48),
+            Ok(QueryArgs { query: "ISS FM".to_string(), hours: Some(6), page: 2 })
+        );
+        assert_eq!(
+            parse_query_args(" SO-50 ", 48),
+            Ok(QueryArgs { query: "SO-50".to_string(), hours: None, page: 1 })
+        );
+        assert!(parse_query_args("ao-91 --hours", 48).is_err());
+        assert!(parse_query_args("ao-91 --hours 0", 48).is_err());
+        assert!(parse_query_args("ao-91 --hours 100", 48).is_err());
+        assert!(parse_query_args("ao-91 --page 0", 48).is_err());
+
+        // The cap follows the configured retention
+        assert!(parse_query_args("ao-91 --hours 24", 12).is_err());
+        assert_eq!(parse_query_args("ao-91 --hours 72", 96).unwrap().hours, Some(72));
+    }
+
+    #[test]
+    fn test_parse_compare_args() {
+        assert_eq!(
+            parse_compare_args("ao-91 so-50"),
+            Some(["ao-91".to_string(), "so-50".to_string()])
+        );
+        assert_eq!(
+            parse_compare_args("ISS FM, SO-50"),
+            Some(["ISS FM".to_string(), "SO-50".to_string()])
+        );
+        assert_eq!(parse_compare_args("ao-91"), None);
+        assert_eq!(parse_compare_args("ao-91 so-50 fo-29"), None);
+        assert_eq!(parse_compare_args("ao-91,"), None);
+    }
+
+    #[test]
+    fn test_parse_subscribe_args() {
+        assert_eq!(parse_subscribe_args("ao-91 daily"), Some(("ao-91".to_string(), Cadence::Daily)));
+        assert_eq!(parse_subscribe_args("ISS FM"), Some(("ISS FM".to_string(), Cadence::Hourly)));
+        // A lone cadence word is taken as the satellite name
+        assert_eq!(parse_subscribe_args("daily"), Some(("daily".to_string(), Cadence::Hourly)));
+        assert_eq!(parse_subscribe_args("  "), None);
+    }
+
+    #[test]
+    fn test_is_blank_query() {
+        assert!(is_blank_query(""));
+        assert!(is_blank_query(" \t\u{3000}"));
+        assert!(is_blank_query("\u{200B}\u{FEFF}"));
+        assert!(!is_blank_query("AO-91"));
+        assert!(!is_blank_query("\u{200B}ISS"));
+    }
+
+    #[test]
+    fn test_limit_results() {
+        let matches: Vec<SatelliteInfo> =
+            (1..=20).map(|i| SatelliteInfo::new(format!("SAT-{}", i))).collect();
+
+        let (limited, omitted) = limit_results(matches.clone(), 8);
+        assert_eq!(limited.len(), 8);
+        assert_eq!(limited[7].name, "SAT-8");
+        assert_eq!(omitted, 12);
+
+        let (limited, omitted) = limit_results(matches[..3].to_vec(), 8);
+        assert_eq!((limited.len(), omitted), (3, 0));
+    }
 }
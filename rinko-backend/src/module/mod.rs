@@ -1,3 +1,7 @@
 pub mod sat;
+pub mod daily_post;
 pub mod handler;
-pub mod scheduled;
\ No newline at end of file
+pub mod report_limit;
+pub mod scheduled;
+pub mod subscription;
+pub mod watchlist;
\ No newline at end of file
@@ -0,0 +1,124 @@
+//! Flood protection for user report submissions
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::config::ReportLimitConfig;
+
+/// Sliding-window rate limiter keyed by submitter and by group
+pub struct ReportRateLimiter {
+    config: ReportLimitConfig,
+    /// key -> accepted submission times within the window
+    history: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl ReportRateLimiter {
+    /// Create a limiter with the given limits
+    pub fn new(config: ReportLimitConfig) -> Self {
+        Self {
+            config,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check and record a submission
+    ///
+    /// Returns the time until the caller may submit again when either the
+    /// submitter or the group is over its limit. Rejected submissions are
+    /// not recorded.
+    pub fn check(&self, submitter: &str, group: Option<&str>) -> Result<(), Duration> {
+        self.check_at(submitter, group, Instant::now())
+    }
+
+    fn check_at(&self, submitter: &str, group: Option<&str>, now: Instant) -> Result<(), Duration> {
+        let window = Duration::from_secs(self.config.window_minutes * 60);
+        let mut keys = vec![(format!("user:{}", submitter), self.config.max_per_user)];
+        if let Some(group) = group.filter(|g| !g.is_empty()) {
+            keys.push((format!("group:{}", group), self.config.max_per_group));
+        }
+        // A limit of 0 means unlimited; nothing to track
+        keys.retain(|(_, limit)| *limit > 0);
+
+        let mut history = self.history.lock().unwrap();
+
+        // Drop expired entries and find the longest wait among exceeded limits
+        let mut retry_after = None;
+        for (key, limit) in &keys {
+            let times = history.entry(key.clone()).or_default();
+            while times.front().is_some_and(|t| now.duration_since(*t) >= window) {
+                times.pop_front();
+            }
+            if times.len() >= *limit
+                && let Some(oldest) = times.front()
+            {
+                let wait = window - now.duration_since(*oldest);
+                retry_after = Some(retry_after.map_or(wait, |w: Duration| w.max(wait)));
+            }
+        }
+        history.retain(|_, times| !times.is_empty());
+
+        if let Some(wait) = retry_after {
+            return Err(wait);
+        }
+
+        for (key, _) in keys {
+            history.entry(key).or_default().push_back(now);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(max_per_user: usize, max_per_group: usize) -> ReportRateLimiter {
+        ReportRateLimiter::new(ReportLimitConfig {
+            max_per_user,
+            max_per_group,
+            window_minutes: 10,
+        })
+    }
+
+    #[test]
+    fn test_user_limit() {
+        let limiter = limiter(2, 100);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("alice", None, start).is_ok());
+        assert!(limiter.check_at("alice", None, start).is_ok());
+        let wait = limiter.check_at("alice", None, start).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(600));
+
+        // Other users are unaffected, and the window slides
+        assert!(limiter.check_at("bob", None, start).is_ok());
+        assert!(limiter.check_at("alice", None, start + Duration::from_secs(600)).is_ok());
+    }
+
+    #[test]
+    fn test_group_limit() {
+        let limiter = limiter(100, 3);
+        let now = Instant::now();
+
+        for user in ["a", "b", "c"] {
+            assert!(limiter.check_at(user, Some("group1"), now).is_ok());
+        }
+        assert!(limiter.check_at("d", Some("group1"), now).is_err());
+        assert!(limiter.check_at("d", Some("group2"), now).is_ok());
+    }
+
+    #[test]
+    fn test_zero_limit_is_unlimited() {
+        let limiter = limiter(0, 2);
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            assert!(limiter.check_at("alice", None, now).is_ok());
+        }
+        // The group limit still applies
+        assert!(limiter.check_at("alice", Some("group1"), now).is_ok());
+        assert!(limiter.check_at("alice", Some("group1"), now).is_ok());
+        assert!(limiter.check_at("alice", Some("group1"), now).is_err());
+    }
+}
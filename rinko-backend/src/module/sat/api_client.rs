@@ -1,33 +1,98 @@
-///! AMSAT API client for fetching satellite status data
+//! AMSAT API client for fetching satellite status data
 use super::types::AmsatReport;
+use crate::http_client::shared_client;
 use anyhow::{Context, Result};
 use reqwest;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::time::Duration;
 
-const AMSAT_API_URL: &str = "https://www.amsat.org/status/api/v1/sat_info.php";
+/// Default AMSAT status API endpoint
+pub const DEFAULT_AMSAT_API_URL: &str = "https://www.amsat.org/status/api/v1/sat_info.php";
 const MAX_RETRIES: u32 = 3;
 const RETRY_DELAY_SECONDS: u64 = 2;
-const REQUEST_TIMEOUT_SECONDS: u64 = 60;
+
+/// Upstream requests left in one update cycle
+///
+/// Shared by every satellite and every retry of the cycle, so a flaky
+/// upstream cannot multiply the per-satellite retries into a request storm.
+#[derive(Debug)]
+pub struct RequestBudget {
+    remaining: AtomicU32,
+}
+
+impl RequestBudget {
+    /// Allow at most `requests` upstream requests
+    pub fn new(requests: u32) -> Self {
+        Self { remaining: AtomicU32::new(requests) }
+    }
+
+    /// Take one request from the budget, or return false if it is spent
+    pub fn try_acquire(&self) -> bool {
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .is_ok()
+    }
+
+    /// Requests still available
+    pub fn remaining(&self) -> u32 {
+        self.remaining.load(Ordering::SeqCst)
+    }
+}
 
 /// Fetch satellite data from AMSAT API
 /// 
 /// # Arguments
+/// * `api_url` - AMSAT API endpoint (see `DEFAULT_AMSAT_API_URL`)
 /// * `sat_name` - Satellite name (case-sensitive)
 /// * `hours` - Number of hours of data to fetch (default: 1, max: 96)
+/// * `budget` - Requests left in this update cycle, one per attempt
 /// 
 /// # Returns
 /// Vec of AmsatReport on success, Error on failure
-pub async fn fetch_satellite_data(sat_name: &str, hours: u64) -> Result<Vec<AmsatReport>> {
-    let api_url = format!("{}?name={}&hours={}", AMSAT_API_URL, sat_name, hours);
+pub async fn fetch_satellite_data(
+    api_url: &str,
+    sat_name: &str,
+    hours: u64,
+    budget: &RequestBudget,
+) -> Result<Vec<AmsatReport>> {
+    let api_url = reqwest::Url::parse_with_params(
+        api_url,
+        &[("name", sat_name.to_string()), ("hours", hours.to_string())],
+    )
+    .with_context(|| format!("Invalid AMSAT API URL '{}'", api_url))?;
     
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
-        .build()
-        .context("Failed to build HTTP client")?;
+    fetch_with_retries(
+        shared_client(),
+        api_url.as_str(),
+        sat_name,
+        Duration::from_secs(RETRY_DELAY_SECONDS),
+        budget,
+    )
+    .await
+}
 
+/// Fetch with up to `MAX_RETRIES` attempts, waiting `retry_delay` times
+/// the attempt number before each retry
+///
+/// Every attempt draws from `budget`; once it is spent the last error (or
+/// a budget error, if nothing was sent) is returned without a request.
+async fn fetch_with_retries(
+    client: &reqwest::Client,
+    api_url: &str,
+    sat_name: &str,
+    retry_delay: Duration,
+    budget: &RequestBudget,
+) -> Result<Vec<AmsatReport>> {
+    let mut last_error = None;
     for attempt in 1..=MAX_RETRIES {
+        if !budget.try_acquire() {
+            tracing::warn!("Request budget for this update cycle spent, not fetching {}", sat_name);
+            return Err(last_error.unwrap_or_else(|| {
+                anyhow::anyhow!("Request budget for this update cycle spent before fetching {}", sat_name)
+            }));
+        }
         if attempt > 1 {
-            let delay = Duration::from_secs(RETRY_DELAY_SECONDS * attempt as u64);
+            let delay = retry_delay * attempt;
             tracing::debug!(
                 "Retrying {} after {:?} (attempt {}/{})",
                 sat_name,
@@ -38,7 +103,7 @@ pub async fn fetch_satellite_data(sat_name: &str, hours: u64) -> Result<Vec<Amsa
             tokio::time::sleep(delay).await;
         }
 
-        match fetch_attempt(&client, &api_url, sat_name).await {
+        match fetch_attempt(client, api_url, sat_name).await {
             Ok(data) => {
                 tracing::debug!(
                     "Successfully fetched {} reports for {}",
@@ -64,6 +129,7 @@ pub async fn fetch_satellite_data(sat_name: &str, hours: u64) -> Result<Vec<Amsa
                         sat_name,
                         e
                     );
+                    last_error = Some(e);
                 }
             }
         }
@@ -96,36 +162,65 @@ async fn fetch_attempt(
         ));
     }
 
-    let data: Vec<AmsatReport> = response
-        .json()
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = response
+        .text()
         .await
-        .context(format!("Failed to parse JSON response for {}", sat_name))?;
+        .context(format!("Failed to read response for {}", sat_name))?;
+
+    parse_reports(&body, &content_type)
+        .with_context(|| format!("Bad AMSAT response for {}", sat_name))
+}
 
-    Ok(data)
+/// Parse an API response body, naming HTML error pages explicitly
+///
+/// AMSAT answers with a 200 HTML page during maintenance, which would
+/// otherwise surface as an opaque serde error.
+fn parse_reports(body: &str, content_type: &str) -> Result<Vec<AmsatReport>> {
+    match serde_json::from_str(body) {
+        Ok(data) => Ok(data),
+        Err(e) if content_type.contains("html") || body.trim_start().starts_with('<') => {
+            Err(anyhow::anyhow!(
+                "AMSAT returned non-JSON ({}) instead of reports (maintenance?): {}",
+                if content_type.is_empty() { "no content type" } else { content_type },
+                e
+            ))
+        }
+        Err(e) => Err(e).context("Failed to parse JSON response"),
+    }
 }
 
 /// Batch fetch multiple satellites with delay between requests
 /// 
 /// # Arguments
+/// * `api_url` - AMSAT API endpoint
 /// * `sat_names` - List of satellite names to fetch
 /// * `hours` - Number of hours of data to fetch
 /// * `delay_ms` - Delay between requests in milliseconds (to avoid rate limiting)
+/// * `budget` - Requests left in this update cycle, shared by all satellites
 /// 
 /// # Returns
 /// HashMap of satellite name to Result<Vec<AmsatReport>>
 pub async fn batch_fetch_satellites(
+    api_url: &str,
     sat_names: &[String],
     hours: u64,
     delay_ms: u64,
+    budget: &RequestBudget,
 ) -> std::collections::HashMap<String, Result<Vec<AmsatReport>>> {
     let mut results = std::collections::HashMap::new();
 
     for (index, sat_name) in sat_names.iter().enumerate() {
-        if index > 0 && delay_ms > 0 {
+        if index > 0 && delay_ms > 0 && budget.remaining() > 0 {
             tokio::time::sleep(Duration::from_millis(delay_ms)).await;
         }
 
-        let result = fetch_satellite_data(sat_name, hours).await;
+        let result = fetch_satellite_data(api_url, sat_name, hours, budget).await;
         results.insert(sat_name.clone(), result);
     }
 
@@ -136,10 +231,121 @@ pub async fn batch_fetch_satellites(
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_fetch_uses_configured_url() {
+        use axum::{Json, Router, extract::Query, routing::get};
+        use std::collections::HashMap;
+
+        let app = Router::new().route(
+            "/mock/sat_info.php",
+            get(|Query(params): Query<HashMap<String, String>>| async move {
+                Json(vec![AmsatReport {
+                    name: params["name"].clone(),
+                    reported_time: "2026-02-16T08:10:00Z".to_string(),
+                    callsign: format!("hours={}", params["hours"]),
+                    report: "Heard".to_string(),
+                    ..Default::default()
+                }])
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let api_url = format!("http://{}/mock/sat_info.php", addr);
+        let reports = fetch_satellite_data(&api_url, "ISS-FM", 6, &RequestBudget::new(1)).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].name, "ISS-FM");
+        assert_eq!(reports[0].callsign, "hours=6");
+    }
+
+    #[tokio::test]
+    async fn test_html_error_page_is_retried_with_clear_error() {
+        use axum::{Router, response::Html, routing::get};
+        use std::sync::Arc;
+
+        let requests = Arc::new(AtomicU32::new(0));
+        let counter = requests.clone();
+        let app = Router::new().route(
+            "/mock/sat_info.php",
+            get(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { Html("<html><body><h1>Down for maintenance</h1></body></html>") }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let api_url = format!("http://{}/mock/sat_info.php", addr);
+        let error = fetch_with_retries(
+            &reqwest::Client::new(),
+            &api_url,
+            "AO-91",
+            Duration::from_millis(1),
+            &RequestBudget::new(10),
+        )
+        .await
+        .unwrap_err();
+
+        let message = format!("{:#}", error);
+        assert!(message.contains("AO-91"), "{}", message);
+        assert!(message.contains("non-JSON (text/html"), "{}", message);
+        assert!(message.contains("maintenance?"), "{}", message);
+        assert_eq!(requests.load(Ordering::SeqCst), MAX_RETRIES);
+    }
+
+    #[tokio::test]
+    async fn test_retries_across_cycle_stay_within_budget() {
+        use axum::{Router, http::StatusCode, routing::get};
+        use std::sync::Arc;
+
+        let requests = Arc::new(AtomicU32::new(0));
+        let counter = requests.clone();
+        let app = Router::new().route(
+            "/mock/sat_info.php",
+            get(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { StatusCode::SERVICE_UNAVAILABLE }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let api_url = format!("http://{}/mock/sat_info.php", addr);
+        let client = reqwest::Client::new();
+        let budget = RequestBudget::new(5);
+        // Two attempts of a cycle over three satellites would send 18
+        // requests with per-satellite retries alone
+        for _ in 0..2 {
+            for sat_name in ["AO-91", "SO-50", "FO-29"] {
+                let error = fetch_with_retries(&client, &api_url, sat_name, Duration::from_millis(1), &budget)
+                    .await
+                    .unwrap_err();
+                assert!(format!("{:#}", error).contains(sat_name));
+            }
+        }
+
+        assert_eq!(requests.load(Ordering::SeqCst), 5);
+        assert_eq!(budget.remaining(), 0);
+        let skipped = fetch_with_retries(&client, &api_url, "ISS-FM", Duration::from_millis(1), &budget)
+            .await
+            .unwrap_err();
+        assert!(skipped.to_string().contains("budget"), "{}", skipped);
+    }
+
+    #[test]
+    fn test_parse_reports_keeps_json_errors_distinct() {
+        let message = format!("{:#}", parse_reports("{\"error\": 1}", "application/json").unwrap_err());
+        assert!(message.starts_with("Failed to parse JSON response"), "{}", message);
+        assert!(parse_reports("[]", "text/html").unwrap().is_empty());
+    }
+
     #[tokio::test]
     #[ignore] // Requires network connection
     async fn test_fetch_satellite_data() {
-        let result = fetch_satellite_data("AO-91", 1).await;
+        let result = fetch_satellite_data(DEFAULT_AMSAT_API_URL, "AO-91", 1, &RequestBudget::new(3)).await;
         assert!(result.is_ok() || result.is_err()); // Just test it can run
     }
 
@@ -147,7 +353,7 @@ mod tests {
     #[ignore]
     async fn test_batch_fetch() {
         let sat_names = vec!["AO-91".to_string(), "ISS-FM".to_string()];
-        let results = batch_fetch_satellites(&sat_names, 1, 200).await;
+        let results = batch_fetch_satellites(DEFAULT_AMSAT_API_URL, &sat_names, 1, 200, &RequestBudget::new(6)).await;
         assert_eq!(results.len(), 2);
     }
 }
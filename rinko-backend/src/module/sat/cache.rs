@@ -1,12 +1,88 @@
-///! File cache management for satellite data
+//! File cache management for satellite data
 use super::types::{SatelliteInfo, SatelliteList};
 use anyhow::{Context, Result};
-use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 use tokio::fs;
 
 const SATELLITE_CACHE_FILE: &str = "satellite_cache.json";
 const SATELLITE_LIST_FILE: &str = "satellite_list.toml";
 
+/// Current on-disk cache schema version
+///
+/// - 0: bare JSON array of `SatelliteInfo` (legacy, no wrapper)
+/// - 1: `CacheFile` wrapper with an explicit version
+const CACHE_VERSION: u32 = 1;
+
+/// Versioned satellite cache file
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheFile<T> {
+    version: u32,
+    satellites: Vec<T>,
+}
+
+/// Parse cache file content of any known version, upgrading it to current
+///
+/// Entries are deserialized one by one, so a single corrupt satellite is
+/// logged and skipped instead of discarding the whole cache.
+fn parse_satellite_cache(content: &str) -> Result<Vec<SatelliteInfo>> {
+    let value: serde_json::Value =
+        serde_json::from_str(content).context("Failed to parse satellite cache JSON")?;
+
+    let entries: Vec<serde_json::Value> = if value.is_array() {
+        // Version 0: a bare array of satellites
+        tracing::info!("Migrating satellite cache from version 0 to {}", CACHE_VERSION);
+        serde_json::from_value(value).context("Failed to parse version 0 satellite cache")?
+    } else {
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .context("Satellite cache has no version field")?;
+        if version > CACHE_VERSION as u64 {
+            anyhow::bail!(
+                "Satellite cache version {} is newer than supported version {}",
+                version,
+                CACHE_VERSION
+            );
+        }
+
+        let file: CacheFile<serde_json::Value> =
+            serde_json::from_value(value).context("Failed to parse satellite cache file")?;
+        file.satellites
+    };
+
+    let total = entries.len();
+    let satellites: Vec<SatelliteInfo> = entries
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let name = entry.get("name").and_then(|n| n.as_str()).map(str::to_string);
+            match serde_json::from_value(entry) {
+                Ok(info) => Some(info),
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping corrupt cache entry #{} ({}): {}",
+                        index,
+                        name.as_deref().unwrap_or("unnamed"),
+                        e
+                    );
+                    None
+                }
+            }
+        })
+        .collect();
+
+    if satellites.len() < total {
+        tracing::warn!(
+            "Recovered {} of {} cached satellites",
+            satellites.len(),
+            total
+        );
+    }
+
+    Ok(satellites)
+}
+
 /// Load satellite cache from JSON file
 /// 
 /// # Arguments
@@ -26,8 +102,7 @@ pub async fn load_satellite_cache(cache_dir: &Path) -> Result<Vec<SatelliteInfo>
         .await
         .context(format!("Failed to read cache file: {:?}", cache_path))?;
     
-    let satellites: Vec<SatelliteInfo> = serde_json::from_str(&content)
-        .context("Failed to parse satellite cache JSON")?;
+    let satellites = parse_satellite_cache(&content)?;
     
     tracing::info!(
         "Loaded {} satellites from cache: {:?}",
@@ -54,7 +129,11 @@ pub async fn save_satellite_cache(
     
     let cache_path = cache_dir.join(SATELLITE_CACHE_FILE);
     
-    let json = serde_json::to_string_pretty(satellites)
+    let file = CacheFile {
+        version: CACHE_VERSION,
+        satellites: satellites.iter().collect(),
+    };
+    let json = serde_json::to_string_pretty(&file)
         .context("Failed to serialize satellite cache")?;
     
     fs::write(&cache_path, json)
@@ -132,24 +211,15 @@ pub async fn save_satellite_list(cache_dir: &Path, list: &SatelliteList) -> Resu
     Ok(())
 }
 
-/// Get the path to the rendered images directory
-pub fn get_images_dir(cache_dir: &Path) -> PathBuf {
-    cache_dir.join("rendered_images")
-}
-
 /// Ensure the images directory exists
-pub async fn ensure_images_dir(cache_dir: &Path) -> Result<PathBuf> {
-    let images_dir = get_images_dir(cache_dir);
-    fs::create_dir_all(&images_dir)
+pub async fn ensure_images_dir(images_dir: &Path) -> Result<()> {
+    fs::create_dir_all(images_dir)
         .await
-        .context(format!("Failed to create images directory: {:?}", images_dir))?;
-    Ok(images_dir)
+        .context(format!("Failed to create images directory: {:?}", images_dir))
 }
 
 /// Clean up old cached images (older than specified days)
-pub async fn cleanup_old_images(cache_dir: &Path, days_to_keep: i64) -> Result<usize> {
-    let images_dir = get_images_dir(cache_dir);
-    
+pub async fn cleanup_old_images(images_dir: &Path, days_to_keep: i64) -> Result<usize> {
     if !images_dir.exists() {
         return Ok(0);
     }
@@ -157,23 +227,23 @@ pub async fn cleanup_old_images(cache_dir: &Path, days_to_keep: i64) -> Result<u
     let mut deleted_count = 0;
     let cutoff_time = chrono::Utc::now() - chrono::Duration::days(days_to_keep);
     
-    let mut entries = fs::read_dir(&images_dir).await?;
+    let mut entries = fs::read_dir(images_dir).await?;
     
     while let Some(entry) = entries.next_entry().await? {
         let path = entry.path();
         
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "png") {
-            if let Ok(metadata) = entry.metadata().await {
-                if let Ok(modified) = metadata.modified() {
-                    let modified_time: chrono::DateTime<chrono::Utc> = modified.into();
-                    if modified_time < cutoff_time {
-                        if let Err(e) = fs::remove_file(&path).await {
-                            tracing::warn!("Failed to delete old image {:?}: {}", path, e);
-                        } else {
-                            deleted_count += 1;
-                            tracing::debug!("Deleted old image: {:?}", path);
-                        }
-                    }
+        if path.is_file()
+            && path.extension().is_some_and(|ext| ext == "png")
+            && let Ok(metadata) = entry.metadata().await
+            && let Ok(modified) = metadata.modified()
+        {
+            let modified_time: chrono::DateTime<chrono::Utc> = modified.into();
+            if modified_time < cutoff_time {
+                if let Err(e) = fs::remove_file(&path).await {
+                    tracing::warn!("Failed to delete old image {:?}: {}", path, e);
+                } else {
+                    deleted_count += 1;
+                    tracing::debug!("Deleted old image: {:?}", path);
                 }
             }
         }
@@ -212,6 +282,56 @@ mod tests {
         let _ = fs::remove_dir_all(&temp_dir).await;
     }
 
+    #[test]
+    fn test_parse_cache_versions() {
+        let satellites = vec![SatelliteInfo::new("AO-91"), SatelliteInfo::new("SO-50")];
+
+        // Version 0: bare array
+        let v0 = serde_json::to_string(&satellites).unwrap();
+        let loaded = parse_satellite_cache(&v0).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[1].name, "SO-50");
+
+        // Version 1: wrapped
+        let v1 = serde_json::json!({ "version": 1, "satellites": satellites }).to_string();
+        let loaded = parse_satellite_cache(&v1).unwrap();
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].name, "AO-91");
+
+        let future = serde_json::json!({ "version": 99, "satellites": [] }).to_string();
+        assert!(parse_satellite_cache(&future).is_err());
+    }
+
+    #[test]
+    fn test_parse_cache_skips_corrupt_entry() {
+        let good = serde_json::to_value(SatelliteInfo::new("AO-91")).unwrap();
+        let also_good = serde_json::to_value(SatelliteInfo::new("SO-50")).unwrap();
+        let corrupt = serde_json::json!({ "name": "XW-2A", "data_blocks": "not a list" });
+
+        let content = serde_json::json!({
+            "version": CACHE_VERSION,
+            "satellites": [good, corrupt, also_good],
+        })
+        .to_string();
+
+        let loaded = parse_satellite_cache(&content).unwrap();
+        let names: Vec<&str> = loaded.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["AO-91", "SO-50"]);
+    }
+
+    #[tokio::test]
+    async fn test_saved_cache_is_versioned() {
+        let temp_dir = std::env::temp_dir().join("rinko_test_cache_version");
+        let _ = fs::remove_dir_all(&temp_dir).await;
+
+        save_satellite_cache(&temp_dir, &[SatelliteInfo::new("AO-91")]).await.unwrap();
+        let content = fs::read_to_string(temp_dir.join(SATELLITE_CACHE_FILE)).await.unwrap();
+        let value: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(value["version"], CACHE_VERSION);
+
+        let _ = fs::remove_dir_all(&temp_dir).await;
+    }
+
     #[tokio::test]
     async fn test_save_and_load_list() {
         let temp_dir = std::env::temp_dir().join("rinko_test_list");
@@ -0,0 +1,117 @@
+//! Maidenhead grid locator helpers
+//!
+//! Converts grid squares to coordinates and computes great-circle
+//! distance and initial bearing between two locations.
+
+/// Mean Earth radius in kilometers
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Convert a Maidenhead locator (2, 4, 6 or 8 characters) to the
+/// latitude/longitude of the center of that square
+///
+/// Returns `None` for malformed locators.
+pub fn maidenhead_to_latlon(grid: &str) -> Option<(f64, f64)> {
+    let chars: Vec<char> = grid.trim().to_ascii_uppercase().chars().collect();
+    if chars.is_empty() || chars.len() > 8 || !chars.len().is_multiple_of(2) {
+        return None;
+    }
+
+    // South-west corner and size of the current square in degrees
+    let mut lon = -180.0;
+    let mut lat = -90.0;
+    let mut lon_size = 360.0;
+    let mut lat_size = 180.0;
+
+    for (i, pair) in chars.chunks(2).enumerate() {
+        let (divisions, lon_idx, lat_idx) = match i {
+            // Field: A-R
+            0 => (18.0, letter_index(pair[0], 'R')?, letter_index(pair[1], 'R')?),
+            // Square: 0-9
+            1 | 3 => (10.0, pair[0].to_digit(10)? as f64, pair[1].to_digit(10)? as f64),
+            // Subsquare: A-X
+            _ => (24.0, letter_index(pair[0], 'X')?, letter_index(pair[1], 'X')?),
+        };
+
+        lon_size /= divisions;
+        lat_size /= divisions;
+        lon += lon_idx * lon_size;
+        lat += lat_idx * lat_size;
+    }
+
+    Some((lat + lat_size / 2.0, lon + lon_size / 2.0))
+}
+
+/// Canonical spelling of a locator: trimmed, field letters uppercase and
+/// subsquare letters lowercase (e.g. " fn31PR " -> "FN31pr")
+pub fn normalize_grid(grid: &str) -> String {
+    grid.trim()
+        .chars()
+        .enumerate()
+        .map(|(i, c)| if i < 4 { c.to_ascii_uppercase() } else { c.to_ascii_lowercase() })
+        .collect()
+}
+
+/// Zero-based index of an uppercase letter, rejecting anything past `max`
+fn letter_index(c: char, max: char) -> Option<f64> {
+    if ('A'..=max).contains(&c) {
+        Some((c as u8 - b'A') as f64)
+    } else {
+        None
+    }
+}
+
+/// Great-circle distance (km) and initial bearing (degrees, 0-360)
+/// from `from` to `to`, both given as (latitude, longitude)
+pub fn distance_bearing(from: (f64, f64), to: (f64, f64)) -> (f64, f64) {
+    let (lat1, lon1) = (from.0.to_radians(), from.1.to_radians());
+    let (lat2, lon2) = (to.0.to_radians(), to.1.to_radians());
+    let d_lat = lat2 - lat1;
+    let d_lon = lon2 - lon1;
+
+    // Haversine formula
+    let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+    let distance = 2.0 * EARTH_RADIUS_KM * a.sqrt().asin();
+
+    let y = d_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * d_lon.cos();
+    let bearing = (y.atan2(x).to_degrees() + 360.0) % 360.0;
+
+    (distance, bearing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_grid() {
+        assert_eq!(normalize_grid(" fn31PR "), "FN31pr");
+        assert_eq!(normalize_grid("jn58"), "JN58");
+    }
+
+    #[test]
+    fn test_maidenhead_to_latlon() {
+        let (lat, lon) = maidenhead_to_latlon("JN58").unwrap();
+        assert!((lat - 48.5).abs() < 1e-9);
+        assert!((lon - 11.0).abs() < 1e-9);
+
+        let (lat, lon) = maidenhead_to_latlon("fn31pr").unwrap();
+        assert!((lat - 41.729).abs() < 0.01);
+        assert!((lon - -72.708).abs() < 0.01);
+
+        assert!(maidenhead_to_latlon("").is_none());
+        assert!(maidenhead_to_latlon("ZZ00").is_none());
+        assert!(maidenhead_to_latlon("JN5").is_none());
+        assert!(maidenhead_to_latlon("JNAB").is_none());
+    }
+
+    #[test]
+    fn test_distance_bearing_jn58_to_fn31() {
+        let from = maidenhead_to_latlon("JN58").unwrap();
+        let to = maidenhead_to_latlon("FN31").unwrap();
+        let (distance, bearing) = distance_bearing(from, to);
+
+        assert!((distance - 6311.0).abs() < 10.0, "distance was {}", distance);
+        assert!((bearing - 297.0).abs() < 1.0, "bearing was {}", bearing);
+    }
+}
@@ -1,21 +1,26 @@
-///! Satellite status manager - Core business logic
+//! Satellite status manager - Core business logic
 use super::{
-    api_client, cache, scraper, search,
+    cache, search,
+    api_client::RequestBudget,
+    grid::{maidenhead_to_latlon, normalize_grid},
+    source::{StatusSource, default_sources},
     types::{
-        AmsatReport, SatelliteDataBlock, SatelliteEntry, SatelliteInfo, SatelliteList,
-        UpdateReport,
+        AmsatReport, ReportSource, ReportStatus, SatelliteDataBlock, SatelliteEntry,
+        SatelliteInfo, SatelliteList, SourceError, UpdateReport, parse_report_time, unknown_first_seen,
     },
 };
+use crate::config::SatelliteConfig;
 use anyhow::{Context, Result};
-use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono::{DateTime, Duration, DurationRound, SecondsFormat, Utc};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-const DATA_RETENTION_HOURS: i64 = 48; // Keep 48 hours of data
 const INACTIVE_THRESHOLD_HOURS: i64 = 168; // 7 days without data = inactive
-const API_REQUEST_DELAY_MS: u64 = 200; // Delay between API requests
+const RECENT_ACTIVITY_HOURS: i64 = 24; // Heard within a day = busy
+/// Modes with voice traffic, heard on most passes when active
+const VOICE_MODES: &[&str] = &["FM", "SSB", "LINEAR", "CW"];
 
 /// Satellite manager - main coordinator
 pub struct SatelliteManager {
@@ -23,11 +28,35 @@ pub struct SatelliteManager {
     satellite_list: Arc<RwLock<SatelliteList>>,
     cache_dir: PathBuf,
     update_interval_minutes: i64,
+    config: SatelliteConfig,
+    sources: Vec<Box<dyn StatusSource>>,
+    /// Last failure of each source, cleared when it next succeeds
+    source_errors: RwLock<HashMap<ReportSource, SourceError>>,
 }
 
 impl SatelliteManager {
     /// Create a new satellite manager
     pub fn new(cache_dir: impl AsRef<Path>, update_interval_minutes: i64) -> Result<Arc<Self>> {
+        Self::with_config(cache_dir, update_interval_minutes, SatelliteConfig::default())
+    }
+
+    /// Create a new satellite manager with custom satellite settings
+    pub fn with_config(
+        cache_dir: impl AsRef<Path>,
+        update_interval_minutes: i64,
+        config: SatelliteConfig,
+    ) -> Result<Arc<Self>> {
+        let sources = default_sources(&config);
+        Self::with_sources(cache_dir, update_interval_minutes, config, sources)
+    }
+
+    /// Create a new satellite manager polling the given status sources
+    pub fn with_sources(
+        cache_dir: impl AsRef<Path>,
+        update_interval_minutes: i64,
+        config: SatelliteConfig,
+        sources: Vec<Box<dyn StatusSource>>,
+    ) -> Result<Arc<Self>> {
         let cache_dir = cache_dir.as_ref().to_path_buf();
 
         Ok(Arc::new(Self {
@@ -35,6 +64,9 @@ impl SatelliteManager {
             satellite_list: Arc::new(RwLock::new(SatelliteList::default())),
             cache_dir,
             update_interval_minutes,
+            config,
+            sources,
+            source_errors: RwLock::new(HashMap::new()),
         }))
     }
 
@@ -79,11 +111,17 @@ impl SatelliteManager {
 
     /// Initialize satellite list from AMSAT
     async fn initialize_satellite_list(&self) -> Result<()> {
-        let sat_names = scraper::fetch_satellite_names_with_fallback().await;
+        let mut failures = HashMap::new();
+        let sat_names = self.filter_tracked(self.fetch_satellite_names(&mut failures).await);
+        self.record_source_errors(failures).await;
 
+        // The initial list is the baseline, not newly added satellites
         let mut list = SatelliteList::default();
         for name in sat_names {
-            list.satellites.push(SatelliteEntry::new(name));
+            list.satellites.push(SatelliteEntry {
+                first_seen: unknown_first_seen(),
+                ..SatelliteEntry::new(name)
+            });
         }
 
         cache::save_satellite_list(&self.cache_dir, &list).await?;
@@ -97,15 +135,30 @@ impl SatelliteManager {
         Ok(())
     }
 
-    /// Update all satellites (called by updater)
+    /// Update all satellites with a fresh request budget
     pub async fn update_all_satellites(&self) -> Result<UpdateReport> {
+        self.update_all_satellites_within(&self.request_budget()).await
+    }
+
+    /// Budget of upstream requests for one update cycle
+    ///
+    /// Retries of a failed update should reuse the same budget, see
+    /// [`Self::update_all_satellites_within`].
+    pub fn request_budget(&self) -> RequestBudget {
+        RequestBudget::new(self.config.max_requests_per_cycle)
+    }
+
+    /// Update all satellites, drawing upstream requests from `budget`
+    pub async fn update_all_satellites_within(&self, budget: &RequestBudget) -> Result<UpdateReport> {
         let start_time = std::time::Instant::now();
         let mut report = UpdateReport::new();
 
         tracing::info!("Starting satellite data update...");
 
-        // Fetch latest satellite names from AMSAT
-        let current_sat_names = scraper::fetch_satellite_names_with_fallback().await;
+        // Fetch latest satellite names from all sources
+        let mut failures = HashMap::new();
+        let current_sat_names = self.fetch_satellite_names(&mut failures).await;
+        let current_sat_names = self.filter_tracked(current_sat_names);
 
         // Update satellite list
         let mut list = self.satellite_list.write().await;
@@ -121,23 +174,46 @@ impl SatelliteManager {
         // Save updated list
         cache::save_satellite_list(&self.cache_dir, &list).await?;
 
-        // Collect satellite names to update
-        let sat_names_to_update: Vec<String> = list
-            .satellites
-            .iter()
-            .map(|s| s.official_name.clone())
-            .collect();
+        // Collect satellite names to update (entries listed before a
+        // denylist change are kept but no longer fetched)
+        let sat_names_to_update: Vec<String> = self.filter_tracked(
+            list.satellites.iter().map(|s| s.official_name.clone()).collect(),
+        );
 
         // Release the lock
         drop(list);
 
-        // Fetch data for all satellites
-        let fetch_results = api_client::batch_fetch_satellites(
-            &sat_names_to_update,
-            1, // Fetch last 1 hour
-            API_REQUEST_DELAY_MS,
-        )
-        .await;
+        // Fetch data for all satellites, widening the window after downtime
+        let last_success = self.last_fetch_success().await;
+        let hours = self.fetch_window_hours(last_success, Utc::now());
+        if hours > self.config.fetch_window_hours {
+            tracing::info!(
+                "Last successful fetch at {:?}, catching up with a {}h window",
+                last_success,
+                hours
+            );
+        }
+        // Quiet satellites get a longer window, grouped into one batch per window
+        let mut batches: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+        {
+            let satellites = self.satellites.read().await;
+            let now = Utc::now();
+            for sat_name in &sat_names_to_update {
+                let window = self.satellite_fetch_window(satellites.get(sat_name), hours, now);
+                batches.entry(window).or_default().push(sat_name.clone());
+            }
+        }
+        let mut fetch_results = HashMap::new();
+        for (window, sat_names) in batches {
+            tracing::debug!("Fetching {} satellites with a {}h window", sat_names.len(), window);
+            fetch_results.extend(self.fetch_reports(&sat_names, window, budget, &mut failures).await);
+        }
+        for reports in fetch_results.values_mut().filter_map(|r| r.as_mut().ok()) {
+            let fetched = reports.len();
+            *reports = std::mem::take(reports).into_iter().filter_map(Self::normalize_report).collect();
+            report.skipped_reports += fetched - reports.len();
+        }
+        self.record_source_errors(failures).await;
 
         // Update each satellite
         let mut satellites = self.satellites.write().await;
@@ -145,7 +221,7 @@ impl SatelliteManager {
             let fetch_result = fetch_results.get(&sat_name);
 
             let existing = satellites.get(&sat_name).cloned();
-            let was_active = existing.as_ref().map_or(true, |s| s.is_active);
+            let was_active = existing.as_ref().is_none_or(|s| s.is_active);
             match self.update_single_satellite(&sat_name, existing, fetch_result).await {
                 Ok(updated_sat) => {
                     // Check if satellite became inactive
@@ -175,6 +251,9 @@ impl SatelliteManager {
             report.failed_updates,
             report.duration_seconds
         );
+        if report.skipped_reports > 0 {
+            tracing::warn!("Skipped {} empty reports", report.skipped_reports);
+        }
 
         if !report.new_satellites.is_empty() {
             tracing::info!("New satellites: {:?}", report.new_satellites);
@@ -186,6 +265,206 @@ impl SatelliteManager {
         Ok(report)
     }
 
+    /// Drop names excluded by the configured allowlist/denylist
+    fn filter_tracked(&self, names: Vec<String>) -> Vec<String> {
+        let (allow, deny) = (&self.config.allowlist, &self.config.denylist);
+        if allow.is_empty() && deny.is_empty() {
+            return names;
+        }
+        names
+            .into_iter()
+            .filter(|name| {
+                let keys = [
+                    search::normalize_string(name),
+                    search::normalize_string(&search::parse_amsat_name(name).base),
+                ];
+                let listed = |list: &[String]| {
+                    list.iter().any(|entry| keys.contains(&search::normalize_string(entry)))
+                };
+                !listed(deny) && (allow.is_empty() || listed(allow))
+            })
+            .collect()
+    }
+
+    /// Union of the satellite names reported by all sources, in first-seen order
+    ///
+    /// Sources whose list could not be fetched are added to `failures`.
+    async fn fetch_satellite_names(&self, failures: &mut HashMap<ReportSource, String>) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut seen = HashSet::new();
+        for source in &self.sources {
+            match source.fetch_satellite_list().await {
+                Ok(list) => names.extend(list.into_iter().filter(|n| seen.insert(n.clone()))),
+                Err(e) => {
+                    tracing::warn!(
+                        "Failed to fetch satellite list from {:?}: {}",
+                        source.report_source(),
+                        e
+                    );
+                    failures
+                        .entry(source.report_source())
+                        .or_insert_with(|| format!("Satellite list: {:#}", e));
+                }
+            }
+        }
+        names
+    }
+
+    /// Replace the recorded source errors with this cycle's outcome
+    ///
+    /// Sources in `failures` get a new error; all others are cleared.
+    async fn record_source_errors(&self, failures: HashMap<ReportSource, String>) {
+        let now = Utc::now();
+        let mut errors = self.source_errors.write().await;
+        for source in &self.sources {
+            let tag = source.report_source();
+            match failures.get(&tag) {
+                Some(message) => {
+                    errors.insert(tag, SourceError { source: tag, time: now, message: message.clone() });
+                }
+                None => {
+                    errors.remove(&tag);
+                }
+            }
+        }
+    }
+
+    /// Most recent failure of each source that has not succeeded since
+    pub async fn source_errors(&self) -> Vec<SourceError> {
+        let mut errors: Vec<SourceError> = self.source_errors.read().await.values().cloned().collect();
+        errors.sort_by_key(|e| e.source);
+        errors
+    }
+
+    /// Fetch reports from all sources and merge them per satellite
+    ///
+    /// Each report is tagged with its source. A satellite's fetch succeeds
+    /// if any source succeeded for it. Sources that failed for any
+    /// satellite are added to `failures`.
+    async fn fetch_reports(
+        &self,
+        sat_names: &[String],
+        hours: u64,
+        budget: &RequestBudget,
+        failures: &mut HashMap<ReportSource, String>,
+    ) -> HashMap<String, Result<Vec<AmsatReport>>> {
+        let mut merged: HashMap<String, Result<Vec<AmsatReport>>> = HashMap::new();
+
+        for source in &self.sources {
+            let tag = source.report_source();
+            let mut results = source.fetch_reports(sat_names, hours, budget).await;
+
+            let failed: Vec<(&String, String)> = sat_names
+                .iter()
+                .filter_map(|name| match results.get(name) {
+                    Some(Ok(_)) => None,
+                    Some(Err(e)) => Some((name, format!("{:#}", e))),
+                    None => Some((name, "no result".to_string())),
+                })
+                .collect();
+            if let Some((name, error)) = failed.first() {
+                failures.entry(tag).or_insert_with(|| {
+                    format!(
+                        "{} of {} satellites failed, e.g. {}: {}",
+                        failed.len(),
+                        sat_names.len(),
+                        name,
+                        error
+                    )
+                });
+            }
+
+            for name in sat_names {
+                let result = results
+                    .remove(name)
+                    .unwrap_or_else(|| Err(anyhow::anyhow!("{:?} returned no result", tag)));
+                let result = result.map(|reports| {
+                    reports
+                        .into_iter()
+                        .map(|report| AmsatReport { source: tag, ..report })
+                        .collect::<Vec<_>>()
+                });
+
+                match (merged.remove(name), result) {
+                    (Some(Ok(mut existing)), Ok(reports)) => {
+                        existing.extend(reports);
+                        merged.insert(name.clone(), Ok(existing));
+                    }
+                    (Some(Ok(existing)), Err(_)) => {
+                        merged.insert(name.clone(), Ok(existing));
+                    }
+                    (_, result) => {
+                        merged.insert(name.clone(), result);
+                    }
+                }
+            }
+        }
+
+        merged
+    }
+
+    /// Most recent successful fetch across all satellites
+    ///
+    /// Persisted in the satellite cache, so it survives restarts.
+    async fn last_fetch_success(&self) -> Option<DateTime<Utc>> {
+        let satellites = self.satellites.read().await;
+        satellites.values().filter_map(|s| s.last_fetch_success).max()
+    }
+
+    /// Hours of history to request from AMSAT this cycle
+    ///
+    /// Normally `fetch_window_hours`; when the last successful fetch is
+    /// older than that, the window grows to cover the gap, capped at
+    /// `max_catch_up_hours`.
+    fn fetch_window_hours(&self, last_success: Option<DateTime<Utc>>, now: DateTime<Utc>) -> u64 {
+        let retention = self.retention_hours();
+        let base = self.config.fetch_window_hours.clamp(1, retention);
+        let Some(last_success) = last_success else {
+            return base;
+        };
+
+        let gap_minutes = (now - last_success).num_minutes().max(0) as u64;
+        let gap_hours = gap_minutes.div_ceil(60);
+        if gap_hours > base {
+            gap_hours.min(self.config.max_catch_up_hours.max(base)).min(retention)
+        } else {
+            base
+        }
+    }
+
+    /// Hours of report blocks kept, at least one
+    pub fn retention_hours(&self) -> u64 {
+        self.config.retention_hours.max(1)
+    }
+
+    /// Fetch window for one satellite given the regular/catch-up window
+    ///
+    /// Busy satellites (active, heard in the last day, on a voice mode or
+    /// transponder) keep `hours`; quiet ones get at least
+    /// `quiet_fetch_window_hours`, since their sparse reports would otherwise
+    /// fall outside a short window. Unknown satellites count as busy.
+    fn satellite_fetch_window(&self, sat: Option<&SatelliteInfo>, hours: u64, now: DateTime<Utc>) -> u64 {
+        let Some(sat) = sat else {
+            return hours;
+        };
+
+        let recently_heard = sat
+            .latest_report()
+            .and_then(AmsatReport::reported_at)
+            .is_some_and(|t| now.signed_duration_since(t) <= Duration::hours(RECENT_ACTIVITY_HOURS));
+        let voice = match search::parse_amsat_name(&sat.name).mode {
+            None => true,
+            // Bracketed transponder modes such as "V/u"
+            Some(mode) => mode.contains('/') || VOICE_MODES.iter().any(|m| m.eq_ignore_ascii_case(&mode)),
+        };
+
+        if sat.is_active && recently_heard && voice {
+            hours
+        } else {
+            hours.max(self.config.quiet_fetch_window_hours).min(self.retention_hours())
+        }
+    }
+
     /// Update a single satellite
     async fn update_single_satellite(
         &self,
@@ -200,6 +479,7 @@ impl SatelliteManager {
         if let Some(entry) = list.satellites.iter().find(|s| s.official_name == sat_name) {
             info.aliases = entry.aliases.clone();
             info.catalog_number = entry.catalog_number.clone();
+            info.first_seen = entry.first_seen;
         }
         drop(list);
 
@@ -207,7 +487,11 @@ impl SatelliteManager {
         match fetch_result {
             Some(Ok(new_reports)) if !new_reports.is_empty() => {
                 // Successful fetch
-                info.data_blocks = Self::merge_reports(info.data_blocks, new_reports.clone());
+                info.data_blocks = Self::merge_reports(
+                    info.data_blocks,
+                    new_reports.clone(),
+                    Duration::minutes(self.config.future_report_tolerance_minutes),
+                );
                 info.last_fetch_success = Some(Utc::now());
                 info.amsat_update_status = true;
             }
@@ -221,8 +505,8 @@ impl SatelliteManager {
             }
         }
 
-        // Clean up old data (keep only last 48 hours)
-        Self::clean_old_data(&mut info.data_blocks, DATA_RETENTION_HOURS);
+        // Clean up blocks past the retention window
+        Self::clean_old_data(&mut info.data_blocks, self.retention_hours() as i64);
 
         // Update metadata
         info.last_updated = Utc::now();
@@ -237,43 +521,119 @@ impl SatelliteManager {
         Ok(info)
     }
 
+    /// Fill in missing report fields, or None for a report with no content
+    ///
+    /// Callsigns are trimmed and valid grids get their canonical spelling;
+    /// other values are kept as sent (trimmed) and only shown as
+    /// `MISSING_FIELD` when rendered. Times in other layouts than RFC3339
+    /// are rewritten as RFC3339 when readable.
+    /// Reports without callsign, grid and status are dropped.
+    fn normalize_report(report: AmsatReport) -> Option<AmsatReport> {
+        let callsign = report.callsign.trim();
+        let grid = normalize_grid(&report.grid_square);
+        let status = report.report.trim();
+        if callsign.is_empty() && grid.is_empty() && status.is_empty() {
+            tracing::debug!("Skipping empty report for {} at {}", report.name, report.reported_time);
+            return None;
+        }
+
+        let reported_time = match parse_report_time(&report.reported_time) {
+            Some(time) if DateTime::parse_from_rfc3339(&report.reported_time).is_err() => {
+                time.to_rfc3339_opts(SecondsFormat::Secs, true)
+            }
+            _ => report.reported_time.clone(),
+        };
+
+        Some(AmsatReport {
+            reported_time,
+            callsign: callsign.to_string(),
+            grid_square: if maidenhead_to_latlon(&grid).is_some() {
+                grid
+            } else {
+                report.grid_square.trim().to_string()
+            },
+            report: status.to_string(),
+            ..report
+        })
+    }
+
     /// Merge new reports into existing data blocks
+    ///
+    /// Reports dated more than `future_tolerance` ahead of now are dropped,
+    /// so clock-skewed upstream data cannot create "future" blocks. A report
+    /// is identified by (callsign, reported_time) across all blocks, so one
+    /// that landed in two adjacent hour blocks is kept once. The copy in the
+    /// block of its own hour wins over the earliest-seen one: existing blocks
+    /// arrive newest first, so "earliest-seen" alone would move a report made
+    /// just before the hour into the next block. Only when no copy sits in
+    /// its own hour is the earliest-seen copy kept.
+    ///
+    /// Blocks are keyed by the hour they start at, not their text, so an
+    /// existing block written in another RFC3339 spelling ("Z" or "+00:00")
+    /// still receives the new reports of its hour.
     fn merge_reports(
         existing: Vec<SatelliteDataBlock>,
         new_reports: Vec<AmsatReport>,
+        future_tolerance: Duration,
     ) -> Vec<SatelliteDataBlock> {
-        let mut grouped: BTreeMap<String, Vec<AmsatReport>> = BTreeMap::new();
+        let latest_allowed = Utc::now() + future_tolerance;
+        let mut grouped: BTreeMap<DateTime<Utc>, Vec<AmsatReport>> = BTreeMap::new();
+        let mut seen_reports: HashSet<(String, String)> = HashSet::new();
 
-        // Group existing reports
+        let mut existing_reports: Vec<(DateTime<Utc>, AmsatReport)> = Vec::new();
         for block in existing {
-            grouped
-                .entry(block.time.clone())
-                .or_default()
-                .extend(block.reports);
+            let Some(block_time) = parse_report_time(&block.time) else {
+                tracing::warn!("Dropping data block with unreadable time '{}'", block.time);
+                continue;
+            };
+            let block_hour = hour_block(block_time);
+            existing_reports.extend(block.reports.into_iter().map(|report| (block_hour, report)));
+        }
+
+        // Of a report's copies in several blocks, keep the one in its own hour
+        let in_own_hour = |(block_hour, report): &(DateTime<Utc>, AmsatReport)| {
+            report.reported_at().is_some_and(|time| hour_block(time) == *block_hour)
+        };
+        let mut kept: HashMap<(String, String), usize> = HashMap::new();
+        for (index, entry) in existing_reports.iter().enumerate() {
+            let key = (entry.1.callsign.clone(), entry.1.reported_time.clone());
+            let current = *kept.entry(key.clone()).or_insert(index);
+            if !in_own_hour(&existing_reports[current]) && in_own_hour(entry) {
+                kept.insert(key, index);
+            }
+        }
+
+        // Group existing reports (dropping cross-block duplicates)
+        for (index, (block_hour, report)) in existing_reports.into_iter().enumerate() {
+            let key = (report.callsign.clone(), report.reported_time.clone());
+            if kept.get(&key) == Some(&index) {
+                seen_reports.insert(key);
+                grouped.entry(block_hour).or_default().push(report);
+            }
         }
 
         // Add new reports
         for report in new_reports {
             // Parse and normalize time to hour block
-            if let Ok(datetime) = DateTime::parse_from_rfc3339(&report.reported_time) {
-                let utc_time = datetime.with_timezone(&Utc);
+            if let Some(utc_time) = report.reported_at() {
+
+                // Skip future reports (beyond the skew tolerance)
+                if utc_time > latest_allowed {
+                    tracing::debug!(
+                        "Dropping future report for {} from {} at {}",
+                        report.name,
+                        report.callsign,
+                        report.reported_time
+                    );
+                    continue;
+                }
 
-                // Skip future reports (with 5 min tolerance)
-                if utc_time > Utc::now() + Duration::minutes(5) {
+                // Skip reports already present in any block
+                if !seen_reports.insert((report.callsign.clone(), report.reported_time.clone())) {
                     continue;
                 }
 
-                // Round down to hour
-                let hour_block = utc_time
-                    .with_minute(0)
-                    .unwrap()
-                    .with_second(0)
-                    .unwrap()
-                    .with_nanosecond(0)
-                    .unwrap()
-                    .to_rfc3339();
-
-                grouped.entry(hour_block).or_default().push(report);
+                grouped.entry(hour_block(utc_time)).or_default().push(report);
             }
         }
 
@@ -290,28 +650,20 @@ impl SatelliteManager {
             });
         }
 
-        // Convert back to Vec and sort by time (descending)
-        let mut blocks: Vec<SatelliteDataBlock> = grouped
+        // Convert back to Vec (dropping blocks emptied by dedup), newest first
+        grouped
             .into_iter()
-            .map(|(time, reports)| SatelliteDataBlock { time, reports })
-            .collect();
-
-        blocks.sort_by(|a, b| b.time.cmp(&a.time));
-
-        blocks
+            .rev()
+            .filter(|(_, reports)| !reports.is_empty())
+            .map(|(time, reports)| SatelliteDataBlock { time: time.to_rfc3339(), reports })
+            .collect()
     }
 
     /// Clean old data blocks (older than retention period)
     fn clean_old_data(blocks: &mut Vec<SatelliteDataBlock>, retention_hours: i64) {
         let cutoff = Utc::now() - Duration::hours(retention_hours);
 
-        blocks.retain(|block| {
-            if let Ok(block_time) = DateTime::parse_from_rfc3339(&block.time) {
-                block_time.with_timezone(&Utc) >= cutoff
-            } else {
-                false
-            }
-        });
+        blocks.retain(|block| parse_report_time(&block.time).is_some_and(|block_time| block_time >= cutoff));
     }
 
     /// Query a single satellite by name
@@ -350,6 +702,96 @@ impl SatelliteManager {
         Ok(results)
     }
 
+    /// Suggest similar satellite names for a query with no matches
+    pub async fn suggest_similar(&self, query: &str, limit: usize) -> Vec<String> {
+        let list = self.satellite_list.read().await;
+        search::suggest_similar(query, &list, limit)
+    }
+
+    /// Record a report submitted by a user
+    ///
+    /// [`Self::validate_user_report`] followed by [`Self::record_user_report`].
+    /// Returns the satellite's official name.
+    pub async fn submit_user_report(
+        &self,
+        satellite: &str,
+        status: &str,
+        grid: &str,
+        callsign: &str,
+    ) -> Result<String> {
+        let report = self.validate_user_report(satellite, status, grid, callsign).await?;
+        self.record_user_report(report).await
+    }
+
+    /// Check a user report and build it, without recording anything
+    ///
+    /// The satellite is resolved like a query, the status must be one a
+    /// single station can observe and the grid a 4 or 6 character locator.
+    /// The report is tagged as user-sourced and dated now.
+    pub async fn validate_user_report(
+        &self,
+        satellite: &str,
+        status: &str,
+        grid: &str,
+        callsign: &str,
+    ) -> Result<AmsatReport> {
+        let status = ReportStatus::from_user_input(status).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown status '{}'. Use heard, not-heard, telemetry or crew.",
+                status.trim()
+            )
+        })?;
+
+        let grid = grid.trim().to_uppercase();
+        if !matches!(grid.len(), 4 | 6) || maidenhead_to_latlon(&grid).is_none() {
+            anyhow::bail!("Invalid grid square '{}'. Example: JN58 or JN58td.", grid);
+        }
+
+        let callsign = callsign.trim();
+        if callsign.is_empty() {
+            anyhow::bail!("A callsign is required to submit a report");
+        }
+
+        let name = match self.query_satellite(satellite.trim()).await? {
+            Some(info) => info.name,
+            None => anyhow::bail!("Unknown satellite '{}'", satellite.trim()),
+        };
+
+        Ok(AmsatReport {
+            name,
+            reported_time: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            callsign: callsign.to_string(),
+            report: status.to_report_format(),
+            grid_square: grid,
+            source: ReportSource::User,
+        })
+    }
+
+    /// Merge a report from [`Self::validate_user_report`] into the current
+    /// hour block and persist it. Returns the satellite's official name.
+    pub async fn record_user_report(&self, report: AmsatReport) -> Result<String> {
+        let name = report.name.clone();
+        let (callsign, status) = (report.callsign.clone(), report.report.clone());
+
+        let mut satellites = self.satellites.write().await;
+        let info = satellites
+            .entry(name.clone())
+            .or_insert_with(|| SatelliteInfo::new(&name));
+        info.data_blocks = Self::merge_reports(
+            std::mem::take(&mut info.data_blocks),
+            vec![report],
+            Duration::minutes(self.config.future_report_tolerance_minutes),
+        );
+
+        let sat_vec: Vec<SatelliteInfo> = satellites.values().cloned().collect();
+        drop(satellites);
+        cache::save_satellite_cache(&self.cache_dir, &sat_vec).await?;
+
+        tracing::info!("User report for {} from {}: {}", name, callsign, status);
+
+        Ok(name)
+    }
+
     /// Get all active satellites
     pub async fn get_active_satellites(&self) -> Vec<SatelliteInfo> {
         let satellites = self.satellites.read().await;
@@ -366,6 +808,20 @@ impl SatelliteManager {
         satellites.values().cloned().collect()
     }
 
+    /// Number of tracked satellites, including inactive ones
+    pub async fn satellite_count(&self) -> usize {
+        self.satellites.read().await.len()
+    }
+
+    /// Time of the most recent completed update, if any satellite is loaded
+    ///
+    /// Every update cycle stamps all satellites, including ones whose
+    /// fetch failed, so this tracks whether the updater is still running.
+    pub async fn last_update(&self) -> Option<DateTime<Utc>> {
+        let satellites = self.satellites.read().await;
+        satellites.values().map(|s| s.last_updated).max()
+    }
+
     /// Reload satellite list from file (for hot reload)
     pub async fn reload_satellite_list(&self) -> Result<()> {
         tracing::info!("Reloading satellite list from file...");
@@ -392,9 +848,16 @@ impl SatelliteManager {
     }
 }
 
+/// Start of the UTC hour `time` falls in (a report at 08:00:00 opens the
+/// 08:00 block)
+fn hour_block(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.duration_trunc(Duration::hours(1)).unwrap_or(time)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::module::sat::test_support::{FakeSource, fresh_dir, manager_with_names, manager_with_sources};
 
     #[tokio::test]
     async fn test_manager_creation() {
@@ -403,6 +866,312 @@ mod tests {
         assert_eq!(manager.update_interval_minutes(), 10);
     }
 
+    #[tokio::test]
+    async fn test_reports_merged_from_multiple_sources() {
+        let sources: Vec<Box<dyn StatusSource>> = vec![
+            Box::new(FakeSource::new(&["AO-91"]).with_reports(ReportSource::Amsat, "AMSAT1")),
+            Box::new(FakeSource::new(&["AO-91"]).with_reports(ReportSource::User, "USER1")),
+        ];
+        let manager = manager_with_sources("rinko_test_multi_source", SatelliteConfig::default(), sources).await;
+
+        let report = manager.update_all_satellites().await.unwrap();
+        assert_eq!(report.successful_updates, 1);
+
+        let info = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        let sources: HashMap<String, ReportSource> = info
+            .data_blocks
+            .iter()
+            .flat_map(|b| b.reports.iter())
+            .map(|r| (r.callsign.clone(), r.source))
+            .collect();
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources["AMSAT1"], ReportSource::Amsat);
+        assert_eq!(sources["USER1"], ReportSource::User);
+    }
+
+    /// Run one update with the given lists; returns the tracked and fetched names
+    async fn update_with_lists(
+        dir_name: &str,
+        allowlist: &[&str],
+        denylist: &[&str],
+    ) -> (Vec<String>, Vec<String>) {
+        let source = FakeSource::new(&["AO-91", "ISS-FM", "ISS-SSTV", "SO-50"]);
+        let fetched = source.fetched.clone();
+        let config = SatelliteConfig {
+            allowlist: allowlist.iter().map(|s| s.to_string()).collect(),
+            denylist: denylist.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        let manager = manager_with_sources(dir_name, config, vec![Box::new(source)]).await;
+        manager.update_all_satellites().await.unwrap();
+
+        let list = cache::load_satellite_list(manager.cache_dir()).await.unwrap();
+        let tracked = list.satellites.into_iter().map(|s| s.official_name).collect();
+        let fetched = fetched.lock().unwrap().clone();
+        (tracked, fetched)
+    }
+
+    #[tokio::test]
+    async fn test_denylisted_satellites_are_skipped() {
+        let (tracked, fetched) = update_with_lists("rinko_test_denylist", &[], &["so50"]).await;
+        assert_eq!(tracked, vec!["AO-91", "ISS-FM", "ISS-SSTV"]);
+        assert_eq!(fetched, vec!["AO-91", "ISS-FM", "ISS-SSTV"]);
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_restricts_fetched_satellites() {
+        // "ISS" matches every ISS entry by base name; the denylist wins
+        let (tracked, fetched) =
+            update_with_lists("rinko_test_allowlist", &["ISS", "AO-91"], &["ISS-SSTV"]).await;
+        assert_eq!(tracked, vec!["AO-91", "ISS-FM"]);
+        assert_eq!(fetched, vec!["AO-91", "ISS-FM"]);
+    }
+
+    #[tokio::test]
+    async fn test_source_error_set_on_failure_and_cleared_on_success() {
+        let source = FakeSource::new(&["AO-91"]);
+        let failing = source.failing.clone();
+        failing.store(true, std::sync::atomic::Ordering::SeqCst);
+        let manager =
+            manager_with_sources("rinko_test_source_errors", SatelliteConfig::default(), vec![Box::new(source)]).await;
+        assert!(manager.source_errors().await.is_empty());
+
+        let before = Utc::now();
+        manager.update_all_satellites().await.unwrap();
+        let errors = manager.source_errors().await;
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].source, ReportSource::Amsat);
+        assert!(errors[0].time >= before);
+        assert_eq!(errors[0].message, "1 of 1 satellites failed, e.g. AO-91: HTTP error 503");
+
+        failing.store(false, std::sync::atomic::Ordering::SeqCst);
+        manager.update_all_satellites().await.unwrap();
+        assert!(manager.source_errors().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_from_fixture_source() {
+        let temp_dir = fresh_dir("rinko_test_fixture_source").await;
+
+        let config = SatelliteConfig {
+            fixture_dir: Some("resources/fixtures/amsat".to_string()),
+            ..Default::default()
+        };
+        let manager = SatelliteManager::with_config(&temp_dir, 10, config).unwrap();
+        manager.initialize().await.unwrap();
+        let report = manager.update_all_satellites().await.unwrap();
+        assert_eq!(report.total_satellites, 3);
+        assert_eq!(report.failed_updates, 0);
+
+        let callsigns = |info: &SatelliteInfo| -> Vec<String> {
+            info.data_blocks
+                .iter()
+                .flat_map(|block| block.reports.iter().map(|r| r.callsign.clone()))
+                .collect()
+        };
+        let ao91 = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        assert_eq!(callsigns(&ao91), vec!["BG5FNA", "W1AW"]);
+        assert_eq!(ao91.data_blocks.len(), 2);
+        // Times keep their minutes, moved so the newest is in the last hour
+        assert!(ao91.data_blocks[0].reports[0].reported_time.ends_with(":20:00Z"));
+        let iss = manager.query_satellite("ISS-FM").await.unwrap().unwrap();
+        assert_eq!(callsigns(&iss), vec!["JA1XYZ"]);
+        let so50 = manager.query_satellite("SO-50").await.unwrap().unwrap();
+        assert!(so50.data_blocks.is_empty());
+
+        // A second update in the same hour adds nothing new
+        manager.update_all_satellites().await.unwrap();
+        let ao91 = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        assert_eq!(callsigns(&ao91), vec!["BG5FNA", "W1AW"]);
+    }
+
+    #[tokio::test]
+    async fn test_reports_with_missing_fields_normalized() {
+        let temp_dir = fresh_dir("rinko_test_missing_fields").await;
+        let fixture_dir = temp_dir.join("fixture");
+        tokio::fs::create_dir_all(&fixture_dir).await.unwrap();
+        tokio::fs::copy("resources/fixtures/amsat/satellites.html", fixture_dir.join("satellites.html"))
+            .await
+            .unwrap();
+        let report = |callsign: &str, grid: &str, status: &str, time: &str| AmsatReport {
+            name: "AO-91".to_string(),
+            reported_time: time.to_string(),
+            callsign: callsign.to_string(),
+            report: status.to_string(),
+            grid_square: grid.to_string(),
+            ..Default::default()
+        };
+        let reports = HashMap::from([(
+            "AO-91",
+            vec![
+                report("", " om89ab ", "Heard", "2026-02-16T08:20:00Z"),
+                report("W1AW", "ZZ99", "Heard", "2026-02-16T08:10:00Z"),
+                report(" ", " ", "", "2026-02-16T08:05:00Z"),
+            ],
+        )]);
+        tokio::fs::write(fixture_dir.join("reports.json"), serde_json::to_string(&reports).unwrap())
+            .await
+            .unwrap();
+
+        let config = SatelliteConfig {
+            fixture_dir: Some(fixture_dir.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let manager = SatelliteManager::with_config(&temp_dir, 10, config).unwrap();
+        manager.initialize().await.unwrap();
+        let update = manager.update_all_satellites().await.unwrap();
+        assert_eq!(update.skipped_reports, 1);
+
+        let ao91 = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        let mut rows: Vec<(String, String)> = ao91
+            .data_blocks
+            .iter()
+            .flat_map(|b| b.reports.iter())
+            .map(|r| (r.callsign.clone(), r.grid_square.clone()))
+            .collect();
+        rows.sort();
+        assert_eq!(
+            rows,
+            vec![
+                (String::new(), "OM89ab".to_string()),
+                ("W1AW".to_string(), "ZZ99".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_first_seen_set_on_discovery_and_preserved() {
+        let temp_dir = fresh_dir("rinko_test_first_seen").await;
+
+        // A list written before first-seen tracking
+        tokio::fs::write(
+            temp_dir.join("satellite_list.toml"),
+            "[[satellites]]\nofficial_name = \"SO-50\"\naliases = []\n",
+        )
+        .await
+        .unwrap();
+
+        let sources: Vec<Box<dyn StatusSource>> =
+            vec![Box::new(FakeSource::new(&["AO-91"]).with_reports(ReportSource::Amsat, "W1AW"))];
+        let manager =
+            SatelliteManager::with_sources(&temp_dir, 10, SatelliteConfig::default(), sources)
+                .unwrap();
+        manager.initialize().await.unwrap();
+        let report = manager.update_all_satellites().await.unwrap();
+        assert_eq!(report.new_satellites, vec!["AO-91"]);
+
+        let ao91 = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        assert!(Utc::now() - ao91.first_seen < Duration::minutes(1));
+        let so50 = manager.query_satellite("SO-50").await.unwrap().unwrap();
+        assert_eq!(so50.first_seen, unknown_first_seen());
+
+        // Survives a reload from cache
+        let list = cache::load_satellite_list(&temp_dir).await.unwrap();
+        let entry = list.satellites.iter().find(|s| s.official_name == "AO-91").unwrap();
+        assert_eq!(entry.first_seen, ao91.first_seen);
+        let cached = cache::load_satellite_cache(&temp_dir).await.unwrap();
+        let info = cached.iter().find(|s| s.name == "AO-91").unwrap();
+        assert_eq!(info.first_seen, ao91.first_seen);
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_fetch_window_after_downtime() {
+        let manager = manager_with_names("rinko_test_fetch_window", &["AO-91"]).await;
+        let now = Utc::now();
+
+        // No fetch recorded yet, or a recent one: regular window
+        assert_eq!(manager.fetch_window_hours(None, now), 1);
+        assert_eq!(manager.fetch_window_hours(Some(now - Duration::minutes(50)), now), 1);
+
+        // Simulated downtime since the last cached success widens the window
+        let mut sat = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        sat.last_fetch_success = Some(now - Duration::minutes(5 * 60 + 10));
+        manager.satellites.write().await.insert(sat.name.clone(), sat);
+        let last_success = manager.last_fetch_success().await;
+        assert_eq!(manager.fetch_window_hours(last_success, now), 6);
+
+        // ...but never beyond the configured cap
+        assert_eq!(manager.fetch_window_hours(Some(now - Duration::days(10)), now), 48);
+
+        // Windows never reach past the retained history
+        let short_retention = SatelliteManager::with_config(
+            std::env::temp_dir().join("rinko_test_fetch_window_retention"),
+            10,
+            SatelliteConfig { retention_hours: 12, fetch_window_hours: 24, ..Default::default() },
+        )
+        .unwrap();
+        assert_eq!(short_retention.fetch_window_hours(None, now), 12);
+        assert_eq!(short_retention.fetch_window_hours(Some(now - Duration::days(10)), now), 12);
+        assert_eq!(short_retention.satellite_fetch_window(Some(&SatelliteInfo::new("RS-44")), 1, now), 6);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_window_by_activity_class() {
+        let manager = manager_with_names("rinko_test_activity_window", &["AO-91"]).await;
+        let now = Utc::now();
+        let heard = |name: &str, hours_ago: i64| {
+            let mut sat = SatelliteInfo::new(name);
+            sat.data_blocks.push(SatelliteDataBlock {
+                time: now.to_rfc3339(),
+                reports: vec![AmsatReport {
+                    reported_time: (now - Duration::hours(hours_ago)).to_rfc3339(),
+                    report: "Heard".to_string(),
+                    ..Default::default()
+                }],
+            });
+            sat
+        };
+
+        // Busy FM and transponder birds keep the regular (or catch-up) window
+        assert_eq!(manager.satellite_fetch_window(Some(&heard("ISS-FM", 1)), 1, now), 1);
+        assert_eq!(manager.satellite_fetch_window(Some(&heard("FO-118[H/u]", 2)), 1, now), 1);
+        assert_eq!(manager.satellite_fetch_window(Some(&heard("SO-50", 1)), 12, now), 12);
+        assert_eq!(manager.satellite_fetch_window(None, 1, now), 1);
+
+        // Inactive, long unheard or non-voice satellites get the long window
+        let mut inactive = heard("AO-07", 1);
+        inactive.is_active = false;
+        assert_eq!(manager.satellite_fetch_window(Some(&inactive), 1, now), 6);
+        assert_eq!(manager.satellite_fetch_window(Some(&heard("AO-91", 48)), 1, now), 6);
+        assert_eq!(manager.satellite_fetch_window(Some(&SatelliteInfo::new("RS-44")), 1, now), 6);
+        assert_eq!(manager.satellite_fetch_window(Some(&heard("ISS-SSTV", 1)), 1, now), 6);
+    }
+
+    #[tokio::test]
+    async fn test_submit_user_report() {
+        let manager = manager_with_names("rinko_test_submit_report", &["AO-91"]).await;
+
+        let name = manager
+            .submit_user_report("ao-91", "heard", "jn58", "BG5FNA")
+            .await
+            .unwrap();
+        assert_eq!(name, "AO-91");
+
+        let info = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        let report = &info.data_blocks[0].reports[0];
+        assert_eq!(report.callsign, "BG5FNA");
+        assert_eq!(report.grid_square, "JN58");
+        assert_eq!(report.report, "Heard");
+        assert_eq!(report.source, ReportSource::User);
+    }
+
+    #[tokio::test]
+    async fn test_submit_user_report_rejections() {
+        let manager = manager_with_names("rinko_test_submit_report_rejected", &["AO-91"]).await;
+
+        let unknown_sat = manager.submit_user_report("XYZZY-9", "heard", "JN58", "BG5FNA").await;
+        assert!(unknown_sat.unwrap_err().to_string().contains("Unknown satellite"));
+
+        let bad_grid = manager.submit_user_report("AO-91", "heard", "ZZ99", "BG5FNA").await;
+        assert!(bad_grid.unwrap_err().to_string().contains("Invalid grid"));
+
+        let bad_status = manager.submit_user_report("AO-91", "loud", "JN58", "BG5FNA").await;
+        assert!(bad_status.unwrap_err().to_string().contains("Unknown status"));
+
+        let info = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        assert_eq!(info.total_reports(), 0);
+    }
+
     #[tokio::test]
     async fn test_merge_reports() {
         let existing = vec![];
@@ -412,10 +1181,95 @@ mod tests {
             callsign: "BG2DNN".to_string(),
             report: "Heard".to_string(),
             grid_square: "OM89".to_string(),
+            ..Default::default()
         }];
 
-        let merged = SatelliteManager::merge_reports(existing, new_reports);
+        let merged = SatelliteManager::merge_reports(existing, new_reports, Duration::minutes(5));
         assert!(!merged.is_empty());
         assert_eq!(merged[0].reports. len(), 1);
     }
+
+    #[test]
+    fn test_merge_reports_rejects_future_timestamp() {
+        let future_time = Utc::now() + Duration::minutes(30);
+        let new_reports = vec![AmsatReport {
+            name: "AO-91".to_string(),
+            reported_time: future_time.to_rfc3339(),
+            callsign: "BG2DNN".to_string(),
+            report: "Heard".to_string(),
+            grid_square: "OM89".to_string(),
+            ..Default::default()
+        }];
+
+        let merged = SatelliteManager::merge_reports(vec![], new_reports, Duration::minutes(5));
+        assert!(merged.is_empty());
+    }
+
+    #[test]
+    fn test_merge_reports_on_hour_boundary() {
+        let report = |time: &str, callsign: &str| AmsatReport {
+            name: "AO-91".to_string(),
+            reported_time: time.to_string(),
+            callsign: callsign.to_string(),
+            report: "Heard".to_string(),
+            grid_square: "OM89".to_string(),
+            ..Default::default()
+        };
+        // Block written with a "Z" suffix, as older caches and user reports have it
+        let existing = vec![SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![report("2026-02-16T08:30:00Z", "BG2DNN")],
+        }];
+        let new_reports = vec![
+            report("2026-02-16T08:00:00Z", "BA1ABC"),
+            report("2026-02-16T08:00:01Z", "BD4XYZ"),
+            report("2026-02-16T07:59:59Z", "BH3EFG"),
+        ];
+
+        let merged = SatelliteManager::merge_reports(existing, new_reports, Duration::minutes(5));
+        let blocks: Vec<(&str, Vec<&str>)> = merged
+            .iter()
+            .map(|block| (block.time.as_str(), block.reports.iter().map(|r| r.callsign.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            blocks,
+            vec![
+                ("2026-02-16T08:00:00+00:00", vec!["BG2DNN", "BA1ABC", "BD4XYZ"]),
+                ("2026-02-16T07:00:00+00:00", vec!["BH3EFG"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_reports_dedupes_across_blocks() {
+        let report = AmsatReport {
+            name: "AO-91".to_string(),
+            reported_time: "2026-02-16T08:59:59Z".to_string(),
+            callsign: "BG2DNN".to_string(),
+            report: "Heard".to_string(),
+            grid_square: "OM89".to_string(),
+            ..Default::default()
+        };
+        let block = |time: &str| SatelliteDataBlock {
+            time: time.to_string(),
+            reports: vec![report.clone()],
+        };
+        let misplaced = block("2026-02-16T09:00:00+00:00");
+        let own_hour = block("2026-02-16T08:00:00+00:00");
+
+        // The 08:59:59 report belongs to the 08:00 block, whichever comes first
+        for existing in [vec![misplaced.clone(), own_hour.clone()], vec![own_hour, misplaced]] {
+            let merged = SatelliteManager::merge_reports(existing, vec![report.clone()], Duration::minutes(5));
+            let total: usize = merged.iter().map(|block| block.reports.len()).sum();
+            assert_eq!(total, 1);
+            assert_eq!(merged.len(), 1);
+            assert_eq!(merged[0].time, "2026-02-16T08:00:00+00:00");
+        }
+
+        // Without a copy in its own hour, the earliest-seen copy is kept
+        let existing = vec![block("2026-02-16T10:00:00+00:00"), block("2026-02-16T09:00:00+00:00")];
+        let merged = SatelliteManager::merge_reports(existing, vec![report.clone()], Duration::minutes(5));
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].time, "2026-02-16T10:00:00+00:00");
+    }
 }
@@ -1,33 +1,38 @@
-///! Satellite status management module
-///! 
-///! This module provides comprehensive satellite status tracking from AMSAT API.
-///! 
-///! ## Features
-///! - Automatic satellite data updates
-///! - Satellite search and querying
-///! - File-based caching
-///! - Hot-reloadable configuration
-///! - SVG/PNG rendering
-///! 
-///! ## Main Components
-///! - `SatelliteManager`: Core manager for satellite data
-///! - `SatelliteUpdater`: Scheduled update task runner
-///! - `SatelliteRenderer`: Image generation from satellite data
+//! Satellite status management module
+//! 
+//! This module provides comprehensive satellite status tracking from AMSAT API.
+//! 
+//! ## Features
+//! - Automatic satellite data updates
+//! - Satellite search and querying
+//! - File-based caching
+//! - Hot-reloadable configuration
+//! - SVG/PNG rendering
+//! 
+//! ## Main Components
+//! - `SatelliteManager`: Core manager for satellite data
+//! - `SatelliteUpdater`: Scheduled update task runner
+//! - `SatelliteRenderer`: Image generation from satellite data
 
 // Core types
 mod types;
 pub use types::{
-    AmsatReport, ReportStatus, SatelliteDataBlock, SatelliteEntry, SatelliteInfo,
-    SatelliteList, UpdateReport,
+    AmsatReport, MISSING_FIELD, ReportSource, ReportStatus, SatelliteDataBlock, SatelliteEntry,
+    SatelliteInfo, SatelliteList, SourceError, UpdateReport, parse_report_time,
 };
 
 // API client and scraper
 mod api_client;
+pub use api_client::{DEFAULT_AMSAT_API_URL, RequestBudget};
 mod scraper;
 
+// Status data sources
+mod source;
+pub use source::{AmsatSource, FixtureSource, StatusSource};
+
 // Cache management
 mod cache;
-pub use cache::cleanup_old_images;
+pub use cache::{cleanup_old_images, ensure_images_dir};
 
 // Search engine
 mod search;
@@ -40,6 +45,28 @@ pub use manager::SatelliteManager;
 mod updater;
 pub use updater::{SatelliteUpdater, start_satellite_updater};
 
+// Grid locator helpers
+mod grid;
+pub use grid::{distance_bearing, maidenhead_to_latlon, normalize_grid};
+
+// Status color palettes
+mod palette;
+pub use palette::ColorPalette;
+
+// SVG templates
+mod template;
+pub use template::{DEFAULT_TEMPLATE_PATH, watch_templates};
+
 // Renderer
 mod renderer;
-pub use renderer::SatelliteRenderer;
+pub use renderer::{LegendPosition, RenderLayout, SatelliteRenderer, SortOrder};
+mod render_cache;
+pub use render_cache::RenderCache;
+mod render_limiter;
+pub use render_limiter::{DEFAULT_MAX_CONCURRENT_RENDERS, RenderLimiter};
+mod report_history;
+pub use report_history::ReportHistory;
+
+// Test fixtures
+#[cfg(test)]
+pub(crate) mod test_support;
@@ -0,0 +1,58 @@
+//! Color palettes for report status markers
+use super::types::ReportStatus;
+use serde::{Deserialize, Serialize};
+
+/// Set of colors used to draw report statuses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorPalette {
+    /// The AMSAT-style colors used since the first renderer
+    #[default]
+    Default,
+    /// Okabe-Ito based colors distinguishable with color-vision deficiency
+    Cvd,
+}
+
+impl ColorPalette {
+    /// Hex color for a status in this palette
+    pub fn color_hex(&self, status: ReportStatus) -> &'static str {
+        match self {
+            ColorPalette::Default => match status {
+                ReportStatus::Blue => "#4297f3ff",
+                ReportStatus::Yellow => "#f3cd36ff",
+                ReportStatus::Orange => "#f97316",
+                ReportStatus::Red => "#ed3f3fff",
+                ReportStatus::Purple => "#946af5ff",
+                ReportStatus::Grey => "#6b7280",
+            },
+            ColorPalette::Cvd => match status {
+                ReportStatus::Blue => "#0072b2",
+                ReportStatus::Yellow => "#f0e442",
+                ReportStatus::Orange => "#e69f00",
+                ReportStatus::Red => "#d55e00",
+                ReportStatus::Purple => "#cc79a7",
+                ReportStatus::Grey => "#999999",
+            },
+        }
+    }
+
+    /// Hex color for a status given as a report string
+    pub fn string_to_color_hex(&self, status: &str) -> &'static str {
+        self.color_hex(ReportStatus::from_string(status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palettes_are_distinct() {
+        for palette in [ColorPalette::Default, ColorPalette::Cvd] {
+            let mut colors: Vec<_> = ReportStatus::ALL.iter().map(|s| palette.color_hex(*s)).collect();
+            colors.sort();
+            colors.dedup();
+            assert_eq!(colors.len(), ReportStatus::ALL.len());
+        }
+    }
+}
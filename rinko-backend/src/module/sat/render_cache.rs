@@ -0,0 +1,147 @@
+//! Single-flight rendering of image files
+//!
+//! Identical concurrent queries map to the same output path. The first
+//! caller renders the file; the others await that render instead of
+//! rasterizing the same image again and racing on the write.
+use anyhow::Result;
+use futures::FutureExt;
+use futures::future::{BoxFuture, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use tokio::sync::oneshot;
+
+/// Outcome of an in-flight render; errors are kept as text so every
+/// waiter can get a copy
+type InFlight = Shared<BoxFuture<'static, std::result::Result<(), String>>>;
+
+static GLOBAL_CACHE: LazyLock<Arc<RenderCache>> = LazyLock::new(|| Arc::new(RenderCache::default()));
+
+/// Output paths currently being rendered
+#[derive(Default)]
+pub struct RenderCache {
+    in_flight: Mutex<HashMap<PathBuf, InFlight>>,
+}
+
+/// Removes the leader's entry when its render ends or is cancelled
+struct InFlightGuard<'a> {
+    cache: &'a RenderCache,
+    path: &'a Path,
+    render: InFlight,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.cache.in_flight.lock().unwrap();
+        if in_flight.get(self.path).is_some_and(|r| r.ptr_eq(&self.render)) {
+            in_flight.remove(self.path);
+        }
+    }
+}
+
+impl RenderCache {
+    /// Process-wide cache shared by all renderers
+    pub fn global() -> Arc<Self> {
+        GLOBAL_CACHE.clone()
+    }
+
+    /// Make sure `path` exists, running `render` only if nobody else is
+    ///
+    /// Returns at once if the file already exists. If the same path is
+    /// being rendered, waits for that render and shares its result.
+    pub async fn render_once<F, Fut>(&self, path: &Path, render: F) -> Result<()>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let claimed = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            if let Some(running) = in_flight.get(path) {
+                Err(running.clone())
+            } else if path.exists() {
+                tracing::debug!("Using cached image: {:?}", path);
+                return Ok(());
+            } else {
+                let (tx, rx) = oneshot::channel();
+                let running: InFlight = async move {
+                    rx.await
+                        .unwrap_or_else(|_| Err("Render was cancelled before it finished".to_string()))
+                }
+                .boxed()
+                .shared();
+                in_flight.insert(path.to_path_buf(), running.clone());
+                Ok((tx, running))
+            }
+        };
+        let (tx, in_flight) = match claimed {
+            Ok(claimed) => claimed,
+            Err(running) => {
+                tracing::debug!("Waiting for in-flight render of {:?}", path);
+                return running.await.map_err(anyhow::Error::msg);
+            }
+        };
+
+        let _guard = InFlightGuard { cache: self, path, render: in_flight };
+        let result = render().await;
+        let _ = tx.send(result.as_ref().map(|_| ()).map_err(|e| format!("{:#}", e)));
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_concurrent_identical_renders_run_once() {
+        let dir = std::env::temp_dir().join("rinko_test_render_cache");
+        let _ = tokio::fs::remove_dir_all(&dir).await;
+        tokio::fs::create_dir_all(&dir).await.unwrap();
+        let path = dir.join("sat_ao91.png");
+
+        let cache = RenderCache::default();
+        let renders = AtomicUsize::new(0);
+        let render = || async {
+            renders.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            tokio::fs::write(&path, b"png").await?;
+            Ok(())
+        };
+
+        let (first, second) = tokio::join!(cache.render_once(&path, render), cache.render_once(&path, render));
+        first.unwrap();
+        second.unwrap();
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+        assert!(cache.in_flight.lock().unwrap().is_empty());
+
+        // Later requests are served from the file
+        cache.render_once(&path, render).await.unwrap();
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_render_is_shared_then_retried() {
+        let path = std::env::temp_dir().join("rinko_test_render_cache_failure.png");
+        let _ = tokio::fs::remove_file(&path).await;
+
+        let cache = RenderCache::default();
+        let renders = AtomicUsize::new(0);
+        let failing = || async {
+            renders.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            anyhow::bail!("font missing")
+        };
+
+        let (first, second) = tokio::join!(cache.render_once(&path, failing), cache.render_once(&path, failing));
+        assert!(first.is_err());
+        assert_eq!(second.unwrap_err().to_string(), "font missing");
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+        // Nothing was cached, so the next request renders again
+        assert!(cache.render_once(&path, failing).await.is_err());
+        assert_eq!(renders.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,125 @@
+//! Concurrency limit for CPU-bound image rendering
+use anyhow::{Context, Result};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Semaphore;
+
+/// Default number of renders allowed to run at the same time
+pub const DEFAULT_MAX_CONCURRENT_RENDERS: usize = 2;
+
+static GLOBAL_LIMITER: OnceLock<RenderLimiter> = OnceLock::new();
+
+/// Bounds how many renders run at once
+///
+/// resvg rasterization is synchronous and CPU-bound, so a burst of renders
+/// can saturate every core and starve the gRPC server. Each render acquires
+/// a permit and then runs on the blocking thread pool.
+#[derive(Debug, Clone)]
+pub struct RenderLimiter {
+    semaphore: Arc<Semaphore>,
+}
+
+impl RenderLimiter {
+    /// Create a limiter allowing `permits` concurrent renders (at least 1)
+    pub fn new(permits: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(permits.max(1))),
+        }
+    }
+
+    /// Configure the process-wide limiter
+    ///
+    /// Must be called before the first render; returns false if the global
+    /// limiter was already initialized.
+    pub fn init_global(permits: usize) -> bool {
+        GLOBAL_LIMITER.set(Self::new(permits)).is_ok()
+    }
+
+    /// Process-wide limiter shared by all renderers
+    pub fn global() -> Self {
+        GLOBAL_LIMITER
+            .get_or_init(|| Self::new(DEFAULT_MAX_CONCURRENT_RENDERS))
+            .clone()
+    }
+
+    /// Run a CPU-heavy closure on the blocking pool once a permit is free
+    ///
+    /// The permit moves into the blocking task, so a render keeps counting
+    /// against the limit even if the caller is cancelled (e.g. timed out).
+    pub async fn run<F, T>(&self, work: F) -> Result<T>
+    where
+        F: FnOnce() -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let permit = self
+            .semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .context("Render limiter closed")?;
+
+        tokio::task::spawn_blocking(move || {
+            let _permit = permit;
+            work()
+        })
+        .await
+        .context("Render task panicked")?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limits_concurrent_renders() {
+        let limiter = RenderLimiter::new(2);
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let mut handles = Vec::new();
+        for _ in 0..8 {
+            let limiter = limiter.clone();
+            let running = running.clone();
+            let max_running = max_running.clone();
+            handles.push(tokio::spawn(async move {
+                limiter
+                    .run(move || {
+                        let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_running.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(Duration::from_millis(20));
+                        running.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    })
+                    .await
+            }));
+        }
+
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert!(max_running.load(Ordering::SeqCst) <= 2);
+        assert!(max_running.load(Ordering::SeqCst) >= 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_cancelled_caller_keeps_permit_until_render_ends() {
+        let limiter = RenderLimiter::new(1);
+        let (release, wait) = std::sync::mpsc::channel::<()>();
+
+        let render = limiter.run(move || {
+            wait.recv().ok();
+            Ok(())
+        });
+        assert!(tokio::time::timeout(Duration::from_millis(50), render).await.is_err());
+
+        // The caller gave up, but the render is still running
+        assert_eq!(limiter.semaphore.available_permits(), 0);
+
+        release.send(()).unwrap();
+        let permit = tokio::time::timeout(Duration::from_secs(5), limiter.semaphore.acquire()).await;
+        assert!(permit.is_ok());
+    }
+}
@@ -1,17 +1,61 @@
-///! Satellite status renderer - Generate images from data
-use super::types::{ReportStatus, SatelliteInfo};
+//! Satellite status renderer - Generate images from data
+use super::grid::{distance_bearing, maidenhead_to_latlon, normalize_grid};
+use super::render_cache::RenderCache;
+use super::search;
+use super::render_limiter::RenderLimiter;
+use super::report_history::{ReportHistory, ReportKey};
+use super::template::load_template;
+use super::types::{AmsatReport, MISSING_FIELD, ReportStatus, SatelliteInfo, parse_report_time};
+use crate::config::RenderConfig;
 use anyhow::{Context, Result};
-use chrono::{DateTime, Timelike, Utc};
+use chrono::{DateTime, FixedOffset, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+
+/// Font database shared by all renders (loaded once from `fonts/`)
+static FONT_DB: OnceLock<Arc<fontdb::Database>> = OnceLock::new();
+
+/// Directory scanned for font faces, relative to the working directory
+const FONTS_DIR: &str = "fonts";
+
+/// Font used when `fonts/` yields no faces, so text never renders blank
+const FALLBACK_FONT: &[u8] = include_bytes!("../../../resources/fonts/DejaVuSansMono.ttf");
+const FALLBACK_FONT_FAMILY: &str = "DejaVu Sans Mono";
+
+/// Get the shared font database, loading it on first use
+fn font_database() -> Arc<fontdb::Database> {
+    FONT_DB
+        .get_or_init(|| Arc::new(load_font_database(Path::new(FONTS_DIR))))
+        .clone()
+}
+
+/// Load every face in `dir`, falling back to the embedded font if there are none
+fn load_font_database(dir: &Path) -> fontdb::Database {
+    let mut fontdb = fontdb::Database::new();
+    fontdb.load_fonts_dir(dir);
+    if fontdb.is_empty() {
+        tracing::warn!(
+            "No font faces found in {}, falling back to embedded {}",
+            dir.display(),
+            FALLBACK_FONT_FAMILY
+        );
+        fontdb.load_font_data(FALLBACK_FONT.to_vec());
+        // Families the template asks for are absent, so route the generic ones
+        // (usvg's last resort) to the embedded face
+        fontdb.set_serif_family(FALLBACK_FONT_FAMILY);
+        fontdb.set_sans_serif_family(FALLBACK_FONT_FAMILY);
+        fontdb.set_monospace_family(FALLBACK_FONT_FAMILY);
+    }
+    tracing::debug!("Loaded {} font faces from {}", fontdb.len(), dir.display());
+    fontdb
+}
 
 /// Map time difference to color gradient (green -> yellow -> red)
 /// Based on hours difference between target time and now
-fn map_time_to_color(target_time: &str, now_utc: &DateTime<Utc>, min_hours: f64, max_hours: f64) -> Result<String> {
-    // Parse target time
-    let target = DateTime::parse_from_rfc3339(target_time)
-        .context("Failed to parse target time")?;
-    let target_utc = target.with_timezone(&Utc);
-
+fn map_time_to_color(target_utc: DateTime<Utc>, now_utc: &DateTime<Utc>, min_hours: f64, max_hours: f64) -> String {
     // Calculate hour difference
     let delta_hours = (now_utc.signed_duration_since(target_utc)).num_seconds().abs() as f64 / 3600.0;
 
@@ -37,7 +81,7 @@ fn map_time_to_color(target_time: &str, now_utc: &DateTime<Utc>, min_hours: f64,
         }
     };
 
-    Ok(format!("#{:02X}{:02X}{:02X}", r, g, b))
+    format!("#{:02X}{:02X}{:02X}", r, g, b)
 }
 
 /// Linear color interpolation
@@ -48,34 +92,364 @@ fn lerp_color(c1: (u8, u8, u8), c2: (u8, u8, u8), t: f64) -> (u8, u8, u8) {
     (r, g, b)
 }
 
-const SVG_TEMPLATE: &str = include_str!("../../../resources/sat_template.svg");
 const BLOCK_TITLE_HEIGHT: f32 = 45.0;
 const HEADER_HEIGHT: f32 = 40.0;
 const ROW_HEIGHT: f32 = 38.0;
 const BLOCK_SPACING: f32 = 30.0;
 const TOP_PADDING: f32 = 20.0;
 const FOOTER_HEIGHT: f32 = 32.0;
+const EMPTY_STATE_HEIGHT: f32 = 80.0;
+const LEGEND_ROW_HEIGHT: f32 = 28.0;
+const LEGEND_COLUMNS: usize = 2;
+const LEGEND_COLUMN_WIDTH: f32 = 400.0;
 const MAX_REPORTS_PER_SATELLITE: usize = 5;
+/// Rows per page when rendering a time window (`--hours`)
+const WINDOW_REPORTS_PER_PAGE: usize = 30;
 
 // Layout positions
+const SVG_WIDTH: f32 = 820.0;
 const X_CALLSIGN: f32 = 20.0;
 const X_GRIDS: f32 = 170.0;
+const X_DISTANCE: f32 = 280.0;
+const DISTANCE_COLUMN_WIDTH: f32 = 150.0;
+const SAT_COLUMN_WIDTH: f32 = 130.0;
 const X_REPORT: f32 = 280.0;
 const X_TIME: f32 = 540.0;
 const COLOR_BLOCK_WIDTH: f32 = 12.0;
 const COLOR_BLOCK_HEIGHT: f32 = 18.0;
+/// Approximate advance of one satellite title character (24px monospace)
+const TITLE_CHAR_WIDTH: f32 = 14.0;
+const NEW_BADGE_WIDTH: f32 = 52.0;
+const NEW_BADGE_HEIGHT: f32 = 22.0;
 const COLOR_BLOCK_TEXT_SPACING: f32 = 8.0;
+const NEW_REPORT_BAR_WIDTH: f32 = 6.0;
+
+/// How satellites are laid out in a rendered image
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderLayout {
+    /// Title, update status and report table per satellite
+    #[default]
+    Full,
+    /// One row per satellite: name, latest status and report time
+    Compact,
+    /// Reports from all satellites in one time-sorted table with a "Sat" column
+    Merged,
+}
+
+/// Where the status color legend is drawn, if at all
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LegendPosition {
+    /// No legend
+    #[default]
+    Off,
+    /// Above the satellite tables
+    Top,
+    /// Below the satellite tables, above the footer
+    Bottom,
+}
+
+/// Collapse runs of reports with the same callsign, grid and status
+///
+/// A report joins the current row when it is within `window` of the row's
+/// first report; the row keeps that first (newest) report and counts the
+/// rest. A zero window disables collapsing.
+fn collapse_reports<'a>(
+    reports: impl Iterator<Item = &'a AmsatReport>,
+    window: chrono::Duration,
+) -> Vec<(&'a AmsatReport, usize)> {
+    let mut rows: Vec<(&AmsatReport, usize)> = Vec::new();
+
+    for report in reports {
+        if let Some((first, count)) = rows.last_mut()
+            && window > chrono::Duration::zero()
+            && same_station_report(first, report)
+            && let (Some(a), Some(b)) = (first.reported_at(), report.reported_at())
+            && (a - b).abs() <= window
+        {
+            *count += 1;
+            continue;
+        }
+        rows.push((report, 1));
+    }
+
+    rows
+}
+
+/// Callsign cut after its prefix: everything up to and including the
+/// first digit, e.g. "BG5FNA" -> "BG5…", "VK2/W1AW" -> "VK2…"
+///
+/// Callsigns without a digit keep their first two characters.
+fn redact_callsign(callsign: &str) -> String {
+    if callsign == MISSING_FIELD {
+        return callsign.to_string();
+    }
+    let keep = match callsign.char_indices().skip(1).find(|(_, c)| c.is_ascii_digit()) {
+        Some((i, digit)) => i + digit.len_utf8(),
+        None => callsign.char_indices().nth(2).map_or(callsign.len(), |(i, _)| i),
+    };
+    if keep >= callsign.len() {
+        return callsign.to_string();
+    }
+    format!("{}…", &callsign[..keep])
+}
+
+/// Grid cut to its 4-character square
+fn redact_grid(grid: &str) -> String {
+    match grid.char_indices().nth(4) {
+        Some((i, _)) if grid != MISSING_FIELD => grid[..i].to_string(),
+        _ => grid.to_string(),
+    }
+}
+
+/// Same callsign (case-insensitive), grid and status
+fn same_station_report(a: &AmsatReport, b: &AmsatReport) -> bool {
+    a.callsign.eq_ignore_ascii_case(&b.callsign)
+        && normalize_grid(&a.grid_square) == normalize_grid(&b.grid_square)
+        && ReportStatus::from_string(&a.report) == ReportStatus::from_string(&b.report)
+}
+
+/// Order of satellites in multi-satellite images
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Search-result order (best match first)
+    #[default]
+    Relevance,
+    /// By name, case-insensitive
+    Alphabetical,
+    /// By NORAD catalog number; satellites without one last
+    Norad,
+    /// Most recent report first; satellites without reports last
+    Recency,
+    /// Most severe latest status first (see `ReportStatus::severity_rank`)
+    Severity,
+}
+
+impl SortOrder {
+    /// Sort satellites in place; ties keep their search-result order
+    pub fn apply(self, satellites: &mut [SatelliteInfo]) {
+        match self {
+            SortOrder::Relevance => {}
+            SortOrder::Alphabetical => satellites.sort_by_key(|sat| sat.name.to_lowercase()),
+            SortOrder::Norad => satellites.sort_by_key(|sat| {
+                let norad = sat
+                    .catalog_number
+                    .as_deref()
+                    .and_then(|n| n.trim().parse::<u64>().ok());
+                (norad.is_none(), norad)
+            }),
+            SortOrder::Recency => satellites.sort_by_key(|sat| {
+                let latest = sat
+                    .latest_report()
+                    .and_then(AmsatReport::reported_at);
+                std::cmp::Reverse(latest)
+            }),
+            SortOrder::Severity => satellites.sort_by_key(|sat| {
+                sat.latest_status()
+                    .unwrap_or(ReportStatus::Grey)
+                    .severity_rank()
+            }),
+        }
+    }
+}
 
 /// Satellite status renderer
 pub struct SatelliteRenderer {
     output_dir: PathBuf,
+    config: RenderConfig,
+    limiter: RenderLimiter,
+    /// Shares renders of the same output file between concurrent requests
+    render_cache: Arc<RenderCache>,
+    /// Observer (latitude, longitude) when the distance column is enabled
+    observer: Option<(f64, f64)>,
+    /// Offset for the local time shown next to UTC report times
+    local_offset: Option<FixedOffset>,
+    /// Matches dropped by the per-query cap, noted above the footer
+    omitted: usize,
+    /// Time window (hours) and 1-based page requested with `--hours`
+    report_window: Option<(u64, usize)>,
+    /// Shared history and the conversation whose previous render is compared
+    report_history: Option<(Arc<ReportHistory>, String)>,
 }
 
 impl SatelliteRenderer {
     /// Create a new renderer
     pub fn new(output_dir: impl AsRef<Path>) -> Self {
+        Self::with_config(output_dir, RenderConfig::default())
+    }
+
+    /// Create a new renderer with custom render settings
+    pub fn with_config(output_dir: impl AsRef<Path>, config: RenderConfig) -> Self {
+        let observer = if config.show_distance {
+            let grid = config.observer_grid.as_deref().unwrap_or_default();
+            let observer = maidenhead_to_latlon(grid);
+            if observer.is_none() {
+                tracing::warn!("Distance column disabled: invalid observer grid '{}'", grid);
+            }
+            observer
+        } else {
+            None
+        };
+
+        let local_offset = config.local_time_offset.as_deref().and_then(|offset| {
+            let parsed = offset.parse::<FixedOffset>().ok();
+            if parsed.is_none() {
+                tracing::warn!("Local report times disabled: invalid UTC offset '{}'", offset);
+            }
+            parsed
+        });
+
         Self {
             output_dir: output_dir.as_ref().to_path_buf(),
+            config,
+            limiter: RenderLimiter::global(),
+            render_cache: RenderCache::global(),
+            observer,
+            local_offset,
+            omitted: 0,
+            report_window: None,
+            report_history: None,
+        }
+    }
+
+    /// Note that `omitted` further matches were left out of the image
+    pub fn with_omitted(mut self, omitted: usize) -> Self {
+        self.omitted = omitted;
+        self
+    }
+
+    /// Show every report from the last `hours` hours instead of the latest
+    /// few, `WINDOW_REPORTS_PER_PAGE` rows per satellite on page `page`
+    pub fn with_report_window(mut self, hours: u64, page: usize) -> Self {
+        self.report_window = Some((hours, page.max(1)));
+        self
+    }
+
+    /// Mark report rows that were not in `conversation`'s previous render of
+    /// the same query
+    pub fn with_report_history(mut self, history: Arc<ReportHistory>, conversation: &str) -> Self {
+        self.report_history = Some((history, conversation.to_string()));
+        self
+    }
+
+    /// Whether query images leave out everything that depends on render
+    /// time, so their content (and content-hash name) only changes with the data
+    fn timeless(&self) -> bool {
+        self.config.content_hash_filenames
+    }
+
+    /// (min, max) report age in hours for the time color gradient
+    ///
+    /// Falls back to the default 0-12h window if the configured one is empty.
+    fn time_color_window(&self) -> (f64, f64) {
+        let (min, max) = (self.config.time_color_min_hours, self.config.time_color_max_hours);
+        if min >= 0.0 && max > min {
+            (min, max)
+        } else {
+            tracing::warn!("Invalid time color window {}-{}h, using 0-12h", min, max);
+            (0.0, 12.0)
+        }
+    }
+
+    /// Report time as shown in the Time column
+    ///
+    /// "09:30Z / 17:30 BJT" when a local offset is configured, otherwise
+    /// the raw timestamp. Unreadable times show as `MISSING_FIELD`.
+    fn time_text(&self, reported_time: &str) -> String {
+        match (self.local_offset, parse_report_time(reported_time)) {
+            (_, None) => MISSING_FIELD.to_string(),
+            (Some(offset), Some(time)) => format!(
+                "{}Z / {} {}",
+                time.format("%H:%M"),
+                time.with_timezone(&offset).format("%H:%M"),
+                Self::escape_xml(&self.config.local_time_label),
+            ),
+            (None, Some(_)) => Self::escape_xml(reported_time),
+        }
+    }
+
+    /// Time cell text with the report's age, e.g. "2026-02-16T08:30:00Z (3h ago)"
+    ///
+    /// Without the age in [`Self::timeless`] images.
+    fn time_with_age(&self, report: &AmsatReport, now_utc: &DateTime<Utc>) -> String {
+        match report.reported_at() {
+            Some(_) if self.timeless() => self.time_text(&report.reported_time),
+            Some(time) => format!(
+                "{} ({}h ago)",
+                self.time_text(&report.reported_time),
+                now_utc.signed_duration_since(time).num_hours()
+            ),
+            None => MISSING_FIELD.to_string(),
+        }
+    }
+
+    /// Horizontal shift applied to the Report/Time columns
+    fn column_offset(&self) -> f32 {
+        if self.observer.is_some() {
+            DISTANCE_COLUMN_WIDTH
+        } else {
+            0.0
+        }
+    }
+
+    /// Total image width (widened when the distance column is shown)
+    fn svg_width(&self) -> f32 {
+        SVG_WIDTH + self.column_offset()
+    }
+
+    /// Configured "...and N more" note for the omitted matches
+    pub fn more_results_text(&self) -> String {
+        self.config
+            .more_results_message
+            .replace("{count}", &self.omitted.to_string())
+    }
+
+    /// Callsign as drawn, honoring `redact_callsigns`; `MISSING_FIELD` if blank
+    fn shown_callsign(&self, callsign: &str) -> String {
+        let callsign = callsign.trim();
+        if callsign.is_empty() {
+            MISSING_FIELD.to_string()
+        } else if self.config.redact_callsigns {
+            redact_callsign(callsign)
+        } else {
+            callsign.to_string()
+        }
+    }
+
+    /// Grid as drawn and used for distances, honoring `redact_grids`;
+    /// `MISSING_FIELD` unless it is a valid locator
+    fn shown_grid(&self, grid: &str) -> String {
+        let grid = normalize_grid(grid);
+        if maidenhead_to_latlon(&grid).is_none() {
+            MISSING_FIELD.to_string()
+        } else if self.config.redact_grids {
+            redact_grid(&grid)
+        } else {
+            grid
+        }
+    }
+
+    /// Filename part marking redacted images, so they never share a cache
+    /// entry with unredacted ones
+    fn redaction_suffix(&self) -> &'static str {
+        match (self.config.redact_callsigns, self.config.redact_grids) {
+            (false, false) => "",
+            (true, false) => "_rc",
+            (false, true) => "_rg",
+            (true, true) => "_rcg",
+        }
+    }
+
+    /// "Dist/Brg" cell text for a report grid, or "-" when it cannot be parsed
+    fn distance_text(observer: (f64, f64), grid: &str) -> String {
+        match maidenhead_to_latlon(grid) {
+            Some(target) => {
+                let (distance, bearing) = distance_bearing(observer, target);
+                format!("{:.0}km/{:.0}°", distance, bearing)
+            }
+            None => "-".to_string(),
         }
     }
 
@@ -83,38 +457,150 @@ impl SatelliteRenderer {
     /// 
     /// # Arguments
     /// * `satellites` - List of satellites to render
+    /// * `layout` - Full tables or one compact row per satellite
     /// 
     /// # Returns
     /// Path to the generated image file
-    pub async fn render_satellites(&self, satellites: &[SatelliteInfo]) -> Result<PathBuf> {
-        // Ensure output directory exists
-        tokio::fs::create_dir_all(&self.output_dir)
-            .await
-            .context("Failed to create output directory")?;
+    pub async fn render_satellites(
+        &self,
+        satellites: &[SatelliteInfo],
+        layout: RenderLayout,
+    ) -> Result<PathBuf> {
+        // Highlights depend on this conversation's previous render, so they
+        // are settled before the cache lookup and named in the file
+        let previous = self.previous_reports(satellites, layout);
+        let (filename, svg) = if self.config.content_hash_filenames {
+            // Timeless SVGs name themselves; highlights are part of the content
+            let svg = self.generate_svg_since(satellites, &[], layout, previous.as_ref())?;
+            (format!("sat_{}_{}.png", self.filename_stem(satellites), Self::content_hash(&svg)), Some(svg))
+        } else {
+            let filename = format!(
+                "{}{}.png",
+                self.generate_filename(satellites, layout).trim_end_matches(".png"),
+                Self::highlight_suffix(satellites, previous.as_ref())
+            );
+            (filename, None)
+        };
+        let output_path = self.output_dir.join(&filename);
+
+        // Reuse the file if it exists or is being rendered right now
+        self.render_cache
+            .render_once(&output_path, || async {
+                let svg_content = match svg {
+                    Some(svg) => svg,
+                    None => self.generate_svg_since(satellites, &[], layout, previous.as_ref())?,
+                };
+                self.render_svg_to_png(&svg_content, &output_path).await?;
+                tracing::info!("Generated satellite status image: {:?}", output_path);
+                Ok(())
+            })
+            .await?;
+
+        Ok(output_path)
+    }
+
+    /// Render satellite status to PNG bytes without touching the disk
+    ///
+    /// For one-shot responses; the image is neither cached nor written to
+    /// `output_dir`. Like [`Self::render_satellites`] it counts as a render
+    /// for new-report highlighting.
+    pub async fn render_satellites_bytes(
+        &self,
+        satellites: &[SatelliteInfo],
+        layout: RenderLayout,
+    ) -> Result<Vec<u8>> {
+        let svg_content = self.generate_query_svg(satellites, layout)?;
+        self.render_svg_bytes(svg_content).await
+    }
 
-        // Generate filename based on satellites and current time
-        let filename = self.generate_filename(satellites);
+    /// Render two satellites side by side
+    ///
+    /// Each side is the query as typed and the satellite it matched, if
+    /// any. A side without a match or without reports says so in its column.
+    ///
+    /// # Returns
+    /// Path to the generated image file
+    pub async fn render_comparison(&self, sides: [(&str, Option<&SatelliteInfo>); 2]) -> Result<PathBuf> {
+        let filename = self.generate_comparison_filename(&sides, Utc::now());
         let output_path = self.output_dir.join(&filename);
 
-        // Check if image already exists (cache hit)
-        if output_path.exists() {
-            tracing::debug!("Using cached image: {:?}", output_path);
-            return Ok(output_path);
-        }
+        self.render_cache
+            .render_once(&output_path, || async {
+                let svg_content = self.generate_comparison_svg(&sides, &Utc::now())?;
+                self.render_svg_to_png(&svg_content, &output_path).await?;
+                tracing::info!("Generated comparison image: {:?}", output_path);
+                Ok(())
+            })
+            .await?;
+
+        Ok(output_path)
+    }
 
-        // Generate the image
-        self.generate_image(satellites, &output_path).await?;
+    /// Render the empty state for a query that matched no satellites
+    ///
+    /// # Arguments
+    /// * `suggestions` - Similar satellite names to offer (may be empty)
+    ///
+    /// # Returns
+    /// Path to the generated image file
+    pub async fn render_no_results(&self, suggestions: &[String]) -> Result<PathBuf> {
+        let filename = self.generate_no_results_filename(suggestions);
+        let output_path = self.output_dir.join(&filename);
 
-        tracing::info!("Generated satellite status image: {:?}", output_path);
+        self.render_cache
+            .render_once(&output_path, || async {
+                self.generate_image(&[], suggestions, RenderLayout::Full, &output_path).await?;
+                tracing::info!("Generated empty-state image: {:?}", output_path);
+                Ok(())
+            })
+            .await?;
 
         Ok(output_path)
     }
 
+    /// Render the empty state to PNG bytes without touching the disk
+    pub async fn render_no_results_bytes(&self, suggestions: &[String]) -> Result<Vec<u8>> {
+        let svg_content = self.generate_svg(&[], suggestions, RenderLayout::Full)?;
+        self.render_svg_bytes(svg_content).await
+    }
+
     /// Generate filename for the rendered image
-    fn generate_filename(&self, satellites: &[SatelliteInfo]) -> String {
-        let now = chrono::Utc::now();
+    ///
+    /// Query images are named `sat_{time}_{names}_{id}[_{layout}].png`. Empty
+    /// states are `sat_{time}_noresults[_{names}_{id}].png`. `{id}` hashes the
+    /// exact satellite names, since `{names}` is shortened and normalized.
+    /// [`Self::render_satellites`] appends `_new{id}` when rows are highlighted,
+    /// or names the image `sat_{names}_{hash}.png` after its SVG with
+    /// `content_hash_filenames`.
+    fn generate_filename(&self, satellites: &[SatelliteInfo], layout: RenderLayout) -> String {
+        self.generate_filename_at(satellites, layout, Utc::now())
+    }
+
+    /// [`Self::generate_filename`] for a render at `now`
+    fn generate_filename_at(
+        &self,
+        satellites: &[SatelliteInfo],
+        layout: RenderLayout,
+        now: DateTime<Utc>,
+    ) -> String {
+        let sat_part = format!(
+            "{}_{}{}",
+            self.filename_stem(satellites),
+            Self::names_id(satellites.iter().map(|s| s.name.as_str())),
+            self.redaction_suffix()
+        );
         
-        // Floor to 15-minute blocks for caching
+        let time_str = Self::time_bucket(now);
+
+        match layout {
+            RenderLayout::Full => format!("sat_{}_{}.png", time_str, sat_part),
+            RenderLayout::Compact => format!("sat_{}_{}_compact.png", time_str, sat_part),
+            RenderLayout::Merged => format!("sat_{}_{}_merged.png", time_str, sat_part),
+        }
+    }
+
+    /// `now` floored to a 15-minute block, so repeated queries reuse the image
+    fn time_bucket(now: DateTime<Utc>) -> String {
         let minute = (now.minute() / 15) * 15;
         let floored = now
             .with_minute(minute)
@@ -124,8 +610,11 @@ impl SatelliteRenderer {
             .with_nanosecond(0)
             .unwrap();
 
-        let time_str = floored.format("%Y%m%d_%H%M").to_string();
+        floored.format("%Y%m%d_%H%M").to_string()
+    }
 
+    /// Filename part naming the query: satellites, omitted count and window
+    fn filename_stem(&self, satellites: &[SatelliteInfo]) -> String {
         // Generate satellite names part
         let sat_names: Vec<String> = satellites
             .iter()
@@ -140,8 +629,73 @@ impl SatelliteRenderer {
         } else {
             sat_names.join("_")
         };
+        let sat_part = if self.omitted > 0 {
+            format!("{}_plus{}", sat_part, self.omitted)
+        } else {
+            sat_part
+        };
+        match self.report_window {
+            Some((hours, page)) => format!("{}_{}h_p{}", sat_part, hours, page),
+            None => sat_part,
+        }
+    }
+
+    /// Short hash of a timeless SVG, which determines the image content
+    fn content_hash(svg: &str) -> String {
+        hex::encode(&Sha256::digest(svg.as_bytes())[..8])
+    }
+
+    /// Short hash of the exact names, in order
+    fn names_id<'a>(names: impl Iterator<Item = &'a str>) -> String {
+        let mut hasher = Sha256::new();
+        for name in names {
+            hasher.update(name.as_bytes());
+            hasher.update([0]);
+        }
+        hex::encode(&hasher.finalize()[..4])
+    }
+
+    /// Filename for a comparison image; unmatched sides are keyed on their query
+    fn generate_comparison_filename(&self, sides: &[(&str, Option<&SatelliteInfo>); 2], now: DateTime<Utc>) -> String {
+        let labels: Vec<&str> = sides
+            .iter()
+            .map(|(query, sat)| sat.map_or(*query, |sat| sat.name.as_str()))
+            .collect();
+        format!(
+            "sat_{}_{}_vs_{}_{}{}_compare.png",
+            Self::time_bucket(now),
+            Self::normalize_sat_name(labels[0]),
+            Self::normalize_sat_name(labels[1]),
+            Self::names_id(labels.iter().copied()),
+            self.redaction_suffix()
+        )
+    }
+
+    /// Generate filename for an empty-state image (keyed on its suggestions)
+    fn generate_no_results_filename(&self, suggestions: &[String]) -> String {
+        self.generate_no_results_filename_at(suggestions, Utc::now())
+    }
+
+    /// [`Self::generate_no_results_filename`] for a render at `now`
+    fn generate_no_results_filename_at(&self, suggestions: &[String], now: DateTime<Utc>) -> String {
+        let time_str = now.format("%Y%m%d_%H%M").to_string();
+
+        let suggestion_part: Vec<String> = suggestions
+            .iter()
+            .take(3)
+            .map(|s| Self::normalize_sat_name(s))
+            .collect();
 
-        format!("sat_{}_{}.png", time_str, sat_part)
+        if suggestion_part.is_empty() {
+            format!("sat_{}_noresults.png", time_str)
+        } else {
+            format!(
+                "sat_{}_noresults_{}_{}.png",
+                time_str,
+                suggestion_part.join("_"),
+                Self::names_id(suggestions.iter().map(String::as_str))
+            )
+        }
     }
 
     /// Normalize satellite name for filename (remove special characters)
@@ -155,9 +709,15 @@ impl SatelliteRenderer {
     /// Generate the actual image
     /// 
     /// Uses SVG template and renders to PNG
-    async fn generate_image(&self, satellites: &[SatelliteInfo], output_path: &Path) -> Result<()> {
+    async fn generate_image(
+        &self,
+        satellites: &[SatelliteInfo],
+        suggestions: &[String],
+        layout: RenderLayout,
+        output_path: &Path,
+    ) -> Result<()> {
         // Generate SVG content
-        let svg_content = self.generate_svg(satellites)?;
+        let svg_content = self.generate_svg(satellites, suggestions, layout)?;
 
         // Render to PNG
         self.render_svg_to_png(&svg_content, output_path).await?;
@@ -165,32 +725,163 @@ impl SatelliteRenderer {
         Ok(())
     }
 
+    /// SVG for a query, marking rows that are new since its previous render
+    fn generate_query_svg(&self, satellites: &[SatelliteInfo], layout: RenderLayout) -> Result<String> {
+        let previous = self.previous_reports(satellites, layout);
+        self.generate_svg_since(satellites, &[], layout, previous.as_ref())
+    }
+
+    /// Reports of the conversation's previous render of this query, if
+    /// highlighting is on; records this render as the new previous one
+    fn previous_reports(&self, satellites: &[SatelliteInfo], layout: RenderLayout) -> Option<HashSet<ReportKey>> {
+        self.report_history.as_ref().and_then(|(history, conversation)| {
+            let key = format!("{}_{:?}", self.filename_stem(satellites), layout);
+            history.replace(conversation, &key, satellites)
+        })
+    }
+
+    /// Filename part identifying the highlighted rows, empty when none are
+    ///
+    /// Two conversations share an image only if the same rows are new to both.
+    fn highlight_suffix(satellites: &[SatelliteInfo], previous: Option<&HashSet<ReportKey>>) -> String {
+        let mut new_reports: Vec<ReportKey> = satellites
+            .iter()
+            .flat_map(|sat| sat.data_blocks.iter().flat_map(|b| b.reports.iter()))
+            .filter(|report| Self::is_new_report(previous, report))
+            .map(ReportKey::of)
+            .collect();
+        if new_reports.is_empty() {
+            return String::new();
+        }
+        new_reports.sort();
+        new_reports.dedup();
+        let key = serde_json::to_vec(&new_reports).unwrap_or_default();
+        format!("_new{}", hex::encode(&Sha256::digest(&key)[..4]))
+    }
+
     /// Generate SVG content from template
-    fn generate_svg(&self, satellites: &[SatelliteInfo]) -> Result<String> {
+    ///
+    /// `suggestions` are only shown in the empty state (no satellites).
+    fn generate_svg(
+        &self,
+        satellites: &[SatelliteInfo],
+        suggestions: &[String],
+        layout: RenderLayout,
+    ) -> Result<String> {
+        self.generate_svg_since(satellites, suggestions, layout, None)
+    }
+
+    /// Generate SVG content, marking report rows missing from `previous`
+    ///
+    /// Nothing is marked without a previous render to compare against.
+    fn generate_svg_since(
+        &self,
+        satellites: &[SatelliteInfo],
+        suggestions: &[String],
+        layout: RenderLayout,
+        previous: Option<&HashSet<ReportKey>>,
+    ) -> Result<String> {
+        self.generate_svg_at(satellites, suggestions, layout, previous, Utc::now())
+    }
+
+    /// [`Self::generate_svg_since`] for a render at `now_utc`
+    fn generate_svg_at(
+        &self,
+        satellites: &[SatelliteInfo],
+        suggestions: &[String],
+        layout: RenderLayout,
+        previous: Option<&HashSet<ReportKey>>,
+        now_utc: DateTime<Utc>,
+    ) -> Result<String> {
         let mut current_y = TOP_PADDING;
         let mut content = String::new();
-        let now_utc = Utc::now();
+        // Bottom edge of each section, for the debug overlay
+        let mut boundaries = vec![current_y];
+
+        let show_legend = !satellites.is_empty();
+        if show_legend && self.config.legend == LegendPosition::Top {
+            content.push_str(&self.generate_legend(&mut current_y));
+            boundaries.push(current_y);
+        }
 
         if satellites.is_empty() {
-            content.push_str(&format!(
-                r#"<text x="410" y="100" text-anchor="middle" class="table-text">No satellite data available.</text>"#
+            content.push_str(&self.generate_empty_state(
+                &self.config.empty_message,
+                suggestions,
+                &mut current_y,
             ));
-            current_y = 120.0;
         } else {
-            for sat in satellites {
-                content.push_str(&self.generate_satellite_block(sat, &mut current_y, &now_utc)?);
+            match layout {
+                RenderLayout::Full => {
+                    // Satellites with too few reports share one compact table at the end
+                    let (sparse, busy): (Vec<&SatelliteInfo>, Vec<&SatelliteInfo>) =
+                        satellites.iter().partition(|sat| self.is_sparse(sat, &now_utc));
+                    for sat in busy {
+                        content.push_str(&self.generate_satellite_block(sat, previous, &mut current_y, &now_utc)?);
+                        boundaries.push(current_y);
+                    }
+                    if !sparse.is_empty() {
+                        content.push_str(&self.generate_compact_table(sparse, &mut current_y, &now_utc));
+                        boundaries.push(current_y);
+                    }
+                }
+                RenderLayout::Compact => {
+                    content.push_str(&self.generate_compact_table(satellites, &mut current_y, &now_utc));
+                }
+                RenderLayout::Merged => {
+                    content.push_str(&self.generate_merged_table(satellites, previous, &mut current_y, &now_utc));
+                }
             }
         }
 
-        // Generate footer
-        let footer = self.generate_footer(current_y);
-        let total_height = current_y + FOOTER_HEIGHT;
+        boundaries.push(current_y);
+
+        if show_legend && self.config.legend == LegendPosition::Bottom {
+            content.push_str(&self.generate_legend(&mut current_y));
+            boundaries.push(current_y);
+        }
+
+        if self.omitted > 0 && !satellites.is_empty() {
+            content.push_str(&format!(
+                r#"<text x="{}" y="{}" text-anchor="middle" class="table-text more-results">{}</text>"#,
+                self.svg_width() / 2.0,
+                current_y + ROW_HEIGHT / 2.0,
+                Self::escape_xml(&self.more_results_text()),
+            ));
+            content.push('\n');
+            current_y += ROW_HEIGHT;
+        }
+
+        // Generate footer (omitted entirely when disabled)
+        let (footer, total_height) = if self.config.footer_enabled {
+            (self.generate_footer(current_y), current_y + FOOTER_HEIGHT)
+        } else {
+            (String::new(), current_y)
+        };
+
+        // The merged table's "Sat" column widens the image
+        let width = if layout == RenderLayout::Merged && !satellites.is_empty() {
+            self.svg_width() + SAT_COLUMN_WIDTH
+        } else {
+            self.svg_width()
+        };
+
+        if self.config.debug_overlay {
+            let merged = layout == RenderLayout::Merged && !satellites.is_empty();
+            let overlay = self.generate_debug_overlay(&content, &boundaries, merged, width, total_height);
+            content.push_str(&overlay);
+        }
+
+        self.fill_template(width, total_height, &content, &footer)
+    }
 
-        // Replace placeholders in template
-        let svg = SVG_TEMPLATE
-            .replace("{{SVG_HEIGHT}}", &total_height.to_string())
-            .replace("{{CONTENT}}", &content)
-            .replace("{{FOOTER}}", &footer);
+    /// Replace the placeholders in the configured template
+    fn fill_template(&self, width: f32, height: f32, content: &str, footer: &str) -> Result<String> {
+        let svg = load_template(Path::new(&self.config.template_path))?
+            .replace("{{SVG_WIDTH}}", &width.to_string())
+            .replace("{{SVG_HEIGHT}}", &height.to_string())
+            .replace("{{CONTENT}}", content)
+            .replace("{{FOOTER}}", footer);
 
         Ok(svg)
     }
@@ -199,6 +890,7 @@ impl SatelliteRenderer {
     fn generate_satellite_block(
         &self,
         sat: &SatelliteInfo,
+        previous: Option<&HashSet<ReportKey>>,
         current_y: &mut f32,
         now_utc: &DateTime<Utc>,
     ) -> Result<String> {
@@ -211,6 +903,19 @@ impl SatelliteRenderer {
             *current_y + BLOCK_TITLE_HEIGHT / 2.0,
             Self::escape_xml(&sat.name)
         ));
+        if self.is_new(sat, now_utc) {
+            let badge_x = X_CALLSIGN + sat.name.chars().count() as f32 * TITLE_CHAR_WIDTH + 12.0;
+            let badge_y = *current_y + (BLOCK_TITLE_HEIGHT - NEW_BADGE_HEIGHT) / 2.0;
+            block.push_str(&format!(
+                r##"<g class="new-badge"><rect x="{}" y="{}" width="{}" height="{}" rx="4" fill="#1a7f37" /><text x="{}" y="{}" text-anchor="middle" dominant-baseline="central" font-size="14" font-weight="700" fill="#ffffff">NEW</text></g>"##,
+                badge_x,
+                badge_y,
+                NEW_BADGE_WIDTH,
+                NEW_BADGE_HEIGHT,
+                badge_x + NEW_BADGE_WIDTH / 2.0,
+                badge_y + NEW_BADGE_HEIGHT / 2.0,
+            ));
+        }
         block.push('\n');
         *current_y += BLOCK_TITLE_HEIGHT;
 
@@ -222,7 +927,7 @@ impl SatelliteRenderer {
         };
 
         let logo_size = ROW_HEIGHT * 0.6;
-        let logo_x = X_TIME;
+        let logo_x = X_TIME + self.column_offset();
         let logo_y = *current_y + (ROW_HEIGHT - logo_size) / 2.0;
 
         block.push_str(&format!(
@@ -240,10 +945,14 @@ impl SatelliteRenderer {
 
         // Last update time
         let last_update_str = sat.last_updated.format("%Y-%m-%d %H:%M:%S UTC").to_string();
-        let hours_ago = (now_utc.signed_duration_since(sat.last_updated)).num_hours();
+        let hours_ago = if self.timeless() {
+            String::new()
+        } else {
+            format!(" ({}h ago)", now_utc.signed_duration_since(sat.last_updated).num_hours())
+        };
         
         block.push_str(&format!(
-            r#"<text x="{}" y="{}" class="table-text">Last update: {} ({}h ago)</text>"#,
+            r#"<text x="{}" y="{}" class="table-text">Last update: {}{}</text>"#,
             X_CALLSIGN,
             *current_y + ROW_HEIGHT / 2.0,
             last_update_str,
@@ -254,124 +963,669 @@ impl SatelliteRenderer {
 
         // Check if we have data
         if sat.data_blocks.is_empty() {
-            block.push_str(&format!(
-                r#"<text x="410" y="{}" text-anchor="middle" class="table-text">No reports available.</text>"#,
-                *current_y + 40.0
+            block.push_str(&self.generate_empty_state(
+                &self.config.no_reports_message,
+                &[],
+                current_y,
             ));
-            block.push('\n');
-            *current_y += 80.0;
             *current_y += BLOCK_SPACING;
             return Ok(block);
         }
 
-        // Table header
-        block.push_str(&format!(
+        block.push_str(&self.generate_table_header(false, current_y));
+
+        let rows = self.report_rows(sat, now_utc);
+        match self.report_window {
+            // One page of the requested window
+            Some((_, page)) => {
+                let pages = rows.len().div_ceil(WINDOW_REPORTS_PER_PAGE).max(1);
+                let page_rows = self.shown_rows(rows, (page - 1) * WINDOW_REPORTS_PER_PAGE, WINDOW_REPORTS_PER_PAGE);
+                for (report, count) in page_rows {
+                    let is_new = Self::is_new_report(previous, report);
+                    block.push_str(&self.generate_report_row(report, count, None, is_new, current_y, now_utc));
+                }
+                block.push_str(&self.generate_page_note(page, pages, current_y));
+            }
+            // Latest reports only (limit to MAX_REPORTS_PER_SATELLITE)
+            None => {
+                for (report, count) in self.shown_rows(rows, 0, MAX_REPORTS_PER_SATELLITE) {
+                    let is_new = Self::is_new_report(previous, report);
+                    block.push_str(&self.generate_report_row(report, count, None, is_new, current_y, now_utc));
+                }
+            }
+        }
+
+        *current_y += BLOCK_SPACING;
+        Ok(block)
+    }
+
+    /// A satellite's report rows as (report, count), newest first
+    ///
+    /// Limited to the `--hours` window if one was requested; applies
+    /// `collapse_window_minutes`. See [`Self::shown_rows`] for `sort_by_grid`.
+    fn report_rows<'a>(
+        &self,
+        sat: &'a SatelliteInfo,
+        now_utc: &DateTime<Utc>,
+    ) -> Vec<(&'a AmsatReport, usize)> {
+        let reports: Vec<&AmsatReport> = match self.report_window {
+            Some((hours, _)) => sat.get_recent_reports(hours, *now_utc),
+            None => sat.data_blocks.iter().flat_map(|b| b.reports.iter()).collect(),
+        };
+        let window = chrono::Duration::minutes(self.config.collapse_window_minutes as i64);
+        collapse_reports(reports.into_iter(), window)
+    }
+
+    /// The `limit` newest of `rows` after skipping `skip`, in `sort_by_grid`
+    /// order if set
+    ///
+    /// The cap applies in time order first, so grid sorting never swaps
+    /// newer reports for alphabetically earlier old ones.
+    fn shown_rows<'a>(
+        &self,
+        rows: Vec<(&'a AmsatReport, usize)>,
+        skip: usize,
+        limit: usize,
+    ) -> Vec<(&'a AmsatReport, usize)> {
+        let mut rows: Vec<_> = rows.into_iter().skip(skip).take(limit).collect();
+        if self.config.sort_by_grid {
+            rows.sort_by_key(|(report, _)| normalize_grid(&report.grid_square));
+        }
+        rows
+    }
+
+    /// Whether a satellite has fewer report rows than `min_reports_per_block`
+    /// and so gets a compact line instead of a full block
+    fn is_sparse(&self, sat: &SatelliteInfo, now_utc: &DateTime<Utc>) -> bool {
+        let min = self.config.min_reports_per_block;
+        min > 0 && self.report_rows(sat, now_utc).len() < min
+    }
+
+    /// Whether a satellite was added within the last `new_badge_days`
+    /// (never in [`Self::timeless`] images)
+    fn is_new(&self, sat: &SatelliteInfo, now_utc: &DateTime<Utc>) -> bool {
+        let window = chrono::Duration::days(self.config.new_badge_days as i64);
+        self.config.new_badge_days > 0 && !self.timeless() && *now_utc - sat.first_seen <= window
+    }
+
+    /// Whether a report was missing from the previous render
+    fn is_new_report(previous: Option<&HashSet<ReportKey>>, report: &AmsatReport) -> bool {
+        previous.is_some_and(|previous| !previous.contains(&ReportKey::of(report)))
+    }
+
+    /// "Page x/y" line under a windowed table, pointing at the next page
+    fn generate_page_note(&self, page: usize, pages: usize, current_y: &mut f32) -> String {
+        if pages <= 1 {
+            return String::new();
+        }
+        let text = if page < pages {
+            format!("Page {}/{}, add --page {} for older reports", page, pages, page + 1)
+        } else {
+            format!("Page {}/{}", page, pages)
+        };
+        let note = format!(
+            "<text x=\"{}\" y=\"{}\" class=\"table-text page-note\">{}</text>\n",
+            X_CALLSIGN,
+            *current_y + ROW_HEIGHT / 2.0,
+            text
+        );
+        *current_y += ROW_HEIGHT;
+        note
+    }
+
+    /// Generate the report table header
+    ///
+    /// `sat_column` adds a leading "Sat" column for the merged layout.
+    fn generate_table_header(&self, sat_column: bool, current_y: &mut f32) -> String {
+        let shift = if sat_column { SAT_COLUMN_WIDTH } else { 0.0 };
+        let text_y = *current_y + HEADER_HEIGHT / 2.0;
+
+        let sat_header = if sat_column {
+            format!(
+                "\n<text x=\"{}\" y=\"{}\" class=\"table-text header-text\">Sat</text>",
+                X_CALLSIGN, text_y,
+            )
+        } else {
+            String::new()
+        };
+        let distance_header = if self.observer.is_some() {
+            format!(
+                "\n<text x=\"{}\" y=\"{}\" class=\"table-text header-text\">Dist/Brg</text>",
+                X_DISTANCE + shift,
+                text_y,
+            )
+        } else {
+            String::new()
+        };
+        let header = format!(
             r##"<g class="header">
-<rect x="0" y="{}" width="100%" height="{}" fill="#f0f2f5" />
+<rect x="0" y="{}" width="100%" height="{}" fill="#f0f2f5" />{}
 <text x="{}" y="{}" class="table-text header-text">Callsign</text>
-<text x="{}" y="{}" class="table-text header-text">Grids</text>
+<text x="{}" y="{}" class="table-text header-text">Grids</text>{}
 <text x="{}" y="{}" class="table-text header-text">Report</text>
 <text x="{}" y="{}" class="table-text header-text">Time</text>
 </g>
 "##,
             *current_y,
             HEADER_HEIGHT,
-            X_CALLSIGN,
-            *current_y + HEADER_HEIGHT / 2.0,
-            X_GRIDS,
-            *current_y + HEADER_HEIGHT / 2.0,
-            X_REPORT,
-            *current_y + HEADER_HEIGHT / 2.0,
-            X_TIME,
-            *current_y + HEADER_HEIGHT / 2.0,
-        ));
+            sat_header,
+            X_CALLSIGN + shift,
+            text_y,
+            X_GRIDS + shift,
+            text_y,
+            distance_header,
+            X_REPORT + self.column_offset() + shift,
+            text_y,
+            X_TIME + self.column_offset() + shift,
+            text_y,
+        );
         *current_y += HEADER_HEIGHT;
+        header
+    }
 
-        // Data rows (limit to MAX_REPORTS_PER_SATELLITE)
-        let mut row_count = 0;
-        'outer: for data_block in &sat.data_blocks {
-            for report in &data_block.reports {
-                if row_count >= MAX_REPORTS_PER_SATELLITE {
-                    break 'outer;
-                }
+    /// Generate one report row, with a leading satellite cell when `sat_name`
+    /// is set and a left accent bar when `is_new`
+    fn generate_report_row(
+        &self,
+        report: &AmsatReport,
+        count: usize,
+        sat_name: Option<&str>,
+        is_new: bool,
+        current_y: &mut f32,
+        now_utc: &DateTime<Utc>,
+    ) -> String {
+        let shift = if sat_name.is_some() { SAT_COLUMN_WIDTH } else { 0.0 };
+        let x_report = X_REPORT + self.column_offset() + shift;
+        let x_time = X_TIME + self.column_offset() + shift;
+        let (min_hours, max_hours) = self.time_color_window();
 
-                let y_pos = *current_y + ROW_HEIGHT / 2.0;
-                let report_color = ReportStatus::string_to_color_hex(&report.report);
-                let report_text = ReportStatus::from_string(&report.report).to_string();
-                
-                // Calculate time difference
-                let report_time = DateTime::parse_from_rfc3339(&report.reported_time)
-                    .unwrap_or_else(|_| Utc::now().into());
-                let hours_ago = (now_utc.signed_duration_since(report_time)).num_hours();
-
-                // Calculate time color (0-12 hours gradient: green -> yellow -> red)
-                let time_color = map_time_to_color(&report.reported_time, now_utc, 0.0, 12.0)
-                    .unwrap_or_else(|e| {
-                        tracing::warn!("Failed to map time to color: {}", e);
-                        "#808080".to_string() // Default gray
-                    });
-
-                block.push_str(&format!(
-                    r##"<g class="data-row">
-   <text x="{}" y="{}" class="table-text">{}</text>
+        let y_pos = *current_y + ROW_HEIGHT / 2.0;
+        let report_color = self.config.palette.string_to_color_hex(&report.report);
+        let report_text = ReportStatus::from_string(&report.report).to_string();
+        let callsign = self.shown_callsign(&report.callsign);
+        let callsign_text = if count > 1 {
+            format!("{} ×{}", callsign, count)
+        } else {
+            callsign
+        };
+        let grid = self.shown_grid(&report.grid_square);
+
+        // Time color (gradient over the configured window: green -> yellow -> red),
+        // gray when the time is unknown or the image is timeless
+        let time_color = match report.reported_at() {
+            Some(time) if !self.timeless() => map_time_to_color(time, now_utc, min_hours, max_hours),
+            _ => "#808080".to_string(),
+        };
+
+        let sat_cell = match sat_name {
+            Some(name) => format!(
+                "\n   <text x=\"{}\" y=\"{}\" class=\"table-text\">{}</text>",
+                X_CALLSIGN,
+                y_pos,
+                Self::escape_xml(name),
+            ),
+            None => String::new(),
+        };
+        let new_marker = if is_new {
+            format!(
+                "\n   <rect x=\"0\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#1f6feb\" class=\"new-report\" />",
+                *current_y,
+                NEW_REPORT_BAR_WIDTH,
+                ROW_HEIGHT,
+            )
+        } else {
+            String::new()
+        };
+        let distance_cell = match self.observer {
+            Some(observer) => format!(
+                "\n   <text x=\"{}\" y=\"{}\" class=\"table-text\">{}</text>",
+                X_DISTANCE + shift,
+                y_pos,
+                Self::distance_text(observer, &grid),
+            ),
+            None => String::new(),
+        };
+
+        let row = format!(
+            r##"<g class="data-row">{}{}
    <text x="{}" y="{}" class="table-text">{}</text>
+   <text x="{}" y="{}" class="table-text">{}</text>{}
    <rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="1" />
    <text x="{}" y="{}" class="table-text">{}</text>
    <rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="1" />
-   <text x="{}" y="{}" class="table-text">{} ({}h ago)</text>
+   <text x="{}" y="{}" class="table-text">{}</text>
 </g>
 "##,
-                    X_CALLSIGN,
-                    y_pos,
-                    Self::escape_xml(&report.callsign),
-                    X_GRIDS,
-                    y_pos,
-                    Self::escape_xml(&report.grid_square),
-                    X_REPORT,
-                    y_pos - COLOR_BLOCK_HEIGHT / 2.0,
-                    COLOR_BLOCK_WIDTH,
-                    COLOR_BLOCK_HEIGHT,
-                    report_color,
-                    X_REPORT + COLOR_BLOCK_WIDTH + COLOR_BLOCK_TEXT_SPACING,
-                    y_pos,
-                    report_text,
-                    X_TIME,
-                    y_pos - COLOR_BLOCK_HEIGHT / 2.0,
-                    COLOR_BLOCK_WIDTH,
-                    COLOR_BLOCK_HEIGHT,
-                    time_color,
-                    X_TIME + COLOR_BLOCK_WIDTH + COLOR_BLOCK_TEXT_SPACING,
-                    y_pos,
-                    report.reported_time,
-                    hours_ago
-                ));
-
-                *current_y += ROW_HEIGHT;
-                row_count += 1;
-            }
-        }
+            new_marker,
+            sat_cell,
+            X_CALLSIGN + shift,
+            y_pos,
+            Self::escape_xml(&callsign_text),
+            X_GRIDS + shift,
+            y_pos,
+            Self::escape_xml(&grid),
+            distance_cell,
+            x_report,
+            y_pos - COLOR_BLOCK_HEIGHT / 2.0,
+            COLOR_BLOCK_WIDTH,
+            COLOR_BLOCK_HEIGHT,
+            report_color,
+            x_report + COLOR_BLOCK_WIDTH + COLOR_BLOCK_TEXT_SPACING,
+            y_pos,
+            report_text,
+            x_time,
+            y_pos - COLOR_BLOCK_HEIGHT / 2.0,
+            COLOR_BLOCK_WIDTH,
+            COLOR_BLOCK_HEIGHT,
+            time_color,
+            x_time + COLOR_BLOCK_WIDTH + COLOR_BLOCK_TEXT_SPACING,
+            y_pos,
+            self.time_with_age(report, now_utc),
+        );
+        *current_y += ROW_HEIGHT;
+        row
+    }
+
+    /// Generate the merged layout: reports from all satellites in one table
+    ///
+    /// Rows are sorted newest first like the per-satellite tables; reports
+    /// with unparseable times go last. At most `merged_max_rows` rows are
+    /// drawn, always the newest ones, with a note counting the rest.
+    fn generate_merged_table(
+        &self,
+        satellites: &[SatelliteInfo],
+        previous: Option<&HashSet<ReportKey>>,
+        current_y: &mut f32,
+        now_utc: &DateTime<Utc>,
+    ) -> String {
+        let mut rows: Vec<(&str, &AmsatReport, usize, Option<DateTime<Utc>>)> = satellites
+            .iter()
+            .flat_map(|sat| {
+                self.report_rows(sat, now_utc).into_iter().map(|(report, count)| {
+                    (sat.name.as_str(), report, count, report.reported_at())
+                })
+            })
+            .collect();
+
+        if rows.is_empty() {
+            return self.generate_empty_state(&self.config.no_reports_message, &[], current_y);
+        }
+
+        // None sorts before Some, so descending order puts unparseable times last.
+        // The cap applies in time order so the newest reports are kept.
+        rows.sort_by_key(|row| std::cmp::Reverse(row.3));
+        let hidden = rows.len().saturating_sub(self.config.merged_max_rows);
+        rows.truncate(self.config.merged_max_rows);
+        if self.config.sort_by_grid {
+            rows.sort_by_key(|row| normalize_grid(&row.1.grid_square));
+        }
+
+        let mut block = self.generate_table_header(true, current_y);
+        for (sat_name, report, count, _) in rows {
+            let is_new = Self::is_new_report(previous, report);
+            block.push_str(&self.generate_report_row(report, count, Some(sat_name), is_new, current_y, now_utc));
+        }
+
+        if hidden > 0 {
+            block.push_str(&format!(
+                r#"<text x="{}" y="{}" text-anchor="middle" class="table-text merged-more">{}</text>"#,
+                (self.svg_width() + SAT_COLUMN_WIDTH) / 2.0,
+                *current_y + ROW_HEIGHT / 2.0,
+                Self::escape_xml(&self.config.merged_more_message.replace("{count}", &hidden.to_string())),
+            ));
+            block.push('\n');
+            *current_y += ROW_HEIGHT;
+        }
 
         *current_y += BLOCK_SPACING;
-        Ok(block)
+        block
+    }
+
+    /// Generate the compact layout: a header and one row per satellite
+    fn generate_compact_table<'a>(
+        &self,
+        satellites: impl IntoIterator<Item = &'a SatelliteInfo>,
+        current_y: &mut f32,
+        now_utc: &DateTime<Utc>,
+    ) -> String {
+        let mut block = format!(
+            r##"<g class="header">
+<rect x="0" y="{}" width="100%" height="{}" fill="#f0f2f5" />
+<text x="{}" y="{}" class="table-text header-text">Satellite</text>
+<text x="{}" y="{}" class="table-text header-text">Status</text>
+<text x="{}" y="{}" class="table-text header-text">Latest report</text>
+</g>
+"##,
+            *current_y,
+            HEADER_HEIGHT,
+            X_CALLSIGN,
+            *current_y + HEADER_HEIGHT / 2.0,
+            X_REPORT,
+            *current_y + HEADER_HEIGHT / 2.0,
+            X_TIME,
+            *current_y + HEADER_HEIGHT / 2.0,
+        );
+        *current_y += HEADER_HEIGHT;
+
+        for sat in satellites {
+            let y_pos = *current_y + ROW_HEIGHT / 2.0;
+
+            // Data blocks are sorted newest first
+            let latest = sat.data_blocks.iter().flat_map(|b| b.reports.iter()).next();
+            let (status_color, status_text, time_text) = match latest {
+                Some(report) => (
+                    self.config.palette.string_to_color_hex(&report.report),
+                    ReportStatus::from_string(&report.report).to_string(),
+                    self.time_with_age(report, now_utc),
+                ),
+                None => (
+                    self.config.palette.color_hex(ReportStatus::Grey),
+                    self.config.no_reports_message.clone(),
+                    "-".to_string(),
+                ),
+            };
+
+            block.push_str(&format!(
+                r##"<g class="compact-row">
+   <text x="{}" y="{}" class="table-text">{}</text>
+   <rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="1" />
+   <text x="{}" y="{}" class="table-text">{}</text>
+   <text x="{}" y="{}" class="table-text">{}</text>
+</g>
+"##,
+                X_CALLSIGN,
+                y_pos,
+                Self::escape_xml(&sat.name),
+                X_REPORT,
+                y_pos - COLOR_BLOCK_HEIGHT / 2.0,
+                COLOR_BLOCK_WIDTH,
+                COLOR_BLOCK_HEIGHT,
+                status_color,
+                X_REPORT + COLOR_BLOCK_WIDTH + COLOR_BLOCK_TEXT_SPACING,
+                y_pos,
+                Self::escape_xml(&status_text),
+                X_TIME,
+                y_pos,
+                time_text,
+            ));
+            *current_y += ROW_HEIGHT;
+        }
+
+        *current_y += BLOCK_SPACING;
+        block
+    }
+
+    /// Generate the comparison image: one column per side, split by a rule
+    fn generate_comparison_svg(
+        &self,
+        sides: &[(&str, Option<&SatelliteInfo>); 2],
+        now_utc: &DateTime<Utc>,
+    ) -> Result<String> {
+        let width = SVG_WIDTH;
+        let column_width = width / 2.0;
+        let mut content = String::new();
+        let mut bottom = TOP_PADDING;
+
+        for (i, (query, sat)) in sides.iter().enumerate() {
+            let x = i as f32 * column_width + X_CALLSIGN;
+            let mut y = TOP_PADDING;
+            content.push_str(&format!("<g class=\"compare-column\" id=\"compare-{}\">\n", i));
+            content.push_str(&format!(
+                r#"<text x="{}" y="{}" class="satellite-title">{}</text>"#,
+                x,
+                y + BLOCK_TITLE_HEIGHT / 2.0,
+                Self::escape_xml(sat.map_or(*query, |sat| sat.name.as_str()))
+            ));
+            content.push('\n');
+            y += BLOCK_TITLE_HEIGHT;
+
+            match sat {
+                None => {
+                    let message = format!("No satellite matches '{}'", query);
+                    content.push_str(&Self::comparison_line(x, &mut y, &message));
+                }
+                Some(sat) => content.push_str(&self.comparison_details(sat, x, &mut y, now_utc)),
+            }
+
+            content.push_str("</g>\n");
+            bottom = bottom.max(y);
+        }
+
+        content.push_str(&format!(
+            r##"<line x1="{0}" y1="{1}" x2="{0}" y2="{2}" stroke="#d0d7de" stroke-width="1" class="compare-divider" />"##,
+            column_width, TOP_PADDING, bottom
+        ));
+        content.push('\n');
+        let current_y = bottom + BLOCK_SPACING;
+
+        let (footer, total_height) = if self.config.footer_enabled {
+            (self.generate_footer(current_y), current_y + FOOTER_HEIGHT)
+        } else {
+            (String::new(), current_y)
+        };
+        self.fill_template(width, total_height, &content, &footer)
+    }
+
+    /// One satellite's column: metadata, latest status and recent reports
+    fn comparison_details(&self, sat: &SatelliteInfo, x: f32, y: &mut f32, now_utc: &DateTime<Utc>) -> String {
+        let mut block = String::new();
+        let mode = search::parse_amsat_name(&sat.name).mode;
+        block.push_str(&Self::comparison_line(x, y, &format!("Mode: {}", mode.as_deref().unwrap_or("-"))));
+        block.push_str(&Self::comparison_line(
+            x,
+            y,
+            &format!("Catalog: {}", sat.catalog_number.as_deref().unwrap_or("-")),
+        ));
+        if !sat.aliases.is_empty() {
+            block.push_str(&Self::comparison_line(x, y, &format!("Aliases: {}", sat.aliases.join(", "))));
+        }
+        block.push_str(&Self::comparison_line(
+            x,
+            y,
+            &format!("Active: {}", if sat.is_active { "yes" } else { "no" }),
+        ));
+
+        let rows = self.report_rows(sat, now_utc);
+        if rows.is_empty() {
+            block.push_str(&Self::comparison_line(x, y, &self.config.no_reports_message));
+            return block;
+        }
+
+        block.push_str(&Self::comparison_line(x, y, &format!("Reports: {}", sat.total_reports())));
+        for (report, count) in self.shown_rows(rows, 0, MAX_REPORTS_PER_SATELLITE) {
+            let y_pos = *y + ROW_HEIGHT / 2.0;
+            let callsign = if count > 1 {
+                format!("{} ×{}", self.shown_callsign(&report.callsign), count)
+            } else {
+                self.shown_callsign(&report.callsign)
+            };
+            let time = report
+                .reported_at()
+                .map_or_else(|| MISSING_FIELD.to_string(), |t| t.format("%m-%d %H:%MZ").to_string());
+            block.push_str(&format!(
+                r##"<g class="compare-row"><rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="1" /><text x="{}" y="{}" class="table-text">{} {} {}</text></g>
+"##,
+                x,
+                y_pos - COLOR_BLOCK_HEIGHT / 2.0,
+                COLOR_BLOCK_WIDTH,
+                COLOR_BLOCK_HEIGHT,
+                self.config.palette.string_to_color_hex(&report.report),
+                x + COLOR_BLOCK_WIDTH + COLOR_BLOCK_TEXT_SPACING,
+                y_pos,
+                Self::escape_xml(&callsign),
+                ReportStatus::from_string(&report.report),
+                time,
+            ));
+            *y += ROW_HEIGHT;
+        }
+        block
+    }
+
+    /// A plain text line in a comparison column
+    fn comparison_line(x: f32, y: &mut f32, text: &str) -> String {
+        let line = format!(
+            "<text x=\"{}\" y=\"{}\" class=\"table-text\">{}</text>\n",
+            x,
+            *y + ROW_HEIGHT / 2.0,
+            Self::escape_xml(text)
+        );
+        *y += ROW_HEIGHT;
+        line
+    }
+
+    /// Generate the status color legend using the active palette
+    fn generate_legend(&self, current_y: &mut f32) -> String {
+        let mut block = String::from("<g id=\"legend\">\n");
+
+        for (i, status) in ReportStatus::ALL.iter().enumerate() {
+            let x = X_CALLSIGN + (i % LEGEND_COLUMNS) as f32 * LEGEND_COLUMN_WIDTH;
+            let y = *current_y + (i / LEGEND_COLUMNS) as f32 * LEGEND_ROW_HEIGHT
+                + LEGEND_ROW_HEIGHT / 2.0;
+            block.push_str(&format!(
+                r#"   <rect x="{}" y="{}" width="{}" height="{}" fill="{}" rx="1" />
+   <text x="{}" y="{}" class="table-text row-text">{}</text>
+"#,
+                x,
+                y - COLOR_BLOCK_HEIGHT / 2.0,
+                COLOR_BLOCK_WIDTH,
+                COLOR_BLOCK_HEIGHT,
+                self.config.palette.color_hex(*status),
+                x + COLOR_BLOCK_WIDTH + COLOR_BLOCK_TEXT_SPACING,
+                y,
+                status
+            ));
+        }
+        block.push_str("</g>\n");
+
+        let rows = ReportStatus::ALL.len().div_ceil(LEGEND_COLUMNS);
+        *current_y += rows as f32 * LEGEND_ROW_HEIGHT + BLOCK_SPACING;
+        block
+    }
+
+    /// Generate a centered empty-state message with optional suggestions
+    fn generate_empty_state(
+        &self,
+        message: &str,
+        suggestions: &[String],
+        current_y: &mut f32,
+    ) -> String {
+        let center_x = self.svg_width() / 2.0;
+        let mut block = format!(
+            r#"<text x="{}" y="{}" text-anchor="middle" class="table-text">{}</text>"#,
+            center_x,
+            *current_y + EMPTY_STATE_HEIGHT / 2.0,
+            Self::escape_xml(message)
+        );
+        block.push('\n');
+        *current_y += EMPTY_STATE_HEIGHT;
+
+        if !suggestions.is_empty() {
+            block.push_str(&format!(
+                r#"<text x="{}" y="{}" text-anchor="middle" class="table-text">{} {}</text>"#,
+                center_x,
+                *current_y,
+                Self::escape_xml(&self.config.suggestion_prefix),
+                Self::escape_xml(&suggestions.join(", "))
+            ));
+            block.push('\n');
+            *current_y += ROW_HEIGHT;
+        }
+
+        block
+    }
+
+    /// Layout debugging guides drawn over the rendered content
+    ///
+    /// Dashed red lines mark the column X positions, blue ticks the y of
+    /// every text element and green lines the section boundaries.
+    fn generate_debug_overlay(
+        &self,
+        content: &str,
+        boundaries: &[f32],
+        merged: bool,
+        width: f32,
+        height: f32,
+    ) -> String {
+        let shift = if merged { SAT_COLUMN_WIDTH } else { 0.0 };
+        let mut columns = vec![
+            ("X_CALLSIGN", X_CALLSIGN + shift),
+            ("X_GRIDS", X_GRIDS + shift),
+            ("X_REPORT", X_REPORT + self.column_offset() + shift),
+            ("X_TIME", X_TIME + self.column_offset() + shift),
+        ];
+        if merged {
+            columns.push(("SAT", X_CALLSIGN));
+        }
+        if self.observer.is_some() {
+            columns.push(("X_DISTANCE", X_DISTANCE + shift));
+        }
+
+        let mut overlay = String::from("<g id=\"debug-overlay\" opacity=\"0.6\">\n");
+        for (name, x) in columns {
+            overlay.push_str(&format!(
+                r##"<line class="debug-column" x1="{}" y1="0" x2="{}" y2="{}" stroke="#e5534b" stroke-dasharray="4 3" />
+<text x="{}" y="10" font-size="9" fill="#e5534b">{}</text>
+"##,
+                x,
+                x,
+                height,
+                x + 2.0,
+                name,
+            ));
+        }
+
+        // Every text element's y, i.e. where its baseline is anchored
+        let mut baselines: Vec<f32> = content
+            .split("<text ")
+            .skip(1)
+            .filter_map(|element| {
+                let start = element.find(" y=\"")? + " y=\"".len();
+                let end = start + element[start..].find('"')?;
+                element[start..end].parse().ok()
+            })
+            .collect();
+        baselines.sort_by(f32::total_cmp);
+        baselines.dedup();
+        for y in baselines {
+            overlay.push_str(&format!(
+                r##"<line class="debug-baseline" x1="0" y1="{}" x2="12" y2="{}" stroke="#1f6feb" />
+"##,
+                y, y,
+            ));
+        }
+
+        for y in boundaries {
+            overlay.push_str(&format!(
+                r##"<line class="debug-block" x1="0" y1="{}" x2="{}" y2="{}" stroke="#2da44e" />
+"##,
+                y, width, y,
+            ));
+        }
+        overlay.push_str("</g>\n");
+        overlay
     }
 
     /// Generate footer
     fn generate_footer(&self, footer_y: f32) -> String {
-        let render_time = chrono::Local::now()
-            .format("%Y-%m-%d %H:%M:%S")
-            .to_string();
+        // Timeless images carry no render time
+        let render_time = if self.timeless() {
+            String::new()
+        } else {
+            format!(", rendered at {} BJT", chrono::Local::now().format("%Y-%m-%d %H:%M:%S"))
+        };
         
         format!(
             r##"    <g id="footer">
 <rect x="0" y="{}" width="100%" height="{}" fill="#f0f2f5" />
 <text x="50%" y="{}" class="table-text footer-text" text-anchor="middle">
-    Powered by Rinko, rendered at {} BJT
+    {}{}
 </text>
 </g>
 "##,
             footer_y,
             FOOTER_HEIGHT,
             footer_y + FOOTER_HEIGHT / 2.0,
+            Self::escape_xml(&self.config.footer_text),
             render_time
         )
     }
@@ -386,21 +1640,75 @@ impl SatelliteRenderer {
     }
 
     /// Render SVG to PNG using resvg
+    ///
+    /// Parsing and rasterization run on the blocking pool under the
+    /// global render limiter; only the file write stays on the runtime.
     async fn render_svg_to_png(&self, svg_content: &str, output_path: &Path) -> Result<()> {
+        let png_data = self.render_svg_bytes(svg_content.to_string()).await?;
+
+        // Write next to the target and rename into place, so readers never
+        // see a half-written PNG under the final name
+        let file_name = output_path
+            .file_name()
+            .context("PNG output path has no file name")?
+            .to_string_lossy();
+        let temp_path = output_path.with_file_name(format!(".{}.{}.tmp", file_name, uuid::Uuid::now_v7()));
+        let written = async {
+            tokio::fs::write(&temp_path, png_data)
+                .await
+                .context("Failed to write PNG file")?;
+            tokio::fs::rename(&temp_path, output_path)
+                .await
+                .context("Failed to move PNG file into place")
+        }
+        .await;
+        if written.is_err() {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+        }
+
+        written
+    }
+
+    /// Rasterize an SVG to PNG bytes on the blocking pool, under the limiter
+    async fn render_svg_bytes(&self, svg_content: String) -> Result<Vec<u8>> {
+        let compression_level = self.config.png_compression_level;
+        self.limiter
+            .run(move || {
+                let png = Self::rasterize_svg(&svg_content)?;
+                match compression_level {
+                    Some(level) => Self::compress_png(&png, level),
+                    None => Ok(png),
+                }
+            })
+            .await
+    }
+
+    /// Losslessly recompress a PNG with oxipng (CPU-bound)
+    ///
+    /// Our images are a few flat colors, so the color type and bit depth
+    /// reductions (down to a palette) save most of the size.
+    fn compress_png(png: &[u8], level: u8) -> Result<Vec<u8>> {
+        let mut options = oxipng::Options::from_preset(level.min(6));
+        options.strip = oxipng::StripChunks::Safe;
+        oxipng::optimize_from_memory(png, &options).context("Failed to compress PNG")
+    }
+
+    /// Parse, rasterize and PNG-encode an SVG document (CPU-bound)
+    fn rasterize_svg(svg_content: &str) -> Result<Vec<u8>> {
+        Self::rasterize_svg_with(svg_content, font_database())
+    }
+
+    fn rasterize_svg_with(svg_content: &str, fontdb: Arc<fontdb::Database>) -> Result<Vec<u8>> {
         use resvg::render;
         use usvg::{Options, Transform, Tree};
         use tiny_skia::Pixmap;
-        use fontdb::Database;
 
-        // Load fonts from fonts directory
-        let mut fontdb = Database::new();
-        fontdb.load_fonts_dir("fonts");
-        tracing::debug!("Loaded {} font faces from fonts directory", fontdb.len());
-
-        // Parse SVG with font database
-        let mut options = Options::default();
-        options.font_family = "Consolas".to_string();
-        options.fontdb = std::sync::Arc::new(fontdb);
+        // Parse SVG with the shared font database
+        let options = Options {
+            font_family: "Consolas".to_string(),
+            fontdb,
+            ..Default::default()
+        };
         
         let tree = Tree::from_str(svg_content, &options)
             .context("Failed to parse SVG")?;
@@ -414,15 +1722,7 @@ impl SatelliteRenderer {
         render(&tree, Transform::default(), &mut pixmap.as_mut());
 
         // Encode to PNG
-        let png_data = pixmap.encode_png()
-            .context("Failed to encode PNG")?;
-
-        // Write to file
-        tokio::fs::write(output_path, png_data)
-            .await
-            .context("Failed to write PNG file")?;
-
-        Ok(())
+        pixmap.encode_png().context("Failed to encode PNG")
     }
 }
 
@@ -442,15 +1742,1238 @@ mod tests {
         assert_eq!(SatelliteRenderer::escape_xml("Test & <tag>"), "Test &amp; &lt;tag&gt;");
     }
 
+    /// Extract the root `height` attribute from generated SVG
+    fn svg_height(svg: &str) -> f32 {
+        let start = svg.find("height=\"").unwrap() + "height=\"".len();
+        let end = start + svg[start..].find('"').unwrap();
+        svg[start..end].parse().unwrap()
+    }
+
+    #[test]
+    fn test_custom_footer_text() {
+        let config = RenderConfig {
+            footer_text: "Operated by BG5FNA".to_string(),
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+
+        let svg = renderer.generate_svg(&[], &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains("Operated by BG5FNA, rendered at"));
+        assert!(!svg.contains("Powered by Rinko"));
+    }
+
+    #[test]
+    fn test_disabled_footer() {
+        let enabled = SatelliteRenderer::new(std::env::temp_dir());
+        let config = RenderConfig {
+            footer_enabled: false,
+            ..Default::default()
+        };
+        let disabled = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+
+        let enabled_svg = enabled.generate_svg(&[], &[], RenderLayout::Full).unwrap();
+        let disabled_svg = disabled.generate_svg(&[], &[], RenderLayout::Full).unwrap();
+
+        assert!(enabled_svg.contains(r#"<g id="footer">"#));
+        assert!(!disabled_svg.contains(r#"<g id="footer">"#));
+        assert_eq!(svg_height(&enabled_svg) - svg_height(&disabled_svg), FOOTER_HEIGHT);
+    }
+
+    #[test]
+    fn test_empty_state_svg() {
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+
+        let svg = renderer.generate_svg(&[], &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains("No satellite data available."));
+        assert!(!svg.contains("Did you mean:"));
+        assert_eq!(svg_height(&svg), TOP_PADDING + EMPTY_STATE_HEIGHT + FOOTER_HEIGHT);
+
+        let suggestions = vec!["AO-91".to_string(), "AO-92".to_string()];
+        let svg = renderer.generate_svg(&[], &suggestions, RenderLayout::Full).unwrap();
+        assert!(svg.contains("Did you mean: AO-91, AO-92"));
+        assert_eq!(
+            svg_height(&svg),
+            TOP_PADDING + EMPTY_STATE_HEIGHT + ROW_HEIGHT + FOOTER_HEIGHT
+        );
+    }
+
+    /// Extract the root `width` attribute from generated SVG
+    fn svg_width(svg: &str) -> f32 {
+        let start = svg.find("width=\"").unwrap() + "width=\"".len();
+        let end = start + svg[start..].find('"').unwrap();
+        svg[start..end].parse().unwrap()
+    }
+
+    #[test]
+    fn test_distance_column() {
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![AmsatReport {
+                name: "AO-91".to_string(),
+                reported_time: "2026-02-16T08:10:00Z".to_string(),
+                callsign: "W1AW".to_string(),
+                report: "Heard".to_string(),
+                grid_square: "FN31".to_string(),
+                ..Default::default()
+            }],
+        });
+
+        let plain = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = plain.generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full).unwrap();
+        assert!(!svg.contains("Dist/Brg"));
+        assert_eq!(svg_width(&svg), SVG_WIDTH);
+
+        let config = RenderConfig {
+            observer_grid: Some("JN58".to_string()),
+            show_distance: true,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        let svg = renderer.generate_svg(&[sat], &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains("Dist/Brg"));
+        assert!(svg.contains("6311km/297°"));
+        assert_eq!(svg_width(&svg), SVG_WIDTH + DISTANCE_COLUMN_WIDTH);
+    }
+
+    #[test]
+    fn test_debug_overlay_guides() {
+        let plain = SatelliteRenderer::new(std::env::temp_dir());
+        let debug = SatelliteRenderer::with_config(
+            std::env::temp_dir(),
+            RenderConfig {
+                debug_overlay: true,
+                ..Default::default()
+            },
+        );
+        let sats = [SatelliteInfo::new("AO-91"), SatelliteInfo::new("SO-50")];
+
+        let svg = plain.generate_svg(&sats, &[], RenderLayout::Full).unwrap();
+        assert!(!svg.contains("debug-overlay"));
+        assert!(!svg.contains("debug-column"));
+
+        let svg = debug.generate_svg(&sats, &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains(r#"<g id="debug-overlay""#));
+        assert!(svg.contains(&format!(r#"class="debug-column" x1="{}""#, X_GRIDS)));
+        assert!(svg.contains(">X_CALLSIGN</text>"));
+        assert!(svg.contains("debug-baseline"));
+        // Top padding, two satellite blocks and the end of the content
+        assert!(svg.matches("debug-block").count() >= 3);
+        SatelliteRenderer::rasterize_svg(&svg).unwrap();
+    }
+
+    #[test]
+    fn test_local_report_times() {
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T09:00:00Z".to_string(),
+            reports: vec![AmsatReport {
+                name: "AO-91".to_string(),
+                reported_time: "2026-02-16T09:30:00Z".to_string(),
+                callsign: "BG5FNA".to_string(),
+                report: "Heard".to_string(),
+                grid_square: "OM89".to_string(),
+                ..Default::default()
+            }],
+        });
+
+        let plain = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = plain.generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains("2026-02-16T09:30:00Z ("));
+
+        let config = RenderConfig {
+            local_time_offset: Some("+08:00".to_string()),
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        for layout in [RenderLayout::Full, RenderLayout::Compact] {
+            let svg = renderer.generate_svg(std::slice::from_ref(&sat), &[], layout).unwrap();
+            assert!(svg.contains("09:30Z / 17:30 BJT ("), "{:?}", layout);
+            assert!(svg.contains("h ago)"));
+        }
+    }
+
+    #[test]
+    fn test_redacted_callsigns_and_grids() {
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        assert_eq!(redact_callsign("BG5FNA"), "BG5…");
+        assert_eq!(redact_callsign("W1AW"), "W1…");
+        assert_eq!(redact_callsign("VK2/W1AW"), "VK2…");
+        assert_eq!(redact_callsign("K1"), "K1");
+        assert_eq!(redact_callsign(MISSING_FIELD), MISSING_FIELD);
+        assert_eq!(redact_grid("OM89ab"), "OM89");
+        assert_eq!(redact_grid("FN31"), "FN31");
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T09:00:00Z".to_string(),
+            reports: vec![AmsatReport {
+                reported_time: "2026-02-16T09:30:00Z".to_string(),
+                callsign: "BG5FNA".to_string(),
+                report: "Heard".to_string(),
+                grid_square: "OM89ab".to_string(),
+                ..Default::default()
+            }],
+        });
+        let now = DateTime::parse_from_rfc3339("2026-02-16T09:40:00Z").unwrap().with_timezone(&Utc);
+
+        let plain = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = plain.generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains(">BG5FNA</text>"));
+        assert!(svg.contains(">OM89ab</text>"));
+
+        let config = RenderConfig {
+            redact_callsigns: true,
+            redact_grids: true,
+            ..Default::default()
+        };
+        let redacted = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        let svg = redacted.generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains(">BG5…</text>"), "{}", svg);
+        assert!(svg.contains(">OM89</text>"));
+        assert!(!svg.contains("FNA"));
+        assert!(!svg.contains("OM89ab"));
+
+        // Redacted images never reuse an unredacted file
+        let sats = std::slice::from_ref(&sat);
+        assert_ne!(
+            plain.generate_filename_at(sats, RenderLayout::Full, now),
+            redacted.generate_filename_at(sats, RenderLayout::Full, now)
+        );
+    }
+
+    #[test]
+    fn test_unknown_report_time_not_shown_as_now() {
+        use super::super::types::SatelliteDataBlock;
+
+        let report = |callsign: &str, time: &str| AmsatReport {
+            reported_time: time.to_string(),
+            callsign: callsign.to_string(),
+            report: "Heard".to_string(),
+            ..Default::default()
+        };
+        let three_hours_ago = (Utc::now() - chrono::Duration::hours(3)).format("%Y-%m-%d %H:%M:%S UTC").to_string();
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T09:00:00Z".to_string(),
+            reports: vec![report("AAA1", &three_hours_ago), report("BBB2", "sometime")],
+        });
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = renderer.generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full).unwrap();
+        let rows: Vec<&str> = svg.split(r#"<g class="data-row">"#).skip(1).collect();
+        assert!(rows[0].contains(&format!("{} (3h ago)", three_hours_ago)), "{}", rows[0]);
+        assert!(rows[1].contains(&format!(">{}</text>", MISSING_FIELD)), "{}", rows[1]);
+        assert!(!rows[1].contains("sometime"));
+        assert!(!rows[1].contains("h ago"));
+    }
+
+    #[test]
+    fn test_missing_fields_shown_as_placeholder() {
+        use super::super::types::SatelliteDataBlock;
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T09:00:00Z".to_string(),
+            reports: vec![AmsatReport {
+                reported_time: "2026-02-16T09:30:00Z".to_string(),
+                callsign: String::new(),
+                report: "Heard".to_string(),
+                grid_square: "ZZ99".to_string(),
+                ..Default::default()
+            }],
+        });
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = renderer.generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full).unwrap();
+        let rows: Vec<&str> = svg.split(r#"<g class="data-row">"#).skip(1).collect();
+        assert_eq!(rows[0].matches(&format!(">{}</text>", MISSING_FIELD)).count(), 2, "{}", rows[0]);
+        assert!(!rows[0].contains("ZZ99"));
+    }
+
+    #[test]
+    fn test_distance_column_needs_valid_observer() {
+        let config = RenderConfig {
+            observer_grid: Some("not a grid".to_string()),
+            show_distance: true,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        assert_eq!(renderer.svg_width(), SVG_WIDTH);
+        assert_eq!(SatelliteRenderer::distance_text((48.5, 11.0), ""), "-");
+    }
+
+    #[test]
+    fn test_compact_layout() {
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        let satellites: Vec<SatelliteInfo> = ["AO-91", "SO-50", "ISS-FM"]
+            .iter()
+            .map(|name| {
+                let mut sat = SatelliteInfo::new(*name);
+                sat.data_blocks.push(SatelliteDataBlock {
+                    time: "2026-02-16T08:00:00Z".to_string(),
+                    reports: vec![AmsatReport::default(), AmsatReport::default()],
+                });
+                sat
+            })
+            .collect();
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let full = renderer.generate_svg(&satellites, &[], RenderLayout::Full).unwrap();
+        let compact = renderer.generate_svg(&satellites, &[], RenderLayout::Compact).unwrap();
+
+        assert_eq!(compact.matches(r#"class="compact-row""#).count(), satellites.len());
+        assert!(!compact.contains(r#"class="data-row""#));
+        assert!(svg_height(&compact) < svg_height(&full));
+
+        let full_name = renderer.generate_filename(&satellites, RenderLayout::Full);
+        let compact_name = renderer.generate_filename(&satellites, RenderLayout::Compact);
+        assert_ne!(full_name, compact_name);
+        assert!(compact_name.ends_with("_compact.png"));
+    }
+
+    #[test]
+    fn test_sparse_satellites_render_compactly() {
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        let sat_with = |name: &str, reports: usize| {
+            let mut sat = SatelliteInfo::new(name);
+            sat.data_blocks.push(SatelliteDataBlock {
+                time: "2026-02-16T08:00:00Z".to_string(),
+                reports: (0..reports)
+                    .map(|i| AmsatReport {
+                        callsign: format!("{}-{}", name, i),
+                        reported_time: format!("2026-02-16T08:{:02}:00Z", i),
+                        report: "Heard".to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            });
+            sat
+        };
+        let satellites = [sat_with("AO-07", 1), sat_with("SO-50", 6)];
+
+        // Off by default
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = renderer.generate_svg(&satellites, &[], RenderLayout::Full).unwrap();
+        let busy_rows = MAX_REPORTS_PER_SATELLITE.min(6);
+        assert_eq!(svg.matches(r#"<g class="data-row">"#).count(), 1 + busy_rows);
+        assert!(!svg.contains("compact-row"));
+
+        let config = RenderConfig {
+            min_reports_per_block: 3,
+            ..Default::default()
+        };
+        let svg = SatelliteRenderer::with_config(std::env::temp_dir(), config)
+            .generate_svg(&satellites, &[], RenderLayout::Full)
+            .unwrap();
+        let compact: Vec<&str> = svg.split(r#"<g class="compact-row">"#).skip(1).collect();
+        assert_eq!(compact.len(), 1);
+        assert!(compact[0].contains(">AO-07</text>"));
+        assert!(!svg.contains(r#"class="satellite-title">AO-07<"#));
+        assert!(svg.contains(r#"class="satellite-title">SO-50<"#));
+        assert_eq!(svg.matches(r#"<g class="data-row">"#).count(), busy_rows);
+        assert!(!svg.contains(">AO-07-0</text>"));
+    }
+
+    #[test]
+    fn test_compressed_png_is_smaller_and_identical() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" width="400" height="120">
+<rect width="400" height="120" fill="#ffffff" />
+<rect x="20" y="20" width="120" height="30" fill="#4caf50" />
+<rect x="160" y="20" width="120" height="30" fill="#f44336" />
+<line x1="0" y1="80" x2="400" y2="80" stroke="#d0d7de" stroke-width="1" />
+</svg>"##;
+        let raw = SatelliteRenderer::rasterize_svg(svg).unwrap();
+        let compressed = SatelliteRenderer::compress_png(&raw, 4).unwrap();
+        assert!(compressed.len() < raw.len(), "{} >= {}", compressed.len(), raw.len());
+
+        let raw = tiny_skia::Pixmap::decode_png(&raw).unwrap();
+        let compressed = tiny_skia::Pixmap::decode_png(&compressed).unwrap();
+        assert_eq!((compressed.width(), compressed.height()), (raw.width(), raw.height()));
+        assert!(compressed.data() == raw.data());
+    }
+
+    #[test]
+    fn test_comparison_columns() {
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        let mut ao91 = SatelliteInfo::new("AO-91");
+        ao91.aliases.push("FOX-1B".to_string());
+        ao91.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![AmsatReport {
+                callsign: "BA1ABC".to_string(),
+                reported_time: "2026-02-16T08:00:00Z".to_string(),
+                report: "Heard".to_string(),
+                ..Default::default()
+            }],
+        });
+        let so50 = SatelliteInfo::new("SO-50");
+        let now = DateTime::parse_from_rfc3339("2026-02-16T09:00:00Z").unwrap().with_timezone(&Utc);
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = renderer
+            .generate_comparison_svg(&[("ao-91", Some(&ao91)), ("so-50", Some(&so50))], &now)
+            .unwrap();
+        let columns: Vec<&str> = svg.split(r#"<g class="compare-column""#).skip(1).collect();
+        assert_eq!(columns.len(), 2);
+        assert!(columns[0].starts_with(r#" id="compare-0">"#));
+        assert!(columns[0].contains(r#"class="satellite-title">AO-91<"#));
+        assert!(columns[0].contains("Aliases: FOX-1B"));
+        assert!(columns[0].contains("BA1ABC"));
+        assert!(columns[1].starts_with(r#" id="compare-1">"#));
+        assert!(columns[1].contains(r#"class="satellite-title">SO-50<"#));
+        assert!(columns[1].contains(&renderer.config.no_reports_message));
+        assert!(!columns[1].contains("AO-91"));
+
+        // A side with no match keeps its column and says so
+        let svg = renderer
+            .generate_comparison_svg(&[("ao-91", Some(&ao91)), ("xx-99", None)], &now)
+            .unwrap();
+        let columns: Vec<&str> = svg.split(r#"<g class="compare-column""#).skip(1).collect();
+        assert!(columns[0].contains(r#"class="satellite-title">AO-91<"#));
+        assert!(columns[1].contains("No satellite matches &apos;xx-99&apos;"));
+    }
+
+    #[test]
+    fn test_merged_layout() {
+        use super::super::types::SatelliteDataBlock;
+
+        let sat_with = |name: &str, reports: &[(&str, &str)]| {
+            let mut sat = SatelliteInfo::new(name);
+            sat.data_blocks.push(SatelliteDataBlock {
+                time: "2026-02-16T08:00:00Z".to_string(),
+                reports: reports
+                    .iter()
+                    .map(|(callsign, time)| AmsatReport {
+                        callsign: callsign.to_string(),
+                        reported_time: time.to_string(),
+                        report: "Heard".to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            });
+            sat
+        };
+        let satellites = [
+            sat_with("AO-91", &[("AAA1", "2026-02-16T08:30:00Z"), ("AAA2", "2026-02-16T08:10:00Z")]),
+            sat_with("SO-50", &[("SSS1", "2026-02-16T08:20:00Z")]),
+        ];
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = renderer.generate_svg(&satellites, &[], RenderLayout::Merged).unwrap();
+
+        assert!(svg.contains(">Sat</text>"));
+        let rows: Vec<&str> = svg.split(r#"<g class="data-row">"#).skip(1).collect();
+        assert_eq!(rows.len(), 3);
+        let expected = [("AO-91", "AAA1"), ("SO-50", "SSS1"), ("AO-91", "AAA2")];
+        for (row, (sat, callsign)) in rows.iter().zip(expected) {
+            assert!(row.contains(&format!(">{}</text>", sat)), "{}", row);
+            assert!(row.contains(&format!(">{}</text>", callsign)), "{}", row);
+        }
+        assert_eq!(svg_width(&svg), SVG_WIDTH + SAT_COLUMN_WIDTH);
+        assert!(renderer
+            .generate_filename(&satellites, RenderLayout::Merged)
+            .ends_with("_merged.png"));
+
+        // Rows are capped across all satellites
+        let config = RenderConfig {
+            merged_max_rows: 2,
+            ..Default::default()
+        };
+        let capped = SatelliteRenderer::with_config(std::env::temp_dir(), config)
+            .generate_svg(&satellites, &[], RenderLayout::Merged)
+            .unwrap();
+        assert_eq!(capped.matches(r#"<g class="data-row">"#).count(), 2);
+        assert!(!capped.contains(">AAA2</text>"));
+        assert!(capped.contains("…and 1 more reports"));
+        assert!(!svg.contains("merged-more"));
+    }
+
+    #[test]
+    fn test_merged_row_cap_keeps_newest() {
+        use super::super::types::SatelliteDataBlock;
+
+        // 100 reports over two satellites, one minute apart, newest at 09:39
+        let base = DateTime::parse_from_rfc3339("2026-02-16T08:00:00Z").unwrap();
+        let satellites: Vec<SatelliteInfo> = ["AO-91", "SO-50"]
+            .iter()
+            .enumerate()
+            .map(|(s, name)| {
+                let mut sat = SatelliteInfo::new(*name);
+                sat.data_blocks.push(SatelliteDataBlock {
+                    time: "2026-02-16T08:00:00Z".to_string(),
+                    reports: (0..50)
+                        .map(|i| {
+                            let minute = i * 2 + s;
+                            AmsatReport {
+                                callsign: format!("CALL{:03}", minute),
+                                reported_time: (base + chrono::Duration::minutes(minute as i64)).to_rfc3339(),
+                                report: "Heard".to_string(),
+                                grid_square: format!("PM{:02}", 99 - minute),
+                                ..Default::default()
+                            }
+                        })
+                        .collect(),
+                });
+                sat
+            })
+            .collect();
+
+        for sort_by_grid in [false, true] {
+            let config = RenderConfig {
+                merged_max_rows: 30,
+                sort_by_grid,
+                ..Default::default()
+            };
+            let svg = SatelliteRenderer::with_config(std::env::temp_dir(), config)
+                .generate_svg(&satellites, &[], RenderLayout::Merged)
+                .unwrap();
+
+            assert_eq!(svg.matches(r#"<g class="data-row">"#).count(), 30);
+            for minute in 0..100 {
+                let shown = svg.contains(&format!(">CALL{:03}</text>", minute));
+                assert_eq!(shown, minute >= 70, "CALL{:03}", minute);
+            }
+            assert!(svg.contains("…and 70 more reports"));
+        }
+    }
+
+    #[test]
+    fn test_more_results_note() {
+        let satellites: Vec<SatelliteInfo> =
+            (1..=8).map(|i| SatelliteInfo::new(format!("SAT-{}", i))).collect();
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let complete = renderer.generate_svg(&satellites, &[], RenderLayout::Compact).unwrap();
+        assert!(!complete.contains("more-results"));
+
+        let truncated = SatelliteRenderer::new(std::env::temp_dir()).with_omitted(12);
+        let svg = truncated.generate_svg(&satellites, &[], RenderLayout::Compact).unwrap();
+        assert!(svg.contains("...and 12 more, refine your query"));
+        assert_eq!(svg_height(&svg) - svg_height(&complete), ROW_HEIGHT);
+        assert_ne!(
+            truncated.generate_filename(&satellites, RenderLayout::Compact),
+            renderer.generate_filename(&satellites, RenderLayout::Compact)
+        );
+    }
+
+    #[test]
+    fn test_content_hash_svg_is_timeless() {
+        use super::super::types::SatelliteDataBlock;
+
+        let renderer = SatelliteRenderer::with_config(
+            std::env::temp_dir(),
+            RenderConfig {
+                content_hash_filenames: true,
+                new_badge_days: 7,
+                ..Default::default()
+            },
+        );
+        let now = DateTime::parse_from_rfc3339("2026-02-16T08:20:00Z").unwrap().with_timezone(&Utc);
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.first_seen = now - chrono::Duration::days(1);
+        sat.last_updated = now - chrono::Duration::hours(1);
+        sat.data_blocks = vec![SatelliteDataBlock {
+            time: "2026-02-16T08:00:00+00:00".to_string(),
+            reports: vec![station_report("W1AW", "FN31", "2026-02-16T08:10:00Z")],
+        }];
+        let svg_at = |renderer: &SatelliteRenderer, now| {
+            renderer
+                .generate_svg_at(std::slice::from_ref(&sat), &[], RenderLayout::Full, None, now)
+                .unwrap()
+        };
+
+        let svg = svg_at(&renderer, now);
+        assert_eq!(svg, svg_at(&renderer, now + chrono::Duration::days(3)));
+        assert!(!svg.contains("ago") && !svg.contains("rendered at") && !svg.contains("new-badge"));
+        assert!(svg.contains("W1AW"));
+
+        // Without content hashes the same data renders differently over time
+        let timed = SatelliteRenderer::new(std::env::temp_dir());
+        assert_ne!(svg_at(&timed, now), svg_at(&timed, now + chrono::Duration::days(3)));
+    }
+
+    #[tokio::test]
+    async fn test_content_hash_filenames() {
+        let temp_dir = std::env::temp_dir().join("rinko_content_hash_filenames_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        let config = RenderConfig {
+            content_hash_filenames: true,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(&temp_dir, config);
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        let name = |path: PathBuf| path.file_name().unwrap().to_string_lossy().to_string();
+        let first = name(renderer.render_satellites(std::slice::from_ref(&sat), RenderLayout::Full).await.unwrap());
+        let again = name(renderer.render_satellites(std::slice::from_ref(&sat), RenderLayout::Full).await.unwrap());
+        assert_eq!(first, again);
+        assert!(first.starts_with("sat_ao91_"), "{}", first);
+
+        let compact = renderer.render_satellites(std::slice::from_ref(&sat), RenderLayout::Compact).await.unwrap();
+        assert_ne!(first, name(compact));
+
+        sat.amsat_update_status = !sat.amsat_update_status;
+        assert_ne!(first, name(renderer.render_satellites(&[sat], RenderLayout::Full).await.unwrap()));
+
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
+
+    #[test]
+    fn test_same_normalized_names_get_distinct_filenames() {
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let now = DateTime::parse_from_rfc3339("2026-02-16T08:20:00Z").unwrap().with_timezone(&Utc);
+        let sats = |names: &[&str]| names.iter().map(|n| SatelliteInfo::new(*n)).collect::<Vec<_>>();
+
+        // Deterministic for a fixed time
+        let name = renderer.generate_filename_at(&sats(&["AO-91", "SO-50"]), RenderLayout::Full, now);
+        assert_eq!(name, renderer.generate_filename_at(&sats(&["AO-91", "SO-50"]), RenderLayout::Full, now));
+        assert!(name.starts_with("sat_20260216_0815_ao91_so50_"), "{}", name);
+
+        // Normalization and "_and_N_more" shortening hide these differences
+        for (a, b) in [
+            (&["FO-29"][..], &["FO 29"][..]),
+            (&["AO-91", "SO-50", "AO-27", "FO-29"], &["AO-91", "SO-50", "IO-117", "RS-44"]),
+        ] {
+            assert_eq!(renderer.filename_stem(&sats(a)), renderer.filename_stem(&sats(b)));
+            assert_ne!(
+                renderer.generate_filename_at(&sats(a), RenderLayout::Full, now),
+                renderer.generate_filename_at(&sats(b), RenderLayout::Full, now)
+            );
+        }
+
+        let suggestions = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_ne!(
+            renderer.generate_no_results_filename_at(&suggestions(&["FO-29"]), now),
+            renderer.generate_no_results_filename_at(&suggestions(&["FO 29"]), now)
+        );
+    }
+
+    #[test]
+    fn test_time_color_window() {
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        let reported = Utc::now() - chrono::Duration::hours(1);
+        let mut sat = SatelliteInfo::new("SO-50");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: reported.to_rfc3339(),
+            reports: vec![AmsatReport {
+                reported_time: reported.to_rfc3339(),
+                ..Default::default()
+            }],
+        });
+
+        let svg_with_window = |max_hours: f64| {
+            let config = RenderConfig {
+                time_color_max_hours: max_hours,
+                ..Default::default()
+            };
+            SatelliteRenderer::with_config(std::env::temp_dir(), config)
+                .generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full)
+                .unwrap()
+        };
+
+        let now = Utc::now();
+        let narrow = map_time_to_color(reported, &now, 0.0, 2.0);
+        let wide = map_time_to_color(reported, &now, 0.0, 12.0);
+        assert_ne!(narrow, wide);
+
+        assert!(svg_with_window(2.0).contains(&narrow));
+        assert!(svg_with_window(12.0).contains(&wide));
+    }
+
+    #[test]
+    fn test_renderer_uses_selected_palette() {
+        use super::super::palette::ColorPalette;
+        use super::super::types::{AmsatReport, SatelliteDataBlock};
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![
+                AmsatReport {
+                    callsign: "BG5FNA".to_string(),
+                    report: "Heard".to_string(),
+                    ..Default::default()
+                },
+                AmsatReport {
+                    callsign: "W1AW".to_string(),
+                    report: "Not Heard".to_string(),
+                    ..Default::default()
+                },
+            ],
+        });
+
+        let config = RenderConfig {
+            palette: ColorPalette::Cvd,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        let svg = renderer
+            .generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full)
+            .unwrap();
+
+        assert!(svg.contains(ColorPalette::Cvd.color_hex(ReportStatus::Blue)));
+        assert!(svg.contains(ColorPalette::Cvd.color_hex(ReportStatus::Red)));
+        assert!(!svg.contains(ColorPalette::Default.color_hex(ReportStatus::Blue)));
+        assert!(!svg.contains(ColorPalette::Default.color_hex(ReportStatus::Red)));
+    }
+
+    #[test]
+    fn test_legend() {
+        use super::super::palette::ColorPalette;
+
+        let sats = [SatelliteInfo::new("AO-91")];
+        let render = |legend| {
+            let config = RenderConfig {
+                legend,
+                palette: ColorPalette::Cvd,
+                ..Default::default()
+            };
+            SatelliteRenderer::with_config(std::env::temp_dir(), config)
+                .generate_svg(&sats, &[], RenderLayout::Full)
+                .unwrap()
+        };
+
+        let without = render(LegendPosition::Off);
+        assert!(!without.contains(r#"<g id="legend">"#));
+
+        for position in [LegendPosition::Top, LegendPosition::Bottom] {
+            let svg = render(position);
+            let legend_start = svg.find(r#"<g id="legend">"#).unwrap();
+            let legend = &svg[legend_start..legend_start + svg[legend_start..].find("</g>").unwrap()];
+            for status in ReportStatus::ALL {
+                assert!(legend.contains(&status.to_string()));
+                assert!(legend.contains(ColorPalette::Cvd.color_hex(status)));
+            }
+            assert_eq!(
+                svg_height(&svg) - svg_height(&without),
+                3.0 * LEGEND_ROW_HEIGHT + BLOCK_SPACING
+            );
+        }
+
+        // The empty state has nothing to explain
+        let config = RenderConfig {
+            legend: LegendPosition::Top,
+            ..Default::default()
+        };
+        let svg = SatelliteRenderer::with_config(std::env::temp_dir(), config)
+            .generate_svg(&[], &[], RenderLayout::Full)
+            .unwrap();
+        assert!(!svg.contains(r#"<g id="legend">"#));
+    }
+
+    #[test]
+    fn test_all_layouts_share_configured_template() {
+        let dir = std::env::temp_dir().join("rinko_template_shared_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sat_template.svg");
+        std::fs::write(
+            &path,
+            r#"<svg width="{{SVG_WIDTH}}" height="{{SVG_HEIGHT}}"><!-- shared -->{{CONTENT}}{{FOOTER}}</svg>"#,
+        )
+        .unwrap();
+
+        let config = RenderConfig {
+            template_path: path.to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(&dir, config);
+        let sats = [SatelliteInfo::new("AO-91")];
+
+        for (sats, layout) in [
+            (&sats[..], RenderLayout::Full),
+            (&sats[..], RenderLayout::Compact),
+            (&[][..], RenderLayout::Full),
+        ] {
+            let svg = renderer.generate_svg(sats, &[], layout).unwrap();
+            assert!(svg.contains("<!-- shared -->"));
+        }
+    }
+
+    #[test]
+    fn test_template_reloads_on_change() {
+        use super::super::template::watch_templates;
+
+        let dir = std::env::temp_dir().join("rinko_template_watch_test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("sat_template.svg");
+        let template = |marker: &str| {
+            format!(
+                r#"<svg width="{{{{SVG_WIDTH}}}}" height="{{{{SVG_HEIGHT}}}}"><!-- {} -->{{{{CONTENT}}}}{{{{FOOTER}}}}</svg>"#,
+                marker
+            )
+        };
+        std::fs::write(&path, template("v1")).unwrap();
+
+        let config = RenderConfig {
+            template_path: path.to_string_lossy().into_owned(),
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(&dir, config);
+        let render = || renderer.generate_svg(&[], &[], RenderLayout::Full).unwrap();
+        assert!(render().contains("<!-- v1 -->"));
+
+        let _watcher = watch_templates(&path).unwrap();
+        std::fs::write(&path, template("v2")).unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while !render().contains("<!-- v2 -->") {
+            assert!(std::time::Instant::now() < deadline, "template was not reloaded");
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_localized_empty_message() {
+        let config = RenderConfig {
+            empty_message: "没有卫星数据".to_string(),
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+
+        let svg = renderer.generate_svg(&[], &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains("没有卫星数据"));
+    }
+
+    #[tokio::test]
+    async fn test_render_does_not_block_runtime() {
+        let temp_dir = std::env::temp_dir().join("rinko_render_nonblocking_test");
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        let output_path = temp_dir.join("heavy.png");
+
+        // Large blurred canvas: slow enough to stall the runtime if run inline
+        let svg = r##"<svg width="800" height="800" xmlns="http://www.w3.org/2000/svg">
+<filter id="blur"><feGaussianBlur stdDeviation="10"/></filter>
+<rect width="100%" height="100%" fill="#4297f3" filter="url(#blur)"/>
+</svg>"##;
+
+        let renderer = SatelliteRenderer::new(&temp_dir);
+        let render_start = std::time::Instant::now();
+        let render = tokio::spawn(async move { renderer.render_svg_to_png(svg, &output_path).await });
+
+        let timer_start = std::time::Instant::now();
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        let timer_elapsed = timer_start.elapsed();
+
+        render.await.unwrap().unwrap();
+        let render_elapsed = render_start.elapsed();
+
+        assert!(
+            timer_elapsed < std::time::Duration::from_millis(100) || timer_elapsed < render_elapsed / 2,
+            "timer took {:?} while render took {:?}",
+            timer_elapsed,
+            render_elapsed
+        );
+
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
+
+    #[tokio::test]
+    async fn test_render_replaces_png_without_leftovers() {
+        let temp_dir = std::env::temp_dir().join("rinko_render_atomic_write_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        let output_path = temp_dir.join("atomic.png");
+        tokio::fs::write(&output_path, b"stale").await.unwrap();
+
+        let svg = r##"<svg width="10" height="10" xmlns="http://www.w3.org/2000/svg"><rect width="10" height="10"/></svg>"##;
+        let renderer = SatelliteRenderer::new(&temp_dir);
+        renderer.render_svg_to_png(svg, &output_path).await.unwrap();
+
+        let png = tokio::fs::read(&output_path).await.unwrap();
+        assert!(png.starts_with(b"\x89PNG"));
+        let mut entries = tokio::fs::read_dir(&temp_dir).await.unwrap();
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await.unwrap() {
+            names.push(entry.file_name().to_string_lossy().to_string());
+        }
+        assert_eq!(names, vec!["atomic.png".to_string()]);
+
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
+
+    fn station_report(callsign: &str, grid: &str, time: &str) -> AmsatReport {
+        AmsatReport {
+            callsign: callsign.to_string(),
+            grid_square: grid.to_string(),
+            reported_time: time.to_string(),
+            report: "Heard".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_collapse_same_station_reports() {
+        use super::super::types::SatelliteDataBlock;
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![
+                station_report("BG5FNA", "PM01", "2026-02-16T08:20:00Z"),
+                station_report("bg5fna", "pm01", "2026-02-16T08:15:00Z"),
+                station_report("BG5FNA", "PM01", "2026-02-16T08:10:00Z"),
+                station_report("W1AW", "FN31", "2026-02-16T08:05:00Z"),
+            ],
+        });
+
+        let config = RenderConfig {
+            collapse_window_minutes: 15,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        let rows = renderer.report_rows(&sat, &Utc::now());
+        assert_eq!(rows.len(), 2);
+        assert_eq!((rows[0].0.reported_time.as_str(), rows[0].1), ("2026-02-16T08:20:00Z", 3));
+        assert_eq!((rows[1].0.callsign.as_str(), rows[1].1), ("W1AW", 1));
+
+        let svg = renderer.generate_svg(&[sat.clone()], &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains("BG5FNA ×3"));
+        assert_eq!(svg.matches(r#"class="data-row""#).count(), 2);
+
+        // Off by default
+        let plain = SatelliteRenderer::new(std::env::temp_dir());
+        assert_eq!(plain.report_rows(&sat, &Utc::now()).len(), 4);
+    }
+
+    #[test]
+    fn test_collapse_respects_window() {
+        let reports = [
+            station_report("BG5FNA", "PM01", "2026-02-16T08:20:00Z"),
+            station_report("BG5FNA", "PM01", "2026-02-16T08:10:00Z"),
+            station_report("BG5FNA", "PM01", "2026-02-16T07:50:00Z"),
+        ];
+        let rows = collapse_reports(reports.iter(), chrono::Duration::minutes(15));
+        let counts: Vec<usize> = rows.iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, vec![2, 1]);
+    }
+
+    #[test]
+    fn test_sort_rows_by_grid() {
+        use super::super::types::SatelliteDataBlock;
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![
+                station_report("A1", "pm01", "2026-02-16T08:20:00Z"),
+                station_report("B2", "FN31", "2026-02-16T08:15:00Z"),
+                station_report("C3", "JN58", "2026-02-16T08:10:00Z"),
+            ],
+        });
+
+        let config = RenderConfig {
+            sort_by_grid: true,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        let rows = renderer.report_rows(&sat, &Utc::now());
+        let callsigns: Vec<&str> = renderer
+            .shown_rows(rows, 0, MAX_REPORTS_PER_SATELLITE)
+            .iter()
+            .map(|(report, _)| report.callsign.as_str())
+            .collect();
+        assert_eq!(callsigns, ["B2", "C3", "A1"]);
+    }
+
+    #[test]
+    fn test_grid_sort_keeps_newest_reports() {
+        use super::super::types::SatelliteDataBlock;
+
+        // Older reports have alphabetically earlier grids
+        let now = Utc::now();
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: now.to_rfc3339(),
+            reports: (0..8)
+                .map(|i| {
+                    let time = (now - chrono::Duration::hours(i)).to_rfc3339();
+                    station_report(&format!("N{}CALL", i), &format!("{}N31", (b'Z' - i as u8) as char), &time)
+                })
+                .collect(),
+        });
+
+        let config = RenderConfig {
+            sort_by_grid: true,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(std::env::temp_dir(), config);
+        let rows = renderer.report_rows(&sat, &now);
+        let callsigns: Vec<&str> = renderer
+            .shown_rows(rows, 0, MAX_REPORTS_PER_SATELLITE)
+            .iter()
+            .map(|(report, _)| report.callsign.as_str())
+            .collect();
+        assert_eq!(callsigns, ["N4CALL", "N3CALL", "N2CALL", "N1CALL", "N0CALL"]);
+
+        let full = renderer.generate_svg(std::slice::from_ref(&sat), &[], RenderLayout::Full).unwrap();
+        let comparison = renderer
+            .generate_comparison_svg(&[("ao-91", Some(&sat)), ("so-50", None)], &now)
+            .unwrap();
+        for svg in [full, comparison] {
+            assert!(svg.contains("N0CALL"));
+            assert!(!svg.contains("N7CALL"));
+        }
+    }
+
+    #[test]
+    fn test_report_window_lifts_row_cap() {
+        use super::super::types::SatelliteDataBlock;
+
+        // One report per hour for the last 40 hours
+        let now = Utc::now();
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: now.to_rfc3339(),
+            reports: (0..40)
+                .map(|h| {
+                    let time = (now - chrono::Duration::hours(h) - chrono::Duration::minutes(1))
+                        .to_rfc3339();
+                    station_report(&format!("N{}CALL", h), "FN31", &time)
+                })
+                .collect(),
+        });
+        let rows = |svg: &str| svg.matches(r#"class="data-row""#).count();
+
+        let latest = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = latest.generate_svg(&[sat.clone()], &[], RenderLayout::Full).unwrap();
+        assert_eq!(rows(&svg), MAX_REPORTS_PER_SATELLITE);
+
+        // 24 reports fall in the window, all on one page
+        let window = SatelliteRenderer::new(std::env::temp_dir()).with_report_window(24, 1);
+        let svg = window.generate_svg(&[sat.clone()], &[], RenderLayout::Full).unwrap();
+        assert_eq!(rows(&svg), 24);
+        assert!(!svg.contains("page-note"));
+
+        // 40 reports span two pages
+        let first = SatelliteRenderer::new(std::env::temp_dir()).with_report_window(48, 1);
+        let svg = first.generate_svg(&[sat.clone()], &[], RenderLayout::Full).unwrap();
+        assert_eq!(rows(&svg), WINDOW_REPORTS_PER_PAGE);
+        assert!(svg.contains("add --page 2"));
+
+        let second = SatelliteRenderer::new(std::env::temp_dir()).with_report_window(48, 2);
+        let svg = second.generate_svg(&[sat], &[], RenderLayout::Full).unwrap();
+        assert_eq!(rows(&svg), 40 - WINDOW_REPORTS_PER_PAGE);
+        assert!(svg.contains("Page 2/2"));
+    }
+
+    #[test]
+    fn test_new_badge_for_recent_entry() {
+        let mut recent = SatelliteInfo::new("AO-123");
+        recent.first_seen = Utc::now() - chrono::Duration::days(2);
+        let known = SatelliteInfo::new("AO-91");
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = renderer.generate_svg(&[recent.clone()], &[], RenderLayout::Full).unwrap();
+        assert!(svg.contains(r#"class="new-badge""#));
+        let svg = renderer.generate_svg(&[known], &[], RenderLayout::Full).unwrap();
+        assert!(!svg.contains(r#"class="new-badge""#));
+
+        // Past the window, or with the badge disabled
+        let short = RenderConfig { new_badge_days: 1, ..Default::default() };
+        let svg = SatelliteRenderer::with_config(std::env::temp_dir(), short)
+            .generate_svg(&[recent.clone()], &[], RenderLayout::Full)
+            .unwrap();
+        assert!(!svg.contains(r#"class="new-badge""#));
+        let off = RenderConfig { new_badge_days: 0, ..Default::default() };
+        let svg = SatelliteRenderer::with_config(std::env::temp_dir(), off)
+            .generate_svg(&[recent], &[], RenderLayout::Full)
+            .unwrap();
+        assert!(!svg.contains(r#"class="new-badge""#));
+    }
+
+    /// Satellites in search-result order: (name, NORAD id, latest report time, status)
+    fn seeded_results() -> Vec<SatelliteInfo> {
+        use super::super::types::SatelliteDataBlock;
+
+        [
+            ("SO-50", Some("27607"), Some("2026-02-16T06:00:00Z"), "Heard"),
+            ("ao-91", Some("43017"), Some("2026-02-16T09:00:00Z"), "Not Heard"),
+            ("ISS-FM", None, None, ""),
+            ("AO-7", Some("7530"), Some("2026-02-16T08:00:00Z"), "Telemetry Only"),
+        ]
+        .iter()
+        .map(|(name, norad, time, report)| {
+            let mut sat = SatelliteInfo::new(*name);
+            sat.catalog_number = norad.map(str::to_string);
+            if let Some(time) = time {
+                sat.data_blocks.push(SatelliteDataBlock {
+                    time: time.to_string(),
+                    reports: vec![AmsatReport {
+                        reported_time: time.to_string(),
+                        report: report.to_string(),
+                        ..Default::default()
+                    }],
+                });
+            }
+            sat
+        })
+        .collect()
+    }
+
+    #[test]
+    fn test_sort_orders() {
+        let cases = [
+            (SortOrder::Relevance, ["SO-50", "ao-91", "ISS-FM", "AO-7"]),
+            (SortOrder::Alphabetical, ["AO-7", "ao-91", "ISS-FM", "SO-50"]),
+            (SortOrder::Norad, ["AO-7", "SO-50", "ao-91", "ISS-FM"]),
+            (SortOrder::Recency, ["ao-91", "AO-7", "SO-50", "ISS-FM"]),
+            (SortOrder::Severity, ["ao-91", "AO-7", "SO-50", "ISS-FM"]),
+        ];
+
+        for (order, expected) in cases {
+            let mut satellites = seeded_results();
+            order.apply(&mut satellites);
+            let names: Vec<&str> = satellites.iter().map(|s| s.name.as_str()).collect();
+            assert_eq!(names, expected, "{:?}", order);
+        }
+    }
+
+    #[test]
+    fn test_sort_order_sets_block_sequence() {
+        let mut satellites = seeded_results();
+        SortOrder::Alphabetical.apply(&mut satellites);
+
+        let renderer = SatelliteRenderer::new(std::env::temp_dir());
+        let svg = renderer.generate_svg(&satellites, &[], RenderLayout::Full).unwrap();
+        let positions: Vec<usize> = ["AO-7", "ao-91", "ISS-FM", "SO-50"]
+            .iter()
+            .map(|name| svg.find(&format!(">{}<", name)).unwrap())
+            .collect();
+        assert!(positions.is_sorted(), "blocks out of order: {:?}", positions);
+    }
+
     #[tokio::test]
     async fn test_render_empty() {
         let temp_dir = std::env::temp_dir().join("rinko_render_test");
+        // Created at startup in production
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
         let renderer = SatelliteRenderer::new(&temp_dir);
         
-        let result = renderer.render_satellites(&[]).await;
+        let result = renderer.render_satellites(&[], RenderLayout::Full).await;
         assert!(result.is_ok());
 
         // Cleanup
         let _ = tokio::fs::remove_dir_all(&temp_dir).await;
     }
+
+    #[tokio::test]
+    async fn test_render_bytes_match_disk_render() {
+        let temp_dir = std::env::temp_dir().join("rinko_render_bytes_test");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        // Without the footer the image does not depend on the render time
+        let config = RenderConfig {
+            footer_enabled: false,
+            ..Default::default()
+        };
+        let renderer = SatelliteRenderer::with_config(&temp_dir, config);
+        let satellites = vec![SatelliteInfo::new("AO-91")];
+
+        let bytes = renderer
+            .render_satellites_bytes(&satellites, RenderLayout::Full)
+            .await
+            .unwrap();
+        let path = renderer.render_satellites(&satellites, RenderLayout::Full).await.unwrap();
+        let on_disk = tokio::fs::read(&path).await.unwrap();
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert!(tiny_skia::Pixmap::decode_png(&bytes).is_ok());
+        assert_eq!(bytes, on_disk);
+    }
+
+    #[test]
+    fn test_empty_fonts_dir_falls_back_to_embedded_font() {
+        let fonts_dir = std::env::temp_dir().join("rinko_test_empty_fonts");
+        std::fs::create_dir_all(&fonts_dir).unwrap();
+        let fontdb = load_font_database(&fonts_dir);
+        let _ = std::fs::remove_dir_all(&fonts_dir);
+        assert!(!fontdb.is_empty());
+
+        // Only the template's preferred family, which is not installed
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="40"><text x="10" y="30" font-family="Consolas" font-size="20">AO-91</text></svg>"#;
+        let png = SatelliteRenderer::rasterize_svg_with(svg, Arc::new(fontdb)).unwrap();
+        let pixmap = tiny_skia::Pixmap::decode_png(&png).unwrap();
+        assert!(pixmap.pixels().iter().any(|p| p.alpha() > 0));
+    }
+
+    #[test]
+    fn test_highlight_reports_new_since_last_render() {
+        use super::super::types::SatelliteDataBlock;
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![
+                station_report("A1", "PM01", "2026-02-16T08:20:00Z"),
+                station_report("B2", "FN31", "2026-02-16T08:15:00Z"),
+            ],
+        });
+        let renderer = SatelliteRenderer::new(std::env::temp_dir())
+            .with_report_history(Arc::new(ReportHistory::new(24)), "group:1");
+
+        // Nothing to compare the first render against
+        let first = renderer.generate_query_svg(std::slice::from_ref(&sat), RenderLayout::Full).unwrap();
+        assert!(!first.contains(r#"class="new-report""#));
+
+        sat.data_blocks[0].reports.insert(0, station_report("C3", "JN58", "2026-02-16T08:25:00Z"));
+        let second = renderer.generate_query_svg(std::slice::from_ref(&sat), RenderLayout::Full).unwrap();
+        let marked: Vec<&str> = second
+            .split(r#"<g class="data-row">"#)
+            .skip(1)
+            .filter(|row| row.contains(r#"class="new-report""#))
+            .collect();
+        assert_eq!(marked.len(), 1);
+        assert!(marked[0].contains(">C3<"));
+    }
+
+    #[tokio::test]
+    async fn test_cached_images_keep_highlights_per_conversation() {
+        use super::super::types::SatelliteDataBlock;
+
+        let temp_dir = std::env::temp_dir().join("rinko_test_highlight_cache");
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+        tokio::fs::create_dir_all(&temp_dir).await.unwrap();
+        // Two renderers share images through the output directory
+        let history = Arc::new(ReportHistory::new(24));
+        let group_1 = SatelliteRenderer::new(&temp_dir).with_report_history(history.clone(), "group:1");
+        let group_2 = SatelliteRenderer::new(&temp_dir).with_report_history(history, "group:2");
+
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![station_report("A1", "PM01", "2026-02-16T08:20:00Z")],
+        });
+        async fn render(renderer: &SatelliteRenderer, sat: &SatelliteInfo) -> String {
+            let path = renderer.render_satellites(std::slice::from_ref(sat), RenderLayout::Full).await.unwrap();
+            path.file_name().unwrap().to_string_lossy().into_owned()
+        }
+
+        let plain = render(&group_1, &sat).await;
+        sat.data_blocks[0].reports.insert(0, station_report("C3", "JN58", "2026-02-16T08:25:00Z"));
+        let group_1_new = render(&group_1, &sat).await;
+        assert!(group_1_new.contains("_new"), "{}", group_1_new);
+
+        // Group 2 has nothing to compare against, so it gets no highlights
+        let group_2_first = render(&group_2, &sat).await;
+        assert!(!group_2_first.contains("_new"), "{}", group_2_first);
+        assert_eq!(group_2_first, plain);
+
+        // The cached image still counts as group 1's render
+        assert_eq!(render(&group_1, &sat).await, group_2_first);
+        let _ = tokio::fs::remove_dir_all(&temp_dir).await;
+    }
 }
@@ -0,0 +1,150 @@
+//! Reports included in the previous render of each query, per conversation
+//!
+//! Lets a re-rendered image mark the rows that arrived since the last one.
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use super::types::{AmsatReport, SatelliteInfo};
+
+/// Identifies one report across renders
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct ReportKey {
+    callsign: String,
+    grid_square: String,
+    report: String,
+    reported_time: String,
+}
+
+impl ReportKey {
+    pub fn of(report: &AmsatReport) -> Self {
+        Self {
+            callsign: report.callsign.clone(),
+            grid_square: report.grid_square.clone(),
+            report: report.report.clone(),
+            reported_time: report.reported_time.clone(),
+        }
+    }
+}
+
+/// Most queries remembered per conversation; the least recently rendered is dropped
+const MAX_QUERIES_PER_CONVERSATION: usize = 64;
+
+/// Reports of one query's last render
+#[derive(Debug)]
+struct Render {
+    at: DateTime<Utc>,
+    reports: HashSet<ReportKey>,
+}
+
+/// Conversation -> render key -> reports of the satellites in its last render
+///
+/// Each group or channel has its own history, so a query in one does not
+/// reset the highlights another sees. Renders older than `max_age` are pruned
+/// and each conversation keeps at most `MAX_QUERIES_PER_CONVERSATION` queries.
+#[derive(Debug)]
+pub struct ReportHistory {
+    max_age: Duration,
+    previous: Mutex<HashMap<String, HashMap<String, Render>>>,
+}
+
+impl ReportHistory {
+    /// History forgetting renders after `max_age_hours` (the report retention)
+    pub fn new(max_age_hours: u64) -> Self {
+        Self {
+            max_age: Duration::hours(max_age_hours as i64),
+            previous: Mutex::default(),
+        }
+    }
+
+    /// Remember the reports of a new render and return those of the one before
+    pub fn replace(
+        &self,
+        conversation: &str,
+        key: &str,
+        satellites: &[SatelliteInfo],
+    ) -> Option<HashSet<ReportKey>> {
+        self.replace_at(conversation, key, satellites, Utc::now())
+    }
+
+    fn replace_at(
+        &self,
+        conversation: &str,
+        key: &str,
+        satellites: &[SatelliteInfo],
+        now: DateTime<Utc>,
+    ) -> Option<HashSet<ReportKey>> {
+        let reports = satellites
+            .iter()
+            .flat_map(|sat| sat.data_blocks.iter().flat_map(|b| b.reports.iter()))
+            .map(ReportKey::of)
+            .collect();
+
+        let mut previous = self.previous.lock().unwrap();
+        let cutoff = now - self.max_age;
+        previous.retain(|_, renders| {
+            renders.retain(|_, render| render.at >= cutoff);
+            !renders.is_empty()
+        });
+
+        let renders = previous.entry(conversation.to_string()).or_default();
+        let last = renders.insert(key.to_string(), Render { at: now, reports });
+        if renders.len() > MAX_QUERIES_PER_CONVERSATION
+            && let Some(oldest) = renders
+                .iter()
+                .min_by_key(|(_, render)| render.at)
+                .map(|(key, _)| key.clone())
+        {
+            renders.remove(&oldest);
+        }
+        last.map(|render| render.reports)
+    }
+
+    /// Queries remembered for `conversation`
+    #[cfg(test)]
+    fn len(&self, conversation: &str) -> usize {
+        self.previous.lock().unwrap().get(conversation).map_or(0, HashMap::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(minutes: i64) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-02-16T08:00:00Z").unwrap().with_timezone(&Utc)
+            + Duration::minutes(minutes)
+    }
+
+    #[test]
+    fn test_history_kept_per_conversation() {
+        let history = ReportHistory::new(24);
+        let sats = [SatelliteInfo::new("AO-91")];
+
+        assert!(history.replace_at("group-1", "ao-91", &sats, at(0)).is_none());
+        // Another group's first render is not compared with group-1's
+        assert!(history.replace_at("group-2", "ao-91", &sats, at(1)).is_none());
+        assert!(history.replace_at("group-1", "ao-91", &sats, at(2)).is_some());
+    }
+
+    #[test]
+    fn test_history_bounded() {
+        let history = ReportHistory::new(2);
+        let sats = [SatelliteInfo::new("AO-91")];
+
+        let count = MAX_QUERIES_PER_CONVERSATION + 5;
+        for i in 0..count {
+            history.replace_at("group-1", &format!("query-{}", i), &sats, at(i as i64));
+        }
+        assert_eq!(history.len("group-1"), MAX_QUERIES_PER_CONVERSATION);
+        // The least recently rendered queries were dropped
+        assert!(history.replace_at("group-1", "query-0", &sats, at(count as i64)).is_none());
+        assert!(history.replace_at("group-1", "query-10", &sats, at(count as i64)).is_some());
+
+        // Renders older than max_age are forgotten by the next replace
+        history.replace_at("group-2", "ao-91", &sats, at(300));
+        assert_eq!(history.len("group-1"), 0);
+        assert!(history.replace_at("group-2", "ao-91", &sats, at(301)).is_some());
+    }
+}
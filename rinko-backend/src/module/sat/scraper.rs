@@ -1,10 +1,17 @@
-///! Web scraper for fetching satellite list from AMSAT status page
+//! Web scraper for fetching satellite list from AMSAT status page
 use anyhow::{Context, Result};
-use reqwest;
+use crate::http_client::shared_client;
 use scraper::{Html, Selector};
 
 const AMSAT_STATUS_URL: &str = "https://www.amsat.org/status/";
 
+/// The satellite dropdown on the status page
+const SATELLITE_SELECT_SELECTOR: &str = r#"select[name="SatName"]"#;
+/// Entries of the satellite dropdown
+const SATELLITE_OPTION_SELECTOR: &str = r#"select[name="SatName"] > option"#;
+/// Characters of received HTML logged when the layout check fails
+const HTML_SNIPPET_CHARS: usize = 500;
+
 /// Fetch list of satellite names from AMSAT status page
 /// 
 /// Scrapes the satellite dropdown menu from the AMSAT status page
@@ -16,7 +23,9 @@ pub async fn fetch_satellite_names() -> Result<Vec<String>> {
     tracing::debug!("Fetching satellite list from {}", AMSAT_STATUS_URL);
     
     // Fetch the page
-    let response = reqwest::get(AMSAT_STATUS_URL)
+    let response = shared_client()
+        .get(AMSAT_STATUS_URL)
+        .send()
         .await
         .context("Failed to fetch AMSAT status page")?;
     
@@ -32,14 +41,44 @@ pub async fn fetch_satellite_names() -> Result<Vec<String>> {
         .await
         .context("Failed to read AMSAT status page body")?;
     
-    // Parse HTML
-    let document = Html::parse_document(&html_body);
+    let satellite_names = parse_satellite_names(&html_body)?;
     
-    // Select the satellite dropdown options
-    let selector = Selector::parse(r#"select[name="SatName"] > option"#)
-        .map_err(|e| anyhow::anyhow!("Invalid CSS selector: {:?}", e))?;
+    tracing::info!(
+        "Successfully fetched {} satellite names from AMSAT",
+        satellite_names.len()
+    );
+    
+    if satellite_names.is_empty() {
+        tracing::warn!("No satellites found in AMSAT status page");
+    }
+    
+    Ok(satellite_names)
+}
+
+/// Extract satellite names from the AMSAT status page HTML
+///
+/// Fails with an error naming the missing selector if the page no longer
+/// has the expected dropdown, instead of silently returning nothing.
+pub fn parse_satellite_names(html_body: &str) -> Result<Vec<String>> {
+    let document = Html::parse_document(html_body);
+    
+    // Check the page structure before extracting anything
+    for selector_str in [SATELLITE_SELECT_SELECTOR, SATELLITE_OPTION_SELECTOR] {
+        if document.select(&parse_selector(selector_str)?).next().is_none() {
+            tracing::warn!(
+                "AMSAT status page is missing `{}`; received HTML starts with: {}",
+                selector_str,
+                html_snippet(html_body)
+            );
+            return Err(anyhow::anyhow!(
+                "AMSAT status page layout changed: selector `{}` matched nothing",
+                selector_str
+            ));
+        }
+    }
     
     // Extract satellite names
+    let selector = parse_selector(SATELLITE_OPTION_SELECTOR)?;
     let mut satellite_names = Vec::new();
     for element in document.select(&selector) {
         if let Some(value) = element.value().attr("value") {
@@ -51,18 +90,23 @@ pub async fn fetch_satellite_names() -> Result<Vec<String>> {
         }
     }
     
-    tracing::info!(
-        "Successfully fetched {} satellite names from AMSAT",
-        satellite_names.len()
-    );
-    
-    if satellite_names.is_empty() {
-        tracing::warn!("No satellites found in AMSAT status page");
-    }
-    
     Ok(satellite_names)
 }
 
+fn parse_selector(selector: &str) -> Result<Selector> {
+    Selector::parse(selector).map_err(|e| anyhow::anyhow!("Invalid CSS selector: {:?}", e))
+}
+
+/// Start of the received HTML with whitespace collapsed, for diagnostics
+fn html_snippet(html: &str) -> String {
+    let collapsed = html.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut snippet: String = collapsed.chars().take(HTML_SNIPPET_CHARS).collect();
+    if collapsed.chars().count() > HTML_SNIPPET_CHARS {
+        snippet.push_str("...");
+    }
+    snippet
+}
+
 /// Get a hardcoded list of known AMSAT satellites as fallback
 /// 
 /// This list is based on common amateur radio satellites and serves
@@ -115,6 +159,42 @@ mod tests {
         assert!(satellites.contains(&"ISS-FM".to_string()));
     }
 
+    const STATUS_PAGE: &str = r#"<html><body><form>
+<select name="SatName">
+  <option value="">Select Satellite</option>
+  <option value="AO-91">AO-91</option>
+  <option value=" ISS-FM ">ISS-FM</option>
+</select>
+</form></body></html>"#;
+
+    #[test]
+    fn test_parse_satellite_names() {
+        let names = parse_satellite_names(STATUS_PAGE).unwrap();
+        assert_eq!(names, vec!["AO-91", "ISS-FM"]);
+    }
+
+    #[test]
+    fn test_layout_change_names_missing_selector() {
+        // Upstream renamed the dropdown
+        let altered = STATUS_PAGE.replace(r#"name="SatName""#, r#"name="satellite""#);
+        let err = parse_satellite_names(&altered).unwrap_err().to_string();
+        assert!(err.contains("layout changed"), "{}", err);
+        assert!(err.contains(SATELLITE_SELECT_SELECTOR), "{}", err);
+
+        // Dropdown kept but options moved elsewhere
+        let altered = r#"<select name="SatName"></select><ul><li>AO-91</li></ul>"#;
+        let err = parse_satellite_names(altered).unwrap_err().to_string();
+        assert!(err.contains(SATELLITE_OPTION_SELECTOR), "{}", err);
+    }
+
+    #[test]
+    fn test_html_snippet_is_bounded() {
+        let html = format!("<html>\n  {}</html>", "x".repeat(2000));
+        let snippet = html_snippet(&html);
+        assert!(snippet.starts_with("<html> xxx"));
+        assert_eq!(snippet.chars().count(), HTML_SNIPPET_CHARS + 3);
+    }
+
     #[tokio::test]
     #[ignore] // Requires network connection
     async fn test_fetch_satellite_names() {
@@ -1,17 +1,93 @@
-///! Search engine for satellite name matching
+//! Search engine for satellite name matching
 use super::types::SatelliteList;
 use strsim::jaro_winkler;
 
 /// Default similarity threshold for fuzzy matching
 pub const DEFAULT_THRESHOLD: f64 = 0.85;
 
+/// Looser similarity threshold used for "did you mean" suggestions
+pub const SUGGESTION_THRESHOLD: f64 = 0.7;
+
+/// Mode/transponder suffixes that may follow a base name after '-' or ' '
+/// (e.g. "ISS-SSTV", "ISS FM"). Numeric suffixes like "AO-91" are never modes.
+const MODE_SUFFIXES: &[&str] = &[
+    "FM", "SSTV", "DATA", "DATV", "APRS", "SSB", "CW", "LINEAR", "DIGI", "PKT", "TLM", "BCN",
+];
+
+/// AMSAT satellite name split into its parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AmsatName {
+    /// Base designation, e.g. "FO-118" or "QMR-KWT-2"
+    pub base: String,
+    /// Mode/transponder, e.g. "H/u" from "FO-118[H/u]" or "FM" from "ISS FM"
+    pub mode: Option<String>,
+    /// Underscore-separated sub-designation, e.g. "RS95s" from "QMR-KWT-2_(RS95s)"
+    pub designation: Option<String>,
+}
+
+/// Parse an AMSAT satellite name into base, mode and sub-designation
+///
+/// Handles trailing `[mode]`, `(mode)`, `_(designation)` groups in any
+/// combination, plus a known mode word after the last '-' or ' '.
+pub fn parse_amsat_name(name: &str) -> AmsatName {
+    let mut rest = name.trim();
+    let mut mode: Option<String> = None;
+    let mut designation: Option<String> = None;
+
+    // Peel trailing bracket groups from the end
+    loop {
+        let (open, close) = if rest.ends_with(']') {
+            ('[', ']')
+        } else if rest.ends_with(')') {
+            ('(', ')')
+        } else {
+            break;
+        };
+        let Some(start) = rest.rfind(open) else {
+            break;
+        };
+        let inner = rest[start + 1..rest.len() - 1].trim().to_string();
+        let before = &rest[..start];
+
+        if let Some(stripped) = before.strip_suffix('_') {
+            designation.get_or_insert(inner);
+            rest = stripped;
+        } else if close == ']' || mode.is_none() {
+            mode.get_or_insert(inner);
+            rest = before;
+        } else {
+            designation.get_or_insert(inner);
+            rest = before;
+        }
+        rest = rest.trim_end();
+    }
+
+    // Plain "BASE-MODE" / "BASE MODE" suffix (only for known mode words)
+    if mode.is_none()
+        && let Some(pos) = rest.rfind(['-', ' '])
+    {
+        let suffix = &rest[pos + 1..];
+        if pos > 0 && MODE_SUFFIXES.iter().any(|m| m.eq_ignore_ascii_case(suffix)) {
+            mode = Some(suffix.to_string());
+            rest = rest[..pos].trim_end();
+        }
+    }
+
+    AmsatName {
+        base: rest.to_string(),
+        mode,
+        designation,
+    }
+}
+
 /// Search for satellites matching the query
 /// 
 /// # Search Priority
 /// 1. Exact match on official name
 /// 2. Exact match on aliases
 /// 3. Catalog number match (if available)
-/// 4. Fuzzy match (Jaro-Winkler similarity >= threshold)
+/// 4. Base designation match (e.g. "FO-118" finds every "FO-118[...]" mode)
+/// 5. Fuzzy match (Jaro-Winkler similarity >= threshold)
 /// 
 /// # Arguments
 /// * `query` - Search query string
@@ -30,6 +106,12 @@ pub fn search_satellites(
     if !hard_matches.is_empty() {
         return hard_matches;
     }
+
+    // Then try matching the base designation
+    let base_matches = base_match(query, satellite_list);
+    if !base_matches.is_empty() {
+        return base_matches;
+    }
     
     // Then try fuzzy match
     let fuzzy_matches = fuzzy_match(query, satellite_list, threshold);
@@ -55,16 +137,28 @@ fn hard_match(query: &str, satellite_list: &SatelliteList) -> Vec<String> {
         }
         
         // Check catalog number
-        if let Some(ref catalog_num) = sat.catalog_number {
-            if normalize_string(catalog_num) == normalized_query {
-                results.push(sat.official_name.clone());
-            }
+        if let Some(ref catalog_num) = sat.catalog_number
+            && normalize_string(catalog_num) == normalized_query
+        {
+            results.push(sat.official_name.clone());
         }
     }
     
     results
 }
 
+/// Base match: query equals the base designation of an official name
+fn base_match(query: &str, satellite_list: &SatelliteList) -> Vec<String> {
+    let normalized_query = normalize_string(query);
+
+    satellite_list
+        .satellites
+        .iter()
+        .filter(|sat| normalize_string(&parse_amsat_name(&sat.official_name).base) == normalized_query)
+        .map(|sat| sat.official_name.clone())
+        .collect()
+}
+
 /// Fuzzy match using Jaro-Winkler similarity
 fn fuzzy_match(
     query: &str,
@@ -92,12 +186,30 @@ fn fuzzy_match(
         }
     }
     
-    // Sort by score descending  
-    matches.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    matches.sort_by(by_score_then_name);
     
     matches
 }
 
+/// Order matches by score descending, then name, so equal scores come out
+/// the same on every run; NaN scores sort last
+fn by_score_then_name(a: &(f64, String), b: &(f64, String)) -> std::cmp::Ordering {
+    let score = |s: f64| if s.is_nan() { f64::NEG_INFINITY } else { s };
+    score(b.0).total_cmp(&score(a.0)).then_with(|| a.1.cmp(&b.1))
+}
+
+/// Suggest similar satellite names for a query that matched nothing
+///
+/// Uses a looser fuzzy threshold than normal search and returns at most
+/// `limit` names, best match first.
+pub fn suggest_similar(query: &str, satellite_list: &SatelliteList, limit: usize) -> Vec<String> {
+    fuzzy_match(query, satellite_list, SUGGESTION_THRESHOLD)
+        .into_iter()
+        .take(limit)
+        .map(|(_, name)| name)
+        .collect()
+}
+
 /// Normalize string for matching (lowercase, remove punctuation and whitespace)
 pub fn normalize_string(s: &str) -> String {
     s.trim()
@@ -136,8 +248,8 @@ pub fn check_special_keywords(query: &str) -> Option<Vec<String>> {
 
 /// Search satellites with special keywords support
 /// 
-/// This function first checks for special keywords, then falls back
-/// to normal search if no special keyword is found.
+/// This function first checks for special keywords, then a trailing mode
+/// qualifier ("iss sstv"), and falls back to normal search otherwise.
 pub fn search_with_keywords(
     query: &str,
     satellite_list: &SatelliteList,
@@ -148,10 +260,64 @@ pub fn search_with_keywords(
         return satellites;
     }
     
+    // "<satellite> <mode>" picks one transponder (e.g. "iss sstv")
+    if let Some((base, mode)) = split_mode_qualifier(query) {
+        let matches = search_with_mode(base, mode, satellite_list, threshold);
+        if !matches.is_empty() {
+            return matches;
+        }
+    }
+
     // Normal search
     search_satellites(query, satellite_list, threshold)
 }
 
+/// Split a trailing mode word off a query ("iss sstv" -> ("iss", "sstv"))
+fn split_mode_qualifier(query: &str) -> Option<(&str, &str)> {
+    let (base, mode) = query.trim().rsplit_once(char::is_whitespace)?;
+    let base = base.trim_end();
+    (!base.is_empty() && MODE_SUFFIXES.iter().any(|m| m.eq_ignore_ascii_case(mode)))
+        .then_some((base, mode))
+}
+
+/// Search `base`, then keep only the satellites' entries for `mode`
+///
+/// Every entry sharing a matched satellite's base designation is considered,
+/// so "iss" reaches ISS-SSTV even if only ISS-FM carries the "ISS" alias.
+/// Falls back to all of those entries when none offers the mode.
+fn search_with_mode(
+    base: &str,
+    mode: &str,
+    satellite_list: &SatelliteList,
+    threshold: f64,
+) -> Vec<String> {
+    let bases: Vec<String> = search_satellites(base, satellite_list, threshold)
+        .iter()
+        .map(|name| normalize_string(&parse_amsat_name(name).base))
+        .collect();
+    let family: Vec<&String> = satellite_list
+        .satellites
+        .iter()
+        .map(|sat| &sat.official_name)
+        .filter(|name| bases.contains(&normalize_string(&parse_amsat_name(name).base)))
+        .collect();
+
+    let with_mode: Vec<String> = family
+        .iter()
+        .filter(|name| {
+            parse_amsat_name(name)
+                .mode
+                .is_some_and(|m| m.eq_ignore_ascii_case(mode))
+        })
+        .map(|name| name.to_string())
+        .collect();
+    if with_mode.is_empty() {
+        family.into_iter().cloned().collect()
+    } else {
+        with_mode
+    }
+}
+
 /// Search multiple queries (separated by '/')
 /// 
 /// # Arguments
@@ -265,6 +431,87 @@ mod tests {
         assert!(results.contains(&"ISS-FM".to_string()));
     }
 
+    fn create_iss_list() -> SatelliteList {
+        let mut list = create_test_list();
+        list.satellites.push(SatelliteEntry::new("ISS-SSTV"));
+        list.satellites.push(SatelliteEntry::new("ISS-DATA"));
+        list
+    }
+
+    #[test]
+    fn test_mode_qualifier_fm() {
+        let list = create_iss_list();
+        assert_eq!(search_with_keywords("iss fm", &list, 0.85), vec!["ISS-FM".to_string()]);
+    }
+
+    #[test]
+    fn test_mode_qualifier_sstv() {
+        let list = create_iss_list();
+        assert_eq!(search_with_keywords("iss sstv", &list, 0.85), vec!["ISS-SSTV".to_string()]);
+        assert_eq!(search_with_keywords("ISS SSTV", &list, 0.85), vec!["ISS-SSTV".to_string()]);
+    }
+
+    #[test]
+    fn test_unknown_mode_qualifier_returns_all_entries() {
+        let list = create_iss_list();
+        assert_eq!(
+            search_with_keywords("iss linear", &list, 0.85),
+            vec!["ISS-FM".to_string(), "ISS-SSTV".to_string(), "ISS-DATA".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_parse_amsat_name() {
+        let cases: &[(&str, &str, Option<&str>, Option<&str>)] = &[
+            ("FO-118[H/u]", "FO-118", Some("H/u"), None),
+            ("QMR-KWT-2_(RS95s)", "QMR-KWT-2", None, Some("RS95s")),
+            ("ISS FM", "ISS", Some("FM"), None),
+            ("ISS-SSTV", "ISS", Some("SSTV"), None),
+            ("AO-7", "AO-7", None, None),
+            ("AO-91", "AO-91", None, None),
+            ("PO-101[FM]", "PO-101", Some("FM"), None),
+        ];
+
+        for (name, base, mode, designation) in cases {
+            let parsed = parse_amsat_name(name);
+            assert_eq!(parsed.base, *base, "base of {}", name);
+            assert_eq!(parsed.mode.as_deref(), *mode, "mode of {}", name);
+            assert_eq!(parsed.designation.as_deref(), *designation, "designation of {}", name);
+        }
+    }
+
+    #[test]
+    fn test_base_designation_match() {
+        let mut list = create_test_list();
+        list.satellites.push(SatelliteEntry::new("FO-118[H/u]"));
+        list.satellites.push(SatelliteEntry::new("FO-118[V/u]"));
+
+        let results = search_satellites("FO-118", &list, 0.85);
+        assert_eq!(results, vec!["FO-118[H/u]".to_string(), "FO-118[V/u]".to_string()]);
+    }
+
+    #[test]
+    fn test_suggest_similar() {
+        let list = create_test_list();
+        let suggestions = suggest_similar("AO-19", &list, 3);
+        assert!(suggestions.contains(&"AO-91".to_string()));
+        assert!(suggest_similar("zzzzzz", &list, 3).is_empty());
+    }
+
+    #[test]
+    fn test_equal_scores_sorted_by_name() {
+        let mut matches = [
+            (0.9, "SO-50".to_string()),
+            (f64::NAN, "AO-7".to_string()),
+            (0.9, "AO-91".to_string()),
+            (0.95, "ISS-FM".to_string()),
+            (0.9, "FO-29".to_string()),
+        ];
+        matches.sort_by(by_score_then_name);
+        let names: Vec<&str> = matches.iter().map(|(_, name)| name.as_str()).collect();
+        assert_eq!(names, vec!["ISS-FM", "AO-91", "FO-29", "SO-50", "AO-7"]);
+    }
+
     #[test]
     fn test_normalize_string() {
         assert_eq!(normalize_string("AO-91"), "ao91");
@@ -0,0 +1,184 @@
+//! Pluggable satellite status data sources
+//!
+//! `SatelliteManager` polls every configured source on each update and
+//! merges their reports, tagging each report with the source it came from.
+//! AMSAT is the default built-in source; [`FixtureSource`] replays saved
+//! AMSAT responses for offline demos and CI.
+use super::types::{AmsatReport, ReportSource};
+use crate::config::SatelliteConfig;
+use super::api_client::{self, RequestBudget};
+use super::scraper;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, DurationRound, SecondsFormat, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const API_REQUEST_DELAY_MS: u64 = 200; // Delay between API requests
+
+/// A provider of satellite names and status reports
+#[async_trait]
+pub trait StatusSource: Send + Sync {
+    /// Tag applied to every report from this source
+    fn report_source(&self) -> ReportSource;
+
+    /// Names of the satellites this source knows about
+    async fn fetch_satellite_list(&self) -> Result<Vec<String>>;
+
+    /// Reports from the last `hours` hours for each requested satellite
+    ///
+    /// Satellites missing from the result are treated as a failed fetch.
+    /// Network sources draw every upstream request from `budget`.
+    async fn fetch_reports(
+        &self,
+        sat_names: &[String],
+        hours: u64,
+        budget: &RequestBudget,
+    ) -> HashMap<String, Result<Vec<AmsatReport>>>;
+}
+
+/// The AMSAT satellite status page and API
+pub struct AmsatSource {
+    api_url: String,
+}
+
+impl AmsatSource {
+    /// Fetch reports from the given AMSAT API endpoint
+    pub fn new(api_url: impl Into<String>) -> Self {
+        Self { api_url: api_url.into() }
+    }
+}
+
+impl Default for AmsatSource {
+    fn default() -> Self {
+        Self::new(api_client::DEFAULT_AMSAT_API_URL)
+    }
+}
+
+#[async_trait]
+impl StatusSource for AmsatSource {
+    fn report_source(&self) -> ReportSource {
+        ReportSource::Amsat
+    }
+
+    async fn fetch_satellite_list(&self) -> Result<Vec<String>> {
+        Ok(scraper::fetch_satellite_names_with_fallback().await)
+    }
+
+    async fn fetch_reports(
+        &self,
+        sat_names: &[String],
+        hours: u64,
+        budget: &RequestBudget,
+    ) -> HashMap<String, Result<Vec<AmsatReport>>> {
+        api_client::batch_fetch_satellites(&self.api_url, sat_names, hours, API_REQUEST_DELAY_MS, budget)
+            .await
+    }
+}
+
+/// Saved AMSAT status page in a fixture directory
+const FIXTURE_STATUS_PAGE: &str = "satellites.html";
+/// Saved AMSAT API responses in a fixture directory, keyed by satellite name
+const FIXTURE_REPORTS: &str = "reports.json";
+
+/// AMSAT data read from local files instead of the network
+///
+/// The directory holds the status page (`satellites.html`) and a JSON object
+/// mapping satellite names to API responses (`reports.json`). Report times
+/// are moved forward by whole hours so the newest lands in the last complete
+/// hour, keeping the fixture recent but never in the future, while repeated
+/// updates in the same hour see identical reports.
+pub struct FixtureSource {
+    dir: PathBuf,
+}
+
+impl FixtureSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    async fn read(&self, file: &str) -> Result<String> {
+        let path = self.dir.join(file);
+        tokio::fs::read_to_string(&path)
+            .await
+            .with_context(|| format!("Failed to read fixture {}", path.display()))
+    }
+
+    async fn load_reports(&self) -> Result<HashMap<String, Vec<AmsatReport>>> {
+        let json = self.read(FIXTURE_REPORTS).await?;
+        serde_json::from_str(&json)
+            .with_context(|| format!("Invalid fixture {}", self.dir.join(FIXTURE_REPORTS).display()))
+    }
+}
+
+/// Shift every parseable report time by the offset that moves the newest
+/// report into the hour before the one containing `now`
+fn rebase_report_times(reports: &mut HashMap<String, Vec<AmsatReport>>, now: DateTime<Utc>) {
+    let parse = AmsatReport::reported_at;
+    let Some(newest) = reports.values().flatten().filter_map(parse).max() else {
+        return;
+    };
+    let (Ok(newest_hour), Ok(current_hour)) = (
+        newest.duration_trunc(Duration::hours(1)),
+        now.duration_trunc(Duration::hours(1)),
+    ) else {
+        return;
+    };
+
+    let offset = current_hour - Duration::hours(1) - newest_hour;
+    for report in reports.values_mut().flatten() {
+        if let Some(time) = parse(report) {
+            report.reported_time = (time + offset).to_rfc3339_opts(SecondsFormat::Secs, true);
+        }
+    }
+}
+
+#[async_trait]
+impl StatusSource for FixtureSource {
+    fn report_source(&self) -> ReportSource {
+        ReportSource::Amsat
+    }
+
+    async fn fetch_satellite_list(&self) -> Result<Vec<String>> {
+        scraper::parse_satellite_names(&self.read(FIXTURE_STATUS_PAGE).await?)
+    }
+
+    async fn fetch_reports(
+        &self,
+        sat_names: &[String],
+        _hours: u64,
+        _budget: &RequestBudget,
+    ) -> HashMap<String, Result<Vec<AmsatReport>>> {
+        let mut fixture = match self.load_reports().await {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                let message = format!("{:#}", e);
+                return sat_names
+                    .iter()
+                    .map(|name| (name.clone(), Err(anyhow::anyhow!(message.clone()))))
+                    .collect();
+            }
+        };
+        rebase_report_times(&mut fixture, Utc::now());
+
+        // The whole fixture is returned regardless of `hours`, so a render
+        // does not depend on where in the hour the update ran
+        sat_names
+            .iter()
+            .map(|name| (name.clone(), Ok(fixture.remove(name).unwrap_or_default())))
+            .collect()
+    }
+}
+
+/// The sources used when none are configured explicitly
+///
+/// A configured fixture directory replaces the AMSAT network source.
+pub fn default_sources(config: &SatelliteConfig) -> Vec<Box<dyn StatusSource>> {
+    match &config.fixture_dir {
+        Some(dir) => {
+            tracing::info!("Reading AMSAT data from fixtures in {}", dir);
+            vec![Box::new(FixtureSource::new(dir))]
+        }
+        None => vec![Box::new(AmsatSource::new(config.amsat_api_url.clone()))],
+    }
+}
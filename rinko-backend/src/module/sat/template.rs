@@ -0,0 +1,109 @@
+//! SVG templates read from disk at runtime
+//!
+//! Templates are read on first use and cached by path. With
+//! `watch_templates` enabled, a file watcher re-reads cached templates
+//! whenever an `.svg` file in their directory changes, so template edits
+//! show up in the next render without a restart.
+use anyhow::{Context, Result};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// Default satellite table template, relative to the working directory
+pub const DEFAULT_TEMPLATE_PATH: &str = "resources/sat_template.svg";
+
+/// path -> template content
+static TEMPLATES: LazyLock<RwLock<HashMap<PathBuf, Arc<str>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Get a template, reading it from disk on first use
+pub fn load_template(path: &Path) -> Result<Arc<str>> {
+    if let Some(content) = TEMPLATES.read().unwrap().get(path) {
+        return Ok(content.clone());
+    }
+
+    let content = read_template(path)?;
+    TEMPLATES
+        .write()
+        .unwrap()
+        .insert(path.to_path_buf(), content.clone());
+    Ok(content)
+}
+
+fn read_template(path: &Path) -> Result<Arc<str>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read SVG template {:?}", path))?;
+    Ok(content.into())
+}
+
+/// Re-read every cached template, keeping the old content on failure
+fn reload_templates() {
+    let paths: Vec<PathBuf> = TEMPLATES.read().unwrap().keys().cloned().collect();
+    for path in paths {
+        match read_template(&path) {
+            Ok(content) => {
+                TEMPLATES.write().unwrap().insert(path.clone(), content);
+                tracing::info!("Reloaded SVG template {:?}", path);
+            }
+            Err(e) => tracing::warn!("Keeping previous template: {:#}", e),
+        }
+    }
+}
+
+/// Watch the directory containing `template_path` and reload on changes
+///
+/// The directory is watched rather than the file because editors often
+/// save by replacing the file. Watching stops when the returned watcher
+/// is dropped.
+pub fn watch_templates(template_path: &Path) -> Result<RecommendedWatcher> {
+    let dir = template_path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    let mut watcher = notify::recommended_watcher(|event: notify::Result<notify::Event>| {
+        match event {
+            Ok(event) => {
+                let is_change = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                );
+                let touches_svg = event
+                    .paths
+                    .iter()
+                    .any(|p| p.extension().is_some_and(|ext| ext == "svg"));
+                if is_change && touches_svg {
+                    reload_templates();
+                }
+            }
+            Err(e) => tracing::warn!("Template watcher error: {}", e),
+        }
+    })
+    .context("Failed to create template watcher")?;
+
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch template directory {:?}", dir))?;
+    tracing::info!("Watching {:?} for SVG template changes", dir);
+
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_template_has_placeholders() {
+        let template = load_template(Path::new(DEFAULT_TEMPLATE_PATH)).unwrap();
+        for placeholder in ["{{SVG_WIDTH}}", "{{SVG_HEIGHT}}", "{{CONTENT}}", "{{FOOTER}}"] {
+            assert!(template.contains(placeholder), "missing {}", placeholder);
+        }
+    }
+
+    #[test]
+    fn test_missing_template_is_an_error() {
+        assert!(load_template(Path::new("resources/does_not_exist.svg")).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Shared fixtures for tests that need a `SatelliteManager` or a fake source
+use super::cache;
+use super::{
+    AmsatReport, ReportSource, RequestBudget, SatelliteEntry, SatelliteInfo, SatelliteList,
+    SatelliteManager, StatusSource,
+};
+use crate::config::SatelliteConfig;
+use anyhow::Result;
+use chrono::{SecondsFormat, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// An empty directory under the system temp dir, cleared of earlier runs
+pub async fn fresh_dir(dir_name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(dir_name);
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    tokio::fs::create_dir_all(&dir).await.unwrap();
+    dir
+}
+
+/// Initialized manager tracking `names`, each cached without reports
+pub async fn manager_with_names(dir_name: &str, names: &[&str]) -> Arc<SatelliteManager> {
+    manager_with(
+        dir_name,
+        names.iter().map(|name| SatelliteEntry::new(*name)).collect(),
+        names.iter().map(|name| SatelliteInfo::new(*name)).collect(),
+    )
+    .await
+}
+
+/// Initialized manager with the given satellite list and cached data
+pub async fn manager_with(
+    dir_name: &str,
+    satellites: Vec<SatelliteEntry>,
+    cached: Vec<SatelliteInfo>,
+) -> Arc<SatelliteManager> {
+    let dir = fresh_dir(dir_name).await;
+    cache::save_satellite_list(&dir, &SatelliteList { satellites }).await.unwrap();
+    cache::save_satellite_cache(&dir, &cached).await.unwrap();
+
+    let manager = SatelliteManager::new(&dir, 10).unwrap();
+    manager.initialize().await.unwrap();
+    manager
+}
+
+/// Initialized manager in a fresh directory, fetching from `sources`
+pub async fn manager_with_sources(
+    dir_name: &str,
+    config: SatelliteConfig,
+    sources: Vec<Box<dyn StatusSource>>,
+) -> Arc<SatelliteManager> {
+    let dir = fresh_dir(dir_name).await;
+    let manager = SatelliteManager::with_sources(&dir, 10, config, sources).unwrap();
+    manager.initialize().await.unwrap();
+    manager
+}
+
+/// Source listing fixed satellites, recording fetches and optionally failing
+pub struct FakeSource {
+    tag: ReportSource,
+    names: Vec<String>,
+    /// One "Heard" report per fetched satellite from this callsign, if set
+    callsign: Option<&'static str>,
+    /// Every satellite name fetched, in order
+    pub fetched: Arc<Mutex<Vec<String>>>,
+    /// Report fetches fail with "HTTP error 503" while set
+    pub failing: Arc<AtomicBool>,
+}
+
+impl FakeSource {
+    /// AMSAT-tagged source listing `names` and returning no reports
+    pub fn new(names: &[&str]) -> Self {
+        Self {
+            tag: ReportSource::Amsat,
+            names: names.iter().map(|name| name.to_string()).collect(),
+            callsign: None,
+            fetched: Arc::default(),
+            failing: Arc::default(),
+        }
+    }
+
+    /// Return one current report per satellite, tagged `tag`, from `callsign`
+    pub fn with_reports(mut self, tag: ReportSource, callsign: &'static str) -> Self {
+        self.tag = tag;
+        self.callsign = Some(callsign);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl StatusSource for FakeSource {
+    fn report_source(&self) -> ReportSource {
+        self.tag
+    }
+
+    async fn fetch_satellite_list(&self) -> Result<Vec<String>> {
+        Ok(self.names.clone())
+    }
+
+    async fn fetch_reports(
+        &self,
+        sat_names: &[String],
+        _hours: u64,
+        _budget: &RequestBudget,
+    ) -> HashMap<String, Result<Vec<AmsatReport>>> {
+        self.fetched.lock().unwrap().extend(sat_names.iter().cloned());
+        let failing = self.failing.load(Ordering::SeqCst);
+        let reported_time = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        sat_names
+            .iter()
+            .map(|name| {
+                if failing {
+                    return (name.clone(), Err(anyhow::anyhow!("HTTP error 503")));
+                }
+                let reports = self
+                    .callsign
+                    .map(|callsign| AmsatReport {
+                        name: name.clone(),
+                        callsign: callsign.to_string(),
+                        report: "Heard".to_string(),
+                        reported_time: reported_time.clone(),
+                        grid_square: "JN58".to_string(),
+                        ..Default::default()
+                    })
+                    .into_iter()
+                    .collect();
+                (name.clone(), Ok(reports))
+            })
+            .collect()
+    }
+}
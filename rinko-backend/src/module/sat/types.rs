@@ -1,8 +1,10 @@
-///! Core data structures for satellite status management
+//! Core data structures for satellite status management
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use std::collections::HashMap;
 
+use super::palette::ColorPalette;
+
 /// Satellite report from AMSAT API
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AmsatReport {
@@ -11,6 +13,8 @@ pub struct AmsatReport {
     pub callsign: String,
     pub report: String,
     pub grid_square: String,
+    #[serde(default)]
+    pub source: ReportSource,
 }
 
 impl Default for AmsatReport {
@@ -21,6 +25,86 @@ impl Default for AmsatReport {
             callsign: String::new(),
             report: ReportStatus::Grey.to_string(),
             grid_square: String::new(),
+            source: ReportSource::Amsat,
+        }
+    }
+}
+
+/// Shown instead of a missing callsign, an invalid grid or an unknown time
+pub const MISSING_FIELD: &str = "—";
+
+/// Zone-less layouts seen in AMSAT data, read as UTC
+const NAIVE_TIME_FORMATS: &[&str] = &[
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+    "%Y/%m/%d %H:%M",
+];
+
+/// Parse a report time, accepting more than RFC3339
+///
+/// Tries RFC3339, then AMSAT's zone-less "2026-02-16 08:30:00" style
+/// (with an optional trailing "UTC" or "Z"), explicit numeric offsets,
+/// RFC2822 and finally Unix seconds. Returns None when nothing fits, so
+/// callers can show the time as unknown instead of guessing.
+pub fn parse_report_time(text: &str) -> Option<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(text) {
+        return Some(time.with_timezone(&Utc));
+    }
+
+    let naive = text
+        .strip_suffix("UTC")
+        .or_else(|| text.strip_suffix('Z'))
+        .unwrap_or(text)
+        .trim_end();
+    if let Some(time) = NAIVE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(naive, format).ok())
+    {
+        return Some(time.and_utc());
+    }
+
+    if let Ok(time) = DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(time.with_timezone(&Utc));
+    }
+    if let Ok(time) = DateTime::parse_from_rfc2822(text) {
+        return Some(time.with_timezone(&Utc));
+    }
+
+    // Unix seconds; shorter numbers are more likely garbage than 1970s dates
+    if text.len() >= 9 && text.bytes().all(|b| b.is_ascii_digit()) {
+        return text.parse().ok().and_then(|secs| DateTime::from_timestamp(secs, 0));
+    }
+    None
+}
+
+impl AmsatReport {
+    /// When the report was made, or None if `reported_time` is unreadable
+    pub fn reported_at(&self) -> Option<DateTime<Utc>> {
+        parse_report_time(&self.reported_time)
+    }
+}
+
+/// Where a report came from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportSource {
+    /// Fetched from the AMSAT status API
+    #[default]
+    Amsat,
+    /// Submitted by a bot user
+    User,
+}
+
+impl ReportSource {
+    /// Lowercase name, as serialized
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportSource::Amsat => "amsat",
+            ReportSource::User => "user",
         }
     }
 }
@@ -36,16 +120,41 @@ pub enum ReportStatus {
     Grey,    // Unknown status
 }
 
+/// User-friendly description of the status
+impl std::fmt::Display for ReportStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let text = match self {
+            ReportStatus::Blue => "Transponder/Repeater active",
+            ReportStatus::Yellow => "Telemetry/Beacon only",
+            ReportStatus::Orange => "Conflicting reports",
+            ReportStatus::Red => "No signal",
+            ReportStatus::Purple => "ISS Crew (Voice) Active",
+            ReportStatus::Grey => "Unknown status",
+        };
+        write!(f, "{}", text)
+    }
+}
+
 impl ReportStatus {
-    /// Convert to user-friendly string
-    pub fn to_string(&self) -> String {
+    /// All status variants
+    pub const ALL: [ReportStatus; 6] = [
+        ReportStatus::Blue,
+        ReportStatus::Yellow,
+        ReportStatus::Orange,
+        ReportStatus::Red,
+        ReportStatus::Purple,
+        ReportStatus::Grey,
+    ];
+
+    /// Rank for severity sorting, most severe (no signal) first
+    pub fn severity_rank(&self) -> u8 {
         match self {
-            ReportStatus::Blue => "Transponder/Repeater active".to_string(),
-            ReportStatus::Yellow => "Telemetry/Beacon only".to_string(),
-            ReportStatus::Orange => "Conflicting reports".to_string(),
-            ReportStatus::Red => "No signal".to_string(),
-            ReportStatus::Purple => "ISS Crew (Voice) Active".to_string(),
-            ReportStatus::Grey => "Unknown status".to_string(),
+            ReportStatus::Red => 0,
+            ReportStatus::Orange => 1,
+            ReportStatus::Yellow => 2,
+            ReportStatus::Blue => 3,
+            ReportStatus::Purple => 4,
+            ReportStatus::Grey => 5,
         }
     }
 
@@ -54,36 +163,51 @@ impl ReportStatus {
         match self {
             ReportStatus::Blue => "Heard".to_string(),
             ReportStatus::Yellow => "Telemetry Only".to_string(),
+            ReportStatus::Orange => "Conflicting Reports".to_string(),
             ReportStatus::Red => "Not Heard".to_string(),
             ReportStatus::Purple => "Crew Active".to_string(),
-            _ => "Unknown status".to_string(),
+            ReportStatus::Grey => "Unknown status".to_string(),
         }
     }
 
     /// Parse from string
+    ///
+    /// Accepts both the report format (`to_report_format`) and the
+    /// user-friendly description (`Display`) of every status.
     pub fn from_string(s: &str) -> Self {
-        match s.to_lowercase().as_str() {
-            "heard" => ReportStatus::Blue,
-            "telemetry only" => ReportStatus::Yellow,
+        match s.trim().to_lowercase().as_str() {
+            "heard" | "transponder/repeater active" => ReportStatus::Blue,
+            "telemetry only" | "telemetry/beacon only" => ReportStatus::Yellow,
             "conflicting reports" => ReportStatus::Orange,
-            "not heard" => ReportStatus::Red,
-            "crew active" => ReportStatus::Purple,
+            "not heard" | "no signal" => ReportStatus::Red,
+            "crew active" | "iss crew (voice) active" => ReportStatus::Purple,
             _ => ReportStatus::Grey,
         }
     }
 
-    /// Convert to hex color for rendering
-    pub fn to_color_hex(&self) -> &'static str {
-        match self {
-            ReportStatus::Blue => "#4297f3ff",
-            ReportStatus::Yellow => "#f3cd36ff",
-            ReportStatus::Orange => "#f97316",
-            ReportStatus::Red => "#ed3f3fff",
-            ReportStatus::Purple => "#946af5ff",
-            ReportStatus::Grey => "#6b7280",
+    /// Parse a status typed by a user submitting a report
+    ///
+    /// Accepts the report format with `-`/`_` as word separators plus short
+    /// forms ("telemetry", "beacon", "crew"). Statuses a single user cannot
+    /// observe (conflicting, unknown) are rejected.
+    pub fn from_user_input(s: &str) -> Option<Self> {
+        let normalized = s.trim().to_lowercase().replace(['-', '_'], " ");
+        let status = match normalized.as_str() {
+            "telemetry" | "beacon" => ReportStatus::Yellow,
+            "crew" => ReportStatus::Purple,
+            other => ReportStatus::from_string(other),
+        };
+        match status {
+            ReportStatus::Orange | ReportStatus::Grey => None,
+            status => Some(status),
         }
     }
 
+    /// Convert to hex color in the default palette
+    pub fn to_color_hex(&self) -> &'static str {
+        ColorPalette::Default.color_hex(*self)
+    }
+
     /// Get color from string status
     pub fn string_to_color_hex(status: &str) -> &'static str {
         Self::from_string(status).to_color_hex()
@@ -109,6 +233,8 @@ pub struct SatelliteInfo {
     pub is_active: bool,                       // Active flag instead of deletion
     pub amsat_update_status: bool,             // Whether last AMSAT update succeeded
     pub metadata: HashMap<String, String>,     // Extension fields
+    #[serde(default = "unknown_first_seen")]
+    pub first_seen: DateTime<Utc>,             // Copied from the satellite list entry
 }
 
 impl Default for SatelliteInfo {
@@ -123,6 +249,7 @@ impl Default for SatelliteInfo {
             is_active: true,
             amsat_update_status: false,
             metadata: HashMap::new(),
+            first_seen: unknown_first_seen(),
         }
     }
 }
@@ -145,6 +272,34 @@ impl SatelliteInfo {
         }
     }
 
+    /// The most recent report, if any
+    pub fn latest_report(&self) -> Option<&AmsatReport> {
+        // Data blocks are sorted newest first; reports within a block are not
+        self.data_blocks
+            .first()?
+            .reports
+            .iter()
+            .max_by(|a, b| a.reported_time.cmp(&b.reported_time))
+    }
+
+    /// Reports from the last `hours` hours before `now`, newest first
+    ///
+    /// Reports with unparseable times are left out.
+    pub fn get_recent_reports(&self, hours: u64, now: DateTime<Utc>) -> Vec<&AmsatReport> {
+        let cutoff = now - chrono::Duration::hours(hours as i64);
+        self.data_blocks
+            .iter()
+            .flat_map(|block| block.reports.iter())
+            .filter(|report| report.reported_at().is_some_and(|time| time >= cutoff))
+            .collect()
+    }
+
+    /// Status of the most recent report, if any
+    pub fn latest_status(&self) -> Option<ReportStatus> {
+        self.latest_report()
+            .map(|report| ReportStatus::from_string(&report.report))
+    }
+
     /// Get total number of reports
     pub fn total_reports(&self) -> usize {
         self.data_blocks.iter()
@@ -154,17 +309,17 @@ impl SatelliteInfo {
 }
 
 /// Satellite list configuration (stored in TOML)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SatelliteList {
     pub satellites: Vec<SatelliteEntry>,
 }
 
-impl Default for SatelliteList {
-    fn default() -> Self {
-        Self {
-            satellites: Vec::new(),
-        }
-    }
+/// First-seen time of satellites that predate first-seen tracking
+///
+/// Lists and caches written before the field existed load with the Unix
+/// epoch, so satellites already known are never flagged as new.
+pub fn unknown_first_seen() -> DateTime<Utc> {
+    DateTime::UNIX_EPOCH
 }
 
 /// Satellite entry in configuration
@@ -174,18 +329,31 @@ pub struct SatelliteEntry {
     pub aliases: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub catalog_number: Option<String>,
+    /// When the satellite was first added to the list
+    #[serde(default = "unknown_first_seen")]
+    pub first_seen: DateTime<Utc>,
 }
 
 impl SatelliteEntry {
+    /// Create an entry first seen now
     pub fn new(official_name: impl Into<String>) -> Self {
         Self {
             official_name: official_name.into(),
             aliases: Vec::new(),
             catalog_number: None,
+            first_seen: Utc::now(),
         }
     }
 }
 
+/// Most recent failure of a status source, kept until it next succeeds
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SourceError {
+    pub source: ReportSource,
+    pub time: DateTime<Utc>,
+    pub message: String,
+}
+
 /// Update report summary
 #[derive(Debug, Clone)]
 pub struct UpdateReport {
@@ -194,9 +362,17 @@ pub struct UpdateReport {
     pub failed_updates: usize,
     pub new_satellites: Vec<String>,
     pub inactive_satellites: Vec<String>,
+    /// Fetched reports dropped for having no callsign, grid or status
+    pub skipped_reports: usize,
     pub duration_seconds: f64,
 }
 
+impl Default for UpdateReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl UpdateReport {
     pub fn new() -> Self {
         Self {
@@ -205,6 +381,7 @@ impl UpdateReport {
             failed_updates: 0,
             new_satellites: Vec::new(),
             inactive_satellites: Vec::new(),
+            skipped_reports: 0,
             duration_seconds: 0.0,
         }
     }
@@ -214,6 +391,33 @@ impl UpdateReport {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_report_time_variants() {
+        let expected = DateTime::parse_from_rfc3339("2026-02-16T08:30:00Z").unwrap().with_timezone(&Utc);
+        for text in [
+            "2026-02-16T08:30:00Z",
+            "2026-02-16T08:30:00+00:00",
+            "2026-02-16T16:30:00+08:00",
+            "2026-02-16T08:30:00",
+            "2026-02-16T08:30:00.000",
+            "2026-02-16 08:30:00",
+            "2026-02-16 08:30:00 UTC",
+            "2026-02-16 08:30 UTC",
+            "2026-02-16T08:30",
+            " 2026-02-16 08:30:00Z ",
+            "2026/02/16 08:30",
+            "2026-02-16 08:30:00 +0000",
+            "Mon, 16 Feb 2026 08:30:00 +0000",
+            "1771230600",
+        ] {
+            assert_eq!(parse_report_time(text), Some(expected), "{}", text);
+        }
+
+        for text in ["", "yesterday", "16/02/2026 8.30", "2026-02-30 08:30:00", "12345"] {
+            assert_eq!(parse_report_time(text), None, "{}", text);
+        }
+    }
+
     #[test]
     fn test_report_status_conversion() {
         assert_eq!(ReportStatus::from_string("heard"), ReportStatus::Blue);
@@ -222,6 +426,25 @@ mod tests {
         assert_eq!(ReportStatus::from_string("unknown"), ReportStatus::Grey);
     }
 
+    #[test]
+    fn test_report_status_round_trip() {
+        for status in ReportStatus::ALL {
+            assert_eq!(ReportStatus::from_string(&status.to_report_format()), status);
+            assert_eq!(ReportStatus::from_string(&status.to_string()), status);
+        }
+    }
+
+    #[test]
+    fn test_report_status_from_user_input() {
+        assert_eq!(ReportStatus::from_user_input("heard"), Some(ReportStatus::Blue));
+        assert_eq!(ReportStatus::from_user_input("Not-Heard"), Some(ReportStatus::Red));
+        assert_eq!(ReportStatus::from_user_input("not_heard"), Some(ReportStatus::Red));
+        assert_eq!(ReportStatus::from_user_input("telemetry"), Some(ReportStatus::Yellow));
+        assert_eq!(ReportStatus::from_user_input("crew"), Some(ReportStatus::Purple));
+        assert_eq!(ReportStatus::from_user_input("conflicting-reports"), None);
+        assert_eq!(ReportStatus::from_user_input("loud"), None);
+    }
+
     #[test]
     fn test_report_status_color() {
         assert_eq!(ReportStatus::Blue.to_color_hex(), "#4297f3ff");
@@ -1,6 +1,6 @@
-///! Satellite updater - scheduled update tasks
+//! Satellite updater - scheduled update tasks
 use super::manager::SatelliteManager;
-use chrono::{DateTime, Timelike, Utc};
+use chrono::{DateTime, DurationRound, Utc};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::JoinHandle;
@@ -78,7 +78,10 @@ impl SatelliteUpdater {
     }
 
     /// Run a single update cycle with retries
+    ///
+    /// All attempts share one request budget.
     async fn run_update_cycle(&self) {
+        let budget = self.manager.request_budget();
         for attempt in 1..=MAX_RETRIES {
             tracing::info!(
                 "Starting satellite update (attempt {}/{})",
@@ -88,7 +91,7 @@ impl SatelliteUpdater {
 
             let result = tokio::time::timeout(
                 Duration::from_secs(UPDATE_TIMEOUT_SECONDS),
-                self.manager.update_all_satellites(),
+                self.manager.update_all_satellites_within(&budget),
             )
             .await;
 
@@ -162,33 +165,14 @@ impl SatelliteUpdater {
     /// Calculate next trigger for fixed-minute schedule
     /// Updates at: xx:02, xx:17, xx:32, xx:47
     fn calculate_fixed_minute_trigger(&self, now: DateTime<Utc>) -> DateTime<Utc> {
-        let current_minute = now.minute();
-
-        let next_minute = match current_minute {
-            0..=1 => 2,
-            2..=16 => 17,
-            17..=31 => 32,
-            32..=46 => 47,
-            _ => {
-                // 47..=59, wrap to next hour at 02
-                return now
-                    .with_hour((now.hour() + 1) % 24)
-                    .unwrap()
-                    .with_minute(2)
-                    .unwrap()
-                    .with_second(0)
-                    .unwrap()
-                    .with_nanosecond(0)
-                    .unwrap();
-            }
-        };
-
-        now.with_minute(next_minute)
-            .unwrap()
-            .with_second(0)
-            .unwrap()
-            .with_nanosecond(0)
-            .unwrap()
+        let hour_start = now.duration_trunc(chrono::Duration::hours(1)).unwrap_or(now);
+
+        // 47..=59 wraps to 02 past the next hour, which may be tomorrow
+        let minute = [2, 17, 32, 47]
+            .into_iter()
+            .find(|minute| hour_start + chrono::Duration::minutes(*minute) > now)
+            .unwrap_or(60 + 2);
+        hour_start + chrono::Duration::minutes(minute)
     }
 }
 
@@ -210,13 +194,11 @@ pub async fn start_satellite_updater(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
+    use chrono::{TimeZone, Timelike};
 
     #[test]
     fn test_calculate_fixed_minute_trigger() {
-        let manager = Arc::new(
-            SatelliteManager::new(std::env::temp_dir().join("test"), 10).unwrap()
-        );
+        let manager = SatelliteManager::new(std::env::temp_dir().join("test"), 10).unwrap();
         let updater = SatelliteUpdater::new(manager, 10);
 
         // Test various times
@@ -236,5 +218,10 @@ mod tests {
         let next = updater.calculate_fixed_minute_trigger(test_time);
         assert_eq!(next.minute(), 2);
         assert_eq!(next.hour(), 11);
+
+        // The last slot of the day wraps to tomorrow, not back to today
+        let test_time = Utc.with_ymd_and_hms(2026, 2, 16, 23, 50, 0).unwrap();
+        let next = updater.calculate_fixed_minute_trigger(test_time);
+        assert_eq!(next, Utc.with_ymd_and_hms(2026, 2, 17, 0, 2, 0).unwrap());
     }
 }
@@ -1,11 +1,14 @@
-///! Scheduled task manager - Centralize all periodic tasks
-///!
-///! This module manages all scheduled background tasks:
-///! - Satellite data updates (every 10 minutes)
-///! - Image cache cleanup (daily)
-///! - Future tasks can be added here
-
-use super::sat::{SatelliteManager, cleanup_old_images};
+//! Scheduled task manager - Centralize all periodic tasks
+//!
+//! This module manages all scheduled background tasks:
+//! - Satellite data updates (every 10 minutes)
+//! - Image cache cleanup (daily)
+//! - Future tasks can be added here
+
+use super::sat::{RequestBudget, SatelliteManager, cleanup_old_images};
+use super::daily_post::DailyPoster;
+use super::subscription::SubscriptionPoster;
+use super::watchlist::StatusNotifier;
 use chrono::{DateTime, Timelike, Utc};
 use std::sync::Arc;
 use std::time::Duration;
@@ -23,8 +26,8 @@ pub struct ScheduledTaskConfig {
     /// Number of days to keep cached images
     pub image_retention_days: i64,
     
-    /// Cache directory for satellite data
-    pub cache_dir: String,
+    /// Directory holding rendered images
+    pub images_dir: String,
     
     /// Perform initial update immediately
     pub perform_initial_update: bool,
@@ -36,7 +39,7 @@ impl Default for ScheduledTaskConfig {
             satellite_update_interval_minutes: 10,
             image_cleanup_interval_hours: 24,
             image_retention_days: 7,
-            cache_dir: "data/satellite_cache".to_string(),
+            images_dir: "data/satellite_cache/rendered_images".to_string(),
             perform_initial_update: true,
         }
     }
@@ -46,6 +49,9 @@ impl Default for ScheduledTaskConfig {
 pub struct ScheduledTaskManager {
     config: ScheduledTaskConfig,
     satellite_manager: Arc<SatelliteManager>,
+    status_notifier: Option<StatusNotifier>,
+    daily_poster: Option<DailyPoster>,
+    subscription_poster: Option<SubscriptionPoster>,
     task_handles: Vec<JoinHandle<()>>,
 }
 
@@ -55,10 +61,31 @@ impl ScheduledTaskManager {
         Self {
             config,
             satellite_manager,
+            status_notifier: None,
+            daily_poster: None,
+            subscription_poster: None,
             task_handles: Vec::new(),
         }
     }
 
+    /// Check watched satellites for status changes after each update
+    pub fn with_status_notifier(mut self, notifier: StatusNotifier) -> Self {
+        self.status_notifier = Some(notifier);
+        self
+    }
+
+    /// Post a satellite of the day at the configured time
+    pub fn with_daily_poster(mut self, poster: DailyPoster) -> Self {
+        self.daily_poster = Some(poster);
+        self
+    }
+
+    /// Post subscribed satellites to their groups at each group's cadence
+    pub fn with_subscription_poster(mut self, poster: SubscriptionPoster) -> Self {
+        self.subscription_poster = Some(poster);
+        self
+    }
+
     /// Start all scheduled tasks
     pub async fn start_all(&mut self) -> anyhow::Result<()> {
         tracing::info!("Starting scheduled task manager...");
@@ -70,6 +97,18 @@ impl ScheduledTaskManager {
         // Start image cleanup task
         let cleanup_handle = self.start_image_cleanup_task().await?;
         self.task_handles.push(cleanup_handle);
+
+        // Start the optional daily post task
+        if let Some(poster) = self.daily_poster.clone() {
+            let manager = self.satellite_manager.clone();
+            self.task_handles.push(tokio::spawn(Self::daily_post_loop(poster, manager)));
+        }
+
+        // Start the subscription post task
+        if let Some(poster) = self.subscription_poster.clone() {
+            let manager = self.satellite_manager.clone();
+            self.task_handles.push(tokio::spawn(Self::subscription_post_loop(poster, manager)));
+        }
         
         tracing::info!(
             "Started {} scheduled tasks (satellite updates every {} min, image cleanup every {} hours)",
@@ -84,6 +123,7 @@ impl ScheduledTaskManager {
     /// Start satellite data update task
     async fn start_satellite_update_task(&self) -> anyhow::Result<JoinHandle<()>> {
         let manager = self.satellite_manager.clone();
+        let notifier = self.status_notifier.clone();
         let interval_minutes = self.config.satellite_update_interval_minutes;
         let perform_initial = self.config.perform_initial_update;
         
@@ -97,20 +137,24 @@ impl ScheduledTaskManager {
             // Perform initial update if configured
             if perform_initial {
                 tracing::info!("Performing initial satellite update...");
-                if let Err(e) = Self::run_satellite_update(&manager).await {
+                if let Err(e) = Self::run_satellite_update(&manager, &notifier, &manager.request_budget()).await {
                     tracing::error!("Initial satellite update failed: {}", e);
                 }
             }
             
             // Run scheduled updates
-            Self::satellite_update_loop(manager, interval_minutes).await;
+            Self::satellite_update_loop(manager, notifier, interval_minutes).await;
         });
         
         Ok(handle)
     }
 
     /// Satellite update loop
-    async fn satellite_update_loop(manager: Arc<SatelliteManager>, interval_minutes: u64) {
+    async fn satellite_update_loop(
+        manager: Arc<SatelliteManager>,
+        notifier: Option<StatusNotifier>,
+        interval_minutes: u64,
+    ) {
         loop {
             let now = Utc::now();
             let next_trigger = Self::calculate_next_update_time(now, interval_minutes);
@@ -126,10 +170,11 @@ impl ScheduledTaskManager {
 
             tokio::time::sleep(sleep_duration).await;
 
-            // Run update with retries
+            // Run update with retries, all drawing from one request budget
             const MAX_RETRIES: u32 = 3;
+            let budget = manager.request_budget();
             for attempt in 1..=MAX_RETRIES {
-                match Self::run_satellite_update(&manager).await {
+                match Self::run_satellite_update(&manager, &notifier, &budget).await {
                     Ok(_) => {
                         tracing::info!("Satellite update completed successfully");
                         break;
@@ -191,11 +236,15 @@ impl ScheduledTaskManager {
             .unwrap()
     }
 
-    /// Run a single satellite update
-    async fn run_satellite_update(manager: &Arc<SatelliteManager>) -> anyhow::Result<()> {
+    /// Run a single satellite update, then check the watchlist
+    async fn run_satellite_update(
+        manager: &Arc<SatelliteManager>,
+        notifier: &Option<StatusNotifier>,
+        budget: &RequestBudget,
+    ) -> anyhow::Result<()> {
         let timeout_duration = Duration::from_secs(300); // 5 minutes
         
-        match tokio::time::timeout(timeout_duration, manager.update_all_satellites()).await {
+        let result = match tokio::time::timeout(timeout_duration, manager.update_all_satellites_within(budget)).await {
             Ok(result) => result.map(|report| {
                 tracing::info!(
                     "Satellite update: {} total, {} successful, {} failed, {} new, {} inactive",
@@ -209,12 +258,19 @@ impl ScheduledTaskManager {
             Err(_) => {
                 anyhow::bail!("Satellite update timed out after {} seconds", timeout_duration.as_secs());
             }
+        };
+
+        if result.is_ok()
+            && let Some(notifier) = notifier
+        {
+            notifier.check(manager).await;
         }
+        result
     }
 
     /// Start image cleanup task
     async fn start_image_cleanup_task(&self) -> anyhow::Result<JoinHandle<()>> {
-        let cache_dir = self.config.cache_dir.clone();
+        let images_dir = self.config.images_dir.clone();
         let interval_hours = self.config.image_cleanup_interval_hours;
         let retention_days = self.config.image_retention_days;
         
@@ -225,14 +281,41 @@ impl ScheduledTaskManager {
         );
         
         let handle = tokio::spawn(async move {
-            Self::image_cleanup_loop(cache_dir, interval_hours, retention_days).await;
+            Self::image_cleanup_loop(images_dir, interval_hours, retention_days).await;
         });
         
         Ok(handle)
     }
 
+    /// Daily "satellite of the day" loop; ends if the schedule is invalid
+    async fn daily_post_loop(poster: DailyPoster, manager: Arc<SatelliteManager>) {
+        while let Some((next_post, date)) = poster.next_post(Utc::now()) {
+            let sleep_duration = (next_post - Utc::now())
+                .to_std()
+                .unwrap_or(Duration::from_secs(60));
+
+            tracing::info!(
+                "Next satellite of the day at: {}",
+                next_post.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+
+            tokio::time::sleep(sleep_duration).await;
+
+            poster.post(&manager, date).await;
+        }
+    }
+
+    /// Check for due subscriptions once a minute
+    async fn subscription_post_loop(poster: SubscriptionPoster, manager: Arc<SatelliteManager>) {
+        let mut ticker = tokio::time::interval(Duration::from_secs(60));
+        loop {
+            ticker.tick().await;
+            poster.post_due(&manager, Utc::now()).await;
+        }
+    }
+
     /// Image cleanup loop
-    async fn image_cleanup_loop(cache_dir: String, interval_hours: u64, retention_days: i64) {
+    async fn image_cleanup_loop(images_dir: String, interval_hours: u64, retention_days: i64) {
         loop {
             let now = Utc::now();
             let next_trigger = Self::calculate_next_cleanup_time(now, interval_hours);
@@ -249,7 +332,7 @@ impl ScheduledTaskManager {
             tokio::time::sleep(sleep_duration).await;
 
             // Run cleanup
-            match Self::run_image_cleanup(&cache_dir, retention_days).await {
+            match Self::run_image_cleanup(&images_dir, retention_days).await {
                 Ok(deleted_count) => {
                     if deleted_count > 0 {
                         tracing::info!("Image cleanup completed: deleted {} old images", deleted_count);
@@ -294,11 +377,10 @@ impl ScheduledTaskManager {
     }
 
     /// Run image cleanup
-    async fn run_image_cleanup(cache_dir: &str, retention_days: i64) -> anyhow::Result<usize> {
+    async fn run_image_cleanup(images_dir: &str, retention_days: i64) -> anyhow::Result<usize> {
         use std::path::Path;
         
-        let cache_path = Path::new(cache_dir);
-        let deleted_count = cleanup_old_images(cache_path, retention_days).await?;
+        let deleted_count = cleanup_old_images(Path::new(images_dir), retention_days).await?;
         
         Ok(deleted_count)
     }
@@ -318,10 +400,11 @@ impl ScheduledTaskManager {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::Datelike;
 
     #[test]
     fn test_calculate_next_update_time() {
-        // Test at 10:00 - should return 10:17
+        // Test at 10:00 - should return 10:02
         let now = Utc::now()
             .with_hour(10)
             .unwrap()
@@ -330,6 +413,12 @@ mod tests {
             .with_second(0)
             .unwrap();
         let next = ScheduledTaskManager::calculate_next_update_time(now, 15);
+        assert_eq!(next.minute(), 2);
+        assert_eq!(next.hour(), 10);
+
+        // Test at 10:05 - should return 10:17
+        let now = now.with_minute(5).unwrap();
+        let next = ScheduledTaskManager::calculate_next_update_time(now, 15);
         assert_eq!(next.minute(), 17);
         assert_eq!(next.hour(), 10);
 
@@ -358,6 +447,6 @@ mod tests {
         let now = now.with_hour(5).unwrap();
         let next = ScheduledTaskManager::calculate_next_cleanup_time(now, 24);
         assert_eq!(next.hour(), 3);
-        assert_eq!(next.day(), now.day() + 1);
+        assert_eq!(next.day(), (now + chrono::Duration::days(1)).day());
     }
 }
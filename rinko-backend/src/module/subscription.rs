@@ -0,0 +1,353 @@
+//! Periodic satellite status posts for subscribed groups
+//!
+//! Groups subscribe to satellites with "/subscribe AO-91 hourly". The store
+//! keeps each subscription and when it was last posted, persisted as JSON
+//! so restarts keep the schedule. Every minute the poster collects the due
+//! subscriptions, renders each satellite once and sends one post per
+//! satellite addressed to exactly the groups whose subscription is due.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+
+use super::sat::{SatelliteInfo, SatelliteManager, SatelliteRenderer};
+use crate::config::RenderConfig;
+
+/// Subscriptions one group may hold
+pub const MAX_SUBSCRIPTIONS_PER_GROUP: usize = 10;
+
+/// How often a subscription is posted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Cadence {
+    #[default]
+    Hourly,
+    Daily,
+}
+
+impl Cadence {
+    /// Parse "hourly" or "daily" (case-insensitive)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "hourly" => Some(Cadence::Hourly),
+            "daily" => Some(Cadence::Daily),
+            _ => None,
+        }
+    }
+
+    pub fn interval(self) -> Duration {
+        match self {
+            Cadence::Hourly => Duration::hours(1),
+            Cadence::Daily => Duration::days(1),
+        }
+    }
+}
+
+impl fmt::Display for Cadence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cadence::Hourly => write!(f, "hourly"),
+            Cadence::Daily => write!(f, "daily"),
+        }
+    }
+}
+
+/// One group's subscription to one satellite
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Group openid the posts go to
+    pub group: String,
+    /// Satellite name as known to the manager
+    pub satellite: String,
+    pub cadence: Cadence,
+    /// Last post, or when the subscription was made; the next post is one
+    /// cadence later
+    pub last_posted: DateTime<Utc>,
+}
+
+impl Subscription {
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        now - self.last_posted >= self.cadence.interval()
+    }
+}
+
+/// Outcome of a subscribe request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscribeOutcome {
+    Added,
+    /// The group was subscribed already; its cadence was updated
+    Updated,
+    /// The group holds `MAX_SUBSCRIPTIONS_PER_GROUP` already
+    LimitReached,
+}
+
+/// All subscriptions, optionally persisted to a JSON file
+#[derive(Default)]
+pub struct SubscriptionStore {
+    /// File saved after every change; None keeps them in memory only
+    path: Option<PathBuf>,
+    subscriptions: Mutex<Vec<Subscription>>,
+}
+
+impl SubscriptionStore {
+    /// Load the subscriptions saved at `path`, starting empty without a file
+    pub async fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let subscriptions = if path.exists() {
+            let content = tokio::fs::read_to_string(&path)
+                .await
+                .context(format!("Failed to read subscriptions file: {:?}", path))?;
+            serde_json::from_str(&content).context("Failed to parse subscriptions JSON")?
+        } else {
+            Vec::new()
+        };
+
+        tracing::info!("Loaded {} satellite subscriptions from {:?}", subscriptions.len(), path);
+        Ok(Self {
+            path: Some(path),
+            subscriptions: Mutex::new(subscriptions),
+        })
+    }
+
+    /// Subscribe `group` to `satellite`, or change the cadence it gets
+    pub async fn subscribe(
+        &self,
+        group: &str,
+        satellite: &str,
+        cadence: Cadence,
+        now: DateTime<Utc>,
+    ) -> Result<SubscribeOutcome> {
+        let mut subscriptions = self.subscriptions.lock().await;
+        if let Some(existing) = subscriptions
+            .iter_mut()
+            .find(|s| s.group == group && s.satellite == satellite)
+        {
+            existing.cadence = cadence;
+            self.save(&subscriptions).await?;
+            return Ok(SubscribeOutcome::Updated);
+        }
+
+        if subscriptions.iter().filter(|s| s.group == group).count() >= MAX_SUBSCRIPTIONS_PER_GROUP {
+            return Ok(SubscribeOutcome::LimitReached);
+        }
+        subscriptions.push(Subscription {
+            group: group.to_string(),
+            satellite: satellite.to_string(),
+            cadence,
+            last_posted: now,
+        });
+        self.save(&subscriptions).await?;
+        Ok(SubscribeOutcome::Added)
+    }
+
+    /// Remove a subscription; false if the group had none for `satellite`
+    pub async fn unsubscribe(&self, group: &str, satellite: &str) -> Result<bool> {
+        let mut subscriptions = self.subscriptions.lock().await;
+        let before = subscriptions.len();
+        subscriptions.retain(|s| !(s.group == group && s.satellite == satellite));
+        if subscriptions.len() == before {
+            return Ok(false);
+        }
+        self.save(&subscriptions).await?;
+        Ok(true)
+    }
+
+    /// A group's subscriptions, by satellite name
+    pub async fn list(&self, group: &str) -> Vec<Subscription> {
+        let mut subscriptions: Vec<Subscription> = self
+            .subscriptions
+            .lock()
+            .await
+            .iter()
+            .filter(|s| s.group == group)
+            .cloned()
+            .collect();
+        subscriptions.sort_by(|a, b| a.satellite.cmp(&b.satellite));
+        subscriptions
+    }
+
+    /// Satellite -> groups whose subscription is due at `now`
+    ///
+    /// The returned subscriptions are marked as posted.
+    pub async fn take_due(&self, now: DateTime<Utc>) -> Result<BTreeMap<String, Vec<String>>> {
+        let mut subscriptions = self.subscriptions.lock().await;
+        let mut due: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for subscription in subscriptions.iter_mut().filter(|s| s.is_due(now)) {
+            due.entry(subscription.satellite.clone())
+                .or_default()
+                .push(subscription.group.clone());
+            subscription.last_posted = now;
+        }
+        if !due.is_empty() {
+            self.save(&subscriptions).await?;
+        }
+        Ok(due)
+    }
+
+    async fn save(&self, subscriptions: &[Subscription]) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .context(format!("Failed to create subscriptions directory: {:?}", dir))?;
+        }
+        let json = serde_json::to_string_pretty(subscriptions).context("Failed to serialize subscriptions")?;
+        tokio::fs::write(path, json)
+            .await
+            .context(format!("Failed to write subscriptions file: {:?}", path))
+    }
+}
+
+/// A rendered status post for the groups subscribed to one satellite
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriptionPost {
+    pub satellite: String,
+    pub groups: Vec<String>,
+    pub message: String,
+    /// Local path of the status image, if rendering succeeded
+    pub image: Option<String>,
+}
+
+/// Short text sent with the image
+pub fn post_message(sat: &SatelliteInfo) -> String {
+    let mut message = format!("🛰️ {} status update", sat.name);
+    match sat.latest_status() {
+        Some(status) => message.push_str(&format!("\nLatest status: {}", status)),
+        None => message.push_str("\nNo recent reports"),
+    }
+    message.push_str(&format!("\nSend /unsubscribe {} to stop these posts", sat.name));
+    message
+}
+
+/// Renders due subscriptions and hands them to the service for delivery
+#[derive(Clone)]
+pub struct SubscriptionPoster {
+    store: Arc<SubscriptionStore>,
+    render_config: RenderConfig,
+    tx: mpsc::Sender<SubscriptionPost>,
+}
+
+impl SubscriptionPoster {
+    pub fn new(store: Arc<SubscriptionStore>, render_config: RenderConfig, tx: mpsc::Sender<SubscriptionPost>) -> Self {
+        Self { store, render_config, tx }
+    }
+
+    /// Render and send a post for every satellite with due subscriptions
+    pub async fn post_due(&self, manager: &SatelliteManager, now: DateTime<Utc>) {
+        let due = match self.store.take_due(now).await {
+            Ok(due) => due,
+            Err(e) => {
+                tracing::warn!("Failed to update subscriptions: {}", e);
+                return;
+            }
+        };
+        if due.is_empty() {
+            return;
+        }
+
+        let satellites = manager.get_all_satellites().await;
+        let renderer = SatelliteRenderer::with_config(&self.render_config.output_dir, self.render_config.clone());
+        for (name, groups) in due {
+            let Some(sat) = satellites.iter().find(|s| s.name == name) else {
+                tracing::warn!("Subscribed satellite {} is no longer tracked, skipping its post", name);
+                continue;
+            };
+
+            let image = match renderer.render_satellites(std::slice::from_ref(sat), self.render_config.layout).await {
+                Ok(path) => Some(path.to_string_lossy().to_string()),
+                Err(e) => {
+                    tracing::warn!("Subscription image for {} failed, sending text only: {}", name, e);
+                    None
+                }
+            };
+
+            tracing::info!("Posting {} status to {} subscribed group(s)", name, groups.len());
+            let post = SubscriptionPost {
+                satellite: name,
+                groups,
+                message: post_message(sat),
+                image,
+            };
+            if let Err(e) = self.tx.send(post).await {
+                tracing::warn!("Dropping subscription post: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_unsubscribe_round_trip() {
+        let path = std::env::temp_dir().join("rinko_test_subscriptions/subscriptions.json");
+        let _ = tokio::fs::remove_file(&path).await;
+        let now = at("2026-02-16T08:00:00Z");
+
+        let store = SubscriptionStore::load(&path).await.unwrap();
+        assert_eq!(store.subscribe("g1", "AO-91", Cadence::Hourly, now).await.unwrap(), SubscribeOutcome::Added);
+        assert_eq!(store.subscribe("g1", "SO-50", Cadence::Daily, now).await.unwrap(), SubscribeOutcome::Added);
+        assert_eq!(store.subscribe("g1", "AO-91", Cadence::Daily, now).await.unwrap(), SubscribeOutcome::Updated);
+
+        // Saved subscriptions survive a reload
+        let store = SubscriptionStore::load(&path).await.unwrap();
+        let listed = store.list("g1").await;
+        let names: Vec<(&str, Cadence)> = listed.iter().map(|s| (s.satellite.as_str(), s.cadence)).collect();
+        assert_eq!(names, vec![("AO-91", Cadence::Daily), ("SO-50", Cadence::Daily)]);
+        assert!(store.list("g2").await.is_empty());
+
+        assert!(store.unsubscribe("g1", "AO-91").await.unwrap());
+        assert!(!store.unsubscribe("g1", "AO-91").await.unwrap());
+        let store = SubscriptionStore::load(&path).await.unwrap();
+        assert_eq!(store.list("g1").await.len(), 1);
+
+        let _ = tokio::fs::remove_file(&path).await;
+    }
+
+    #[tokio::test]
+    async fn test_due_posts_target_only_subscribed_groups() {
+        let store = SubscriptionStore::default();
+        let now = at("2026-02-16T08:00:00Z");
+        store.subscribe("g1", "AO-91", Cadence::Hourly, now).await.unwrap();
+        store.subscribe("g2", "AO-91", Cadence::Hourly, now).await.unwrap();
+        store.subscribe("g2", "SO-50", Cadence::Hourly, now).await.unwrap();
+        store.subscribe("g3", "AO-91", Cadence::Daily, now).await.unwrap();
+
+        assert!(store.take_due(now + Duration::minutes(59)).await.unwrap().is_empty());
+
+        let due = store.take_due(now + Duration::hours(1)).await.unwrap();
+        assert_eq!(due.len(), 2);
+        assert_eq!(due["AO-91"], vec!["g1", "g2"]);
+        assert_eq!(due["SO-50"], vec!["g2"]);
+
+        // Posted subscriptions wait for their next hour
+        assert!(store.take_due(now + Duration::minutes(90)).await.unwrap().is_empty());
+        let due = store.take_due(now + Duration::days(1)).await.unwrap();
+        assert_eq!(due["AO-91"], vec!["g1", "g2", "g3"]);
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_per_group_are_capped() {
+        let store = SubscriptionStore::default();
+        let now = Utc::now();
+        for i in 0..MAX_SUBSCRIPTIONS_PER_GROUP {
+            store.subscribe("g1", &format!("SAT-{}", i), Cadence::Hourly, now).await.unwrap();
+        }
+        assert_eq!(
+            store.subscribe("g1", "AO-91", Cadence::Hourly, now).await.unwrap(),
+            SubscribeOutcome::LimitReached
+        );
+        assert_eq!(store.subscribe("g2", "AO-91", Cadence::Hourly, now).await.unwrap(), SubscribeOutcome::Added);
+    }
+}
@@ -0,0 +1,215 @@
+//! Status-change notifications for watched satellites
+//!
+//! After each update the watcher compares every watched satellite's latest
+//! status with the last one it announced. A new status must hold for the
+//! debounce window before it is announced, so rapid flips stay quiet.
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+use super::sat::{ReportStatus, SatelliteInfo, SatelliteManager};
+use crate::config::WatchlistConfig;
+
+/// A confirmed status change of a watched satellite
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusChange {
+    pub satellite: String,
+    pub previous: ReportStatus,
+    pub current: ReportStatus,
+}
+
+impl StatusChange {
+    /// Text sent to groups
+    pub fn message(&self) -> String {
+        format!(
+            "🛰️ {} status changed: {} → {}",
+            self.satellite, self.previous, self.current
+        )
+    }
+}
+
+/// Per-satellite debounce state
+#[derive(Debug)]
+struct WatchState {
+    /// Last announced (or initially observed) status
+    confirmed: ReportStatus,
+    /// Differing status seen since, and when it was first seen
+    pending: Option<(ReportStatus, DateTime<Utc>)>,
+}
+
+/// Tracks watched satellites and detects real status changes
+pub struct StatusWatcher {
+    /// Watched names or aliases, lowercased
+    watched: Vec<String>,
+    debounce: Duration,
+    states: HashMap<String, WatchState>,
+}
+
+impl StatusWatcher {
+    pub fn new(config: &WatchlistConfig) -> Self {
+        Self {
+            watched: config.satellites.iter().map(|s| s.to_lowercase()).collect(),
+            debounce: Duration::minutes(config.debounce_minutes as i64),
+            states: HashMap::new(),
+        }
+    }
+
+
+    /// Compare the latest statuses against the last announced ones
+    ///
+    /// The first status seen for a satellite is the baseline and is not
+    /// announced. Satellites without reports are skipped.
+    pub fn observe(&mut self, satellites: &[SatelliteInfo], now: DateTime<Utc>) -> Vec<StatusChange> {
+        let mut changes = Vec::new();
+
+        for sat in satellites.iter().filter(|s| is_watched(&self.watched, s)) {
+            let Some(status) = sat.latest_status() else {
+                continue;
+            };
+
+            let Some(state) = self.states.get_mut(&sat.name) else {
+                self.states.insert(
+                    sat.name.clone(),
+                    WatchState { confirmed: status, pending: None },
+                );
+                continue;
+            };
+
+            if status == state.confirmed {
+                state.pending = None;
+                continue;
+            }
+
+            let since = match state.pending {
+                Some((pending, since)) if pending == status => since,
+                _ => now,
+            };
+            if now - since >= self.debounce {
+                changes.push(StatusChange {
+                    satellite: sat.name.clone(),
+                    previous: state.confirmed,
+                    current: status,
+                });
+                state.confirmed = status;
+                state.pending = None;
+            } else {
+                state.pending = Some((status, since));
+            }
+        }
+
+        changes
+    }
+}
+
+/// Whether a satellite matches the watchlist by name or alias
+fn is_watched(watched: &[String], sat: &SatelliteInfo) -> bool {
+    std::iter::once(&sat.name)
+        .chain(sat.aliases.iter())
+        .any(|name| watched.contains(&name.to_lowercase()))
+}
+
+/// Runs the watcher after updates and forwards changes to the service
+#[derive(Clone)]
+pub struct StatusNotifier {
+    watcher: Arc<Mutex<StatusWatcher>>,
+    tx: mpsc::Sender<StatusChange>,
+}
+
+impl StatusNotifier {
+    pub fn new(watcher: StatusWatcher, tx: mpsc::Sender<StatusChange>) -> Self {
+        Self {
+            watcher: Arc::new(Mutex::new(watcher)),
+            tx,
+        }
+    }
+
+    /// Check the manager's current data and send any status changes
+    pub async fn check(&self, manager: &SatelliteManager) {
+        let satellites = manager.get_all_satellites().await;
+        let changes = self.watcher.lock().await.observe(&satellites, Utc::now());
+
+        for change in changes {
+            tracing::info!("{}", change.message());
+            if let Err(e) = self.tx.send(change).await {
+                tracing::warn!("Dropping status change notification: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::sat::{AmsatReport, SatelliteDataBlock};
+
+    fn sat_with_status(report: &str) -> SatelliteInfo {
+        let mut sat = SatelliteInfo::new("AO-91");
+        sat.data_blocks.push(SatelliteDataBlock {
+            time: "2026-02-16T08:00:00Z".to_string(),
+            reports: vec![AmsatReport {
+                report: report.to_string(),
+                reported_time: "2026-02-16T08:10:00Z".to_string(),
+                ..Default::default()
+            }],
+        });
+        sat
+    }
+
+    fn watcher(debounce_minutes: u64) -> StatusWatcher {
+        StatusWatcher::new(&WatchlistConfig {
+            satellites: vec!["ao-91".to_string()],
+            debounce_minutes,
+        })
+    }
+
+    #[test]
+    fn test_one_notification_per_change() {
+        let mut watcher = watcher(30);
+        let t0 = Utc::now();
+        let at = |minutes| t0 + Duration::minutes(minutes);
+
+        // Baseline is not announced
+        assert!(watcher.observe(&[sat_with_status("Not Heard")], at(0)).is_empty());
+
+        // The new status must persist through the debounce window
+        assert!(watcher.observe(&[sat_with_status("Heard")], at(15)).is_empty());
+        assert!(watcher.observe(&[sat_with_status("Heard")], at(30)).is_empty());
+        let changes = watcher.observe(&[sat_with_status("Heard")], at(45));
+        assert_eq!(
+            changes,
+            vec![StatusChange {
+                satellite: "AO-91".to_string(),
+                previous: ReportStatus::Red,
+                current: ReportStatus::Blue,
+            }]
+        );
+
+        // No repeat while the status holds
+        assert!(watcher.observe(&[sat_with_status("Heard")], at(60)).is_empty());
+        assert!(watcher.observe(&[sat_with_status("Heard")], at(120)).is_empty());
+    }
+
+    #[test]
+    fn test_rapid_flips_are_debounced() {
+        let mut watcher = watcher(30);
+        let t0 = Utc::now();
+        let at = |minutes| t0 + Duration::minutes(minutes);
+
+        watcher.observe(&[sat_with_status("Not Heard")], at(0));
+        for (minute, status) in [(10, "Heard"), (20, "Not Heard"), (30, "Heard"), (40, "Not Heard")] {
+            assert!(watcher.observe(&[sat_with_status(status)], at(minute)).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_unwatched_satellites_ignored() {
+        let mut watcher = watcher(0);
+        let mut other = sat_with_status("Not Heard");
+        other.name = "SO-50".to_string();
+        watcher.observe(&[other.clone()], Utc::now());
+
+        other.data_blocks[0].reports[0].report = "Heard".to_string();
+        assert!(watcher.observe(&[other], Utc::now()).is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{RwLock, mpsc};
 use tonic::{Request, Response, Status, Code};
 use tokio_stream::wrappers::ReceiverStream;
@@ -9,19 +10,35 @@ use rinko_common::proto::{
     bot_backend_server::BotBackend,
     UnifiedMessage,
     MessageResponse,
+    ReportSubmission,
+    SatelliteQueryBatch,
+    RenderImageRequest,
+    ImageResponse,
     BotCommand,
     SubscribeRequest,
     HeartbeatRequest,
     HeartbeatResponse,
+    StatusRequest,
+    StatusResponse,
+    SourceError as ProtoSourceError,
     ContentType,
 };
 use rinko_common::Platform;
 
 use crate::module::handler::MessageHandler;
+use crate::module::report_limit::ReportRateLimiter;
 use crate::module::sat::SatelliteManager;
+use crate::module::daily_post::DailyPost;
+use crate::module::subscription::{SubscriptionPost, SubscriptionStore};
+use crate::module::watchlist::StatusChange;
+use crate::config::{RenderConfig, ReportLimitConfig};
+
+/// Default deadline for handling a single request
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// Frontend connection info
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 struct FrontendConnection {
     frontend_id: String,
     platforms: Vec<Platform>,
@@ -34,18 +51,76 @@ pub struct BotBackendService {
     frontends: Arc<RwLock<HashMap<String, FrontendConnection>>>,
     // Message handler
     message_handler: Arc<MessageHandler>,
+    // Satellite data, for source health in heartbeats
+    satellite_manager: Arc<SatelliteManager>,
+    // Deadline for handling a single request
+    request_timeout: Duration,
+    // Flood protection for report submissions
+    report_limiter: ReportRateLimiter,
+    // Reported as uptime by `/status`
+    started_at: chrono::DateTime<chrono::Utc>,
 }
 
 impl BotBackendService {
-    pub fn new(satellite_manager: Arc<SatelliteManager>) -> Self {
-        let message_handler = Arc::new(MessageHandler::new(satellite_manager));
+    pub fn new(satellite_manager: Arc<SatelliteManager>, render_config: RenderConfig) -> Self {
+        let message_handler = Arc::new(MessageHandler::new(satellite_manager.clone(), render_config));
         
         Self {
             frontends: Arc::new(RwLock::new(HashMap::new())),
             message_handler,
+            satellite_manager,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            report_limiter: ReportRateLimiter::new(ReportLimitConfig::default()),
+            started_at: chrono::Utc::now(),
         }
     }
 
+    /// Set the per-user/per-group limits for report submissions
+    pub fn with_report_limits(mut self, limits: ReportLimitConfig) -> Self {
+        self.report_limiter = ReportRateLimiter::new(limits);
+        self
+    }
+
+    /// Set the deadline for handling a single request
+    ///
+    /// Requests exceeding it fail with `DEADLINE_EXCEEDED` so the frontend
+    /// can fall back to local processing instead of hanging.
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Keep `/subscribe` subscriptions in `store`
+    pub fn with_subscriptions(mut self, store: Arc<SubscriptionStore>) -> Self {
+        self.message_handler = Arc::new((*self.message_handler).clone().with_subscriptions(store));
+        self
+    }
+
+    /// Validate, rate-limit and record one report submission
+    async fn handle_report_submission(&self, submission: &ReportSubmission) -> anyhow::Result<MessageResponse> {
+        let report = match self.message_handler.validate_report(submission).await {
+            Ok(report) => report,
+            Err(rejection) => return Ok(rejection),
+        };
+
+        // Only valid submissions count against the limits
+        let group = submission.metadata.get("group_openid").map(String::as_str);
+        if let Err(retry_after) = self.report_limiter.check(&submission.submitter, group) {
+            warn!("Throttling report submissions from {}", submission.submitter);
+            return Ok(MessageResponse {
+                success: false,
+                message: format!(
+                    "Too many reports, please try again in {} minute(s).",
+                    retry_after.as_secs().div_ceil(60).max(1)
+                ),
+                message_id: uuid::Uuid::now_v7().to_string(),
+                content_type: ContentType::Text as i32,
+            });
+        }
+
+        self.message_handler.record_report(report).await
+    }
+
     /// Send a command to a specific frontend
     pub async fn send_command_to_frontend(
         &self,
@@ -68,13 +143,79 @@ impl BotBackendService {
 
     /// Broadcast a command to all connected frontends
     pub async fn broadcast_command(&self, command: BotCommand) {
-        let frontends = self.frontends.read().await;
-        
-        for (frontend_id, connection) in frontends.iter() {
-            if let Err(e) = connection.command_tx.send(Ok(command.clone())).await {
-                warn!("Failed to send command to frontend {}: {}", frontend_id, e);
+        broadcast(&self.frontends, command).await;
+    }
+
+    /// Broadcast watched satellite status changes as `status_change` commands
+    pub fn forward_status_changes(&self, mut rx: mpsc::Receiver<StatusChange>) {
+        let frontends = self.frontends.clone();
+        tokio::spawn(async move {
+            while let Some(change) = rx.recv().await {
+                let parameters = HashMap::from([
+                    ("satellite".to_string(), change.satellite.clone()),
+                    ("previous".to_string(), change.previous.to_string()),
+                    ("current".to_string(), change.current.to_string()),
+                    ("message".to_string(), change.message()),
+                ]);
+                let command = BotCommand {
+                    command_id: uuid::Uuid::now_v7().to_string(),
+                    command_type: "status_change".to_string(),
+                    parameters,
+                    timestamp: chrono::Utc::now().timestamp(),
+                };
+                broadcast(&frontends, command).await;
             }
-        }
+        });
+    }
+
+    /// Broadcast satellite-of-the-day posts as `satellite_of_the_day` commands
+    pub fn forward_daily_posts(&self, mut rx: mpsc::Receiver<DailyPost>) {
+        let frontends = self.frontends.clone();
+        tokio::spawn(async move {
+            while let Some(post) = rx.recv().await {
+                let mut parameters = HashMap::from([
+                    ("satellite".to_string(), post.satellite),
+                    ("message".to_string(), post.message),
+                ]);
+                if let Some(image) = post.image {
+                    parameters.insert("image".to_string(), image);
+                }
+                let command = BotCommand {
+                    command_id: uuid::Uuid::now_v7().to_string(),
+                    command_type: "satellite_of_the_day".to_string(),
+                    parameters,
+                    timestamp: chrono::Utc::now().timestamp(),
+                };
+                broadcast(&frontends, command).await;
+            }
+        });
+    }
+
+    /// Send subscription posts as `subscription_post` commands
+    ///
+    /// `groups` lists the comma-separated openids the post is for; frontends
+    /// deliver it to those groups only.
+    pub fn forward_subscription_posts(&self, mut rx: mpsc::Receiver<SubscriptionPost>) {
+        let frontends = self.frontends.clone();
+        tokio::spawn(async move {
+            while let Some(post) = rx.recv().await {
+                let mut parameters = HashMap::from([
+                    ("satellite".to_string(), post.satellite),
+                    ("groups".to_string(), post.groups.join(",")),
+                    ("message".to_string(), post.message),
+                ]);
+                if let Some(image) = post.image {
+                    parameters.insert("image".to_string(), image);
+                }
+                let command = BotCommand {
+                    command_id: uuid::Uuid::now_v7().to_string(),
+                    command_type: "subscription_post".to_string(),
+                    parameters,
+                    timestamp: chrono::Utc::now().timestamp(),
+                };
+                broadcast(&frontends, command).await;
+            }
+        });
     }
 
     /// Get list of connected frontends
@@ -84,6 +225,20 @@ impl BotBackendService {
     }
 }
 
+/// Send a command to every connected frontend
+async fn broadcast(
+    frontends: &RwLock<HashMap<String, FrontendConnection>>,
+    command: BotCommand,
+) {
+    let frontends = frontends.read().await;
+
+    for (frontend_id, connection) in frontends.iter() {
+        if let Err(e) = connection.command_tx.send(Ok(command.clone())).await {
+            warn!("Failed to send command to frontend {}: {}", frontend_id, e);
+        }
+    }
+}
+
 #[tonic::async_trait]
 impl BotBackend for BotBackendService {
     /// Handle message reports from frontend
@@ -100,10 +255,23 @@ impl BotBackend for BotBackendService {
             &msg.content.chars().take(50).collect::<String>()
         );
 
-        // Process the message through handler
-        let response = match self.message_handler.handle_message(&msg).await {
-            Ok(resp) => resp,
-            Err(e) => {
+        // Process the message through handler, bounded by the request deadline
+        let handled =
+            tokio::time::timeout(self.request_timeout, self.message_handler.handle_message(&msg))
+                .await;
+        let response = match handled {
+            Err(_) => {
+                warn!(
+                    "Handling event {} exceeded {:?}, cancelling",
+                    msg.event_id, self.request_timeout
+                );
+                return Err(Status::deadline_exceeded(format!(
+                    "Request not handled within {:?}",
+                    self.request_timeout
+                )));
+            }
+            Ok(Ok(resp)) => resp,
+            Ok(Err(e)) => {
                 error!("Failed to handle message: {}", e);
                 MessageResponse {
                     success: false,
@@ -117,6 +285,87 @@ impl BotBackend for BotBackendService {
         Ok(Response::new(response))
     }
 
+    /// Handle a user-submitted satellite report
+    async fn submit_report(
+        &self,
+        request: Request<ReportSubmission>,
+    ) -> Result<Response<MessageResponse>, Status> {
+        let submission = request.into_inner();
+
+        info!(
+            "Report submission for '{}' ({}) from {}",
+            submission.satellite, submission.status, submission.submitter
+        );
+
+        match tokio::time::timeout(self.request_timeout, self.handle_report_submission(&submission)).await {
+            Ok(Ok(response)) => Ok(Response::new(response)),
+            Ok(Err(e)) => {
+                error!("Failed to handle report submission: {}", e);
+                Err(Status::internal(format!("Failed to submit report: {}", e)))
+            }
+            Err(_) => {
+                warn!("Report submission from {} exceeded {:?}", submission.submitter, self.request_timeout);
+                Err(Status::deadline_exceeded(format!(
+                    "Request not handled within {:?}",
+                    self.request_timeout
+                )))
+            }
+        }
+    }
+
+    /// Render several satellite queries into one image
+    async fn query_satellites(
+        &self,
+        request: Request<SatelliteQueryBatch>,
+    ) -> Result<Response<MessageResponse>, Status> {
+        let batch = request.into_inner();
+        info!("Batch query for {:?}", batch.queries);
+
+        match tokio::time::timeout(self.request_timeout, self.message_handler.query_satellites(&batch.queries)).await {
+            Ok(Ok(response)) => Ok(Response::new(response)),
+            Ok(Err(e)) => {
+                error!("Failed to handle batch query: {}", e);
+                Err(Status::internal(format!("Failed to query satellites: {}", e)))
+            }
+            Err(_) => {
+                warn!("Batch query {:?} exceeded {:?}", batch.queries, self.request_timeout);
+                Err(Status::deadline_exceeded(format!(
+                    "Request not handled within {:?}",
+                    self.request_timeout
+                )))
+            }
+        }
+    }
+
+    /// Render a query's image and return it inline
+    async fn render_satellite_image(
+        &self,
+        request: Request<RenderImageRequest>,
+    ) -> Result<Response<ImageResponse>, Status> {
+        let render = request.into_inner();
+        info!("Image render for {:?}", render.query);
+
+        match tokio::time::timeout(
+            self.request_timeout,
+            self.message_handler.render_image(&render.query, &render.options),
+        )
+        .await
+        {
+            Ok(Ok(response)) => Ok(Response::new(response)),
+            Ok(Err(e)) => {
+                error!("Failed to render image: {}", e);
+                Err(Status::internal(format!("Failed to render image: {}", e)))
+            }
+            Err(_) => {
+                warn!("Image render {:?} exceeded {:?}", render.query, self.request_timeout);
+                Err(Status::deadline_exceeded(format!(
+                    "Request not handled within {:?}",
+                    self.request_timeout
+                )))
+            }
+        }
+    }
+
     /// Server streaming: Send commands to frontend
     type SubscribeCommandsStream = ReceiverStream<Result<BotCommand, Status>>;
 
@@ -175,6 +424,23 @@ impl BotBackend for BotBackendService {
         Ok(Response::new(ReceiverStream::new(rx)))
     }
 
+    /// Report version, uptime and satellite data freshness
+    async fn get_status(
+        &self,
+        request: Request<StatusRequest>,
+    ) -> Result<Response<StatusResponse>, Status> {
+        debug!("Status request from frontend {}", request.into_inner().frontend_id);
+
+        let response = StatusResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            started_at: self.started_at.timestamp(),
+            last_amsat_update: self.satellite_manager.last_update().await.map_or(0, |t| t.timestamp()),
+            tracked_satellites: self.satellite_manager.satellite_count().await as u32,
+        };
+
+        Ok(Response::new(response))
+    }
+
     /// Handle heartbeat from frontend
     async fn heartbeat(
         &self,
@@ -187,9 +453,22 @@ impl BotBackend for BotBackendService {
             heartbeat_req.frontend_id, heartbeat_req.status
         );
 
+        let source_errors = self
+            .satellite_manager
+            .source_errors()
+            .await
+            .into_iter()
+            .map(|e| ProtoSourceError {
+                source: e.source.as_str().to_string(),
+                timestamp: e.time.timestamp(),
+                message: e.message,
+            })
+            .collect();
+
         let response = HeartbeatResponse {
             healthy: true,
             message: "Backend is healthy".to_string(),
+            source_errors,
         };
 
         Ok(Response::new(response))
@@ -206,3 +485,283 @@ impl BotBackend for BotBackendService {
         Err(Status::new(Code::Unimplemented, "Not implemented yet"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::sat::test_support::manager_with_names;
+
+    #[tokio::test]
+    async fn test_report_submissions_are_throttled() {
+        let manager = manager_with_names("rinko_test_service_throttle", &["AO-91"]).await;
+        let service = BotBackendService::new(manager, RenderConfig::default())
+            .with_report_limits(ReportLimitConfig {
+                max_per_user: 2,
+                ..Default::default()
+            });
+
+        let submit = || {
+            service.submit_report(Request::new(ReportSubmission {
+                satellite: "AO-91".to_string(),
+                status: "heard".to_string(),
+                grid_square: "JN58".to_string(),
+                callsign: "W1AW".to_string(),
+                submitter: "qq:flooder".to_string(),
+                ..Default::default()
+            }))
+        };
+
+        assert!(submit().await.unwrap().into_inner().success);
+        assert!(submit().await.unwrap().into_inner().success);
+        let throttled = submit().await.unwrap().into_inner();
+        assert!(!throttled.success);
+        assert!(throttled.message.contains("Too many reports"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_submissions_do_not_use_quota() {
+        let manager = manager_with_names("rinko_test_service_throttle_invalid", &["AO-91"]).await;
+        let service = BotBackendService::new(manager, RenderConfig::default())
+            .with_report_limits(ReportLimitConfig {
+                max_per_user: 1,
+                ..Default::default()
+            });
+
+        let submit = |grid: &str| {
+            service.submit_report(Request::new(ReportSubmission {
+                satellite: "AO-91".to_string(),
+                status: "heard".to_string(),
+                grid_square: grid.to_string(),
+                callsign: "W1AW".to_string(),
+                submitter: "qq:typo".to_string(),
+                ..Default::default()
+            }))
+        };
+
+        for _ in 0..3 {
+            let rejected = submit("ZZ99").await.unwrap().into_inner();
+            assert!(rejected.message.contains("Invalid grid square"), "{}", rejected.message);
+        }
+        assert!(submit("JN58").await.unwrap().into_inner().success);
+        let throttled = submit("JN58").await.unwrap().into_inner();
+        assert!(throttled.message.contains("Too many reports"), "{}", throttled.message);
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_version_and_satellites() {
+        let manager = manager_with_names("rinko_test_service_status", &["AO-91", "SO-50"]).await;
+        let service = BotBackendService::new(manager.clone(), RenderConfig::default());
+
+        let status = service
+            .get_status(Request::new(StatusRequest { frontend_id: "qq-1".to_string() }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(status.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(status.tracked_satellites, 2);
+        assert_eq!(status.last_amsat_update, manager.last_update().await.unwrap().timestamp());
+        assert!(status.started_at <= chrono::Utc::now().timestamp());
+    }
+
+    #[tokio::test]
+    async fn test_report_without_callsign_is_rejected() {
+        let manager = manager_with_names("rinko_test_service_no_callsign", &["AO-91"]).await;
+        let service = BotBackendService::new(manager.clone(), RenderConfig::default());
+
+        let response = service
+            .submit_report(Request::new(ReportSubmission {
+                satellite: "AO-91".to_string(),
+                status: "heard".to_string(),
+                grid_square: "JN58".to_string(),
+                submitter: "qq:member-openid".to_string(),
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(!response.success);
+        assert!(response.message.contains("callsign is required"), "{}", response.message);
+
+        let sat = manager.query_satellite("AO-91").await.unwrap().unwrap();
+        assert!(sat.data_blocks.iter().all(|b| b.reports.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_batch_query_renders_all_satellites_in_one_image() {
+        let manager = manager_with_names("rinko_test_service_batch", &["AO-91", "SO-50", "FO-29"]).await;
+        let output_dir = std::env::temp_dir().join("rinko_test_service_batch_images");
+        let _ = tokio::fs::remove_dir_all(&output_dir).await;
+        crate::module::sat::ensure_images_dir(&output_dir).await.unwrap();
+        let render_config = RenderConfig {
+            output_dir: output_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let service = BotBackendService::new(manager, render_config);
+
+        let batch = SatelliteQueryBatch {
+            queries: vec!["ao-91".to_string(), "so-50".to_string(), "AO-91".to_string()],
+            ..Default::default()
+        };
+        let response = service.query_satellites(Request::new(batch)).await.unwrap().into_inner();
+        let _ = tokio::fs::remove_dir_all(&output_dir).await;
+
+        assert!(response.success);
+        assert_eq!(response.content_type, ContentType::Image as i32);
+        // One merged image named after both satellites, each once
+        assert!(response.message.contains("_ao91_so50_"), "{}", response.message);
+        assert!(response.message.ends_with("_merged.png"), "{}", response.message);
+    }
+
+    #[tokio::test]
+    async fn test_group_subscription_commands() {
+        let manager = manager_with_names("rinko_test_service_subscriptions", &["AO-91", "SO-50"]).await;
+        let store = Arc::new(SubscriptionStore::default());
+        let service = BotBackendService::new(manager, RenderConfig::default()).with_subscriptions(store.clone());
+
+        let send = |content: &str, group: Option<&str>| {
+            let metadata = group
+                .map(|g| HashMap::from([("group_openid".to_string(), g.to_string())]))
+                .unwrap_or_default();
+            service.report_message(Request::new(UnifiedMessage {
+                content: content.to_string(),
+                metadata,
+                ..Default::default()
+            }))
+        };
+
+        let subscribed = send("/subscribe ao-91 daily", Some("g1")).await.unwrap().into_inner();
+        assert!(subscribed.success, "{}", subscribed.message);
+        assert!(send("/sub so-50", Some("g1")).await.unwrap().into_inner().success);
+        let listed = send("/subscriptions", Some("g1")).await.unwrap().into_inner();
+        assert!(listed.message.contains("AO-91 - daily"), "{}", listed.message);
+        assert!(listed.message.contains("SO-50 - hourly"), "{}", listed.message);
+        assert!(store.list("g2").await.is_empty());
+
+        assert!(send("/unsubscribe AO-91", Some("g1")).await.unwrap().into_inner().success);
+        assert!(!send("/unsubscribe AO-91", Some("g1")).await.unwrap().into_inner().success);
+        let remaining: Vec<String> = store.list("g1").await.into_iter().map(|s| s.satellite).collect();
+        assert_eq!(remaining, vec!["SO-50"]);
+
+        // Outside a group there is nothing to post to
+        let direct = send("/subscribe ao-91", None).await.unwrap().into_inner();
+        assert!(!direct.success);
+        assert!(direct.message.contains("only available in groups"));
+    }
+
+    #[tokio::test]
+    async fn test_blank_query_gets_usage_not_no_match() {
+        let manager = manager_with_names("rinko_test_service_blank_query", &["AO-91"]).await;
+        let output_dir = std::env::temp_dir().join("rinko_test_service_blank_query_images");
+        let _ = tokio::fs::remove_dir_all(&output_dir).await;
+        crate::module::sat::ensure_images_dir(&output_dir).await.unwrap();
+        let render_config = RenderConfig {
+            output_dir: output_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let service = BotBackendService::new(manager, render_config);
+
+        let query = |content: &str| {
+            service.report_message(Request::new(UnifiedMessage {
+                content: content.to_string(),
+                ..Default::default()
+            }))
+        };
+        let empty = query("/q").await.unwrap().into_inner();
+        let whitespace = query("/q  \u{3000}\u{200B} ").await.unwrap().into_inner();
+        let unknown = query("/q NOPE-1").await.unwrap().into_inner();
+        let batch = service
+            .query_satellites(Request::new(SatelliteQueryBatch {
+                queries: vec!["".to_string(), "  ".to_string()],
+                ..Default::default()
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let _ = tokio::fs::remove_dir_all(&output_dir).await;
+
+        for usage in [&empty, &whitespace, &batch] {
+            assert!(!usage.success);
+            assert_eq!(usage.content_type, ContentType::Text as i32);
+            assert!(usage.message.starts_with("Usage: /q"), "{}", usage.message);
+        }
+        // A real name that matches nothing gets the no-results image instead
+        assert!(!unknown.success);
+        assert_eq!(unknown.content_type, ContentType::Image as i32, "{}", unknown.message);
+    }
+
+    #[tokio::test]
+    async fn test_render_image_returns_png_bytes() {
+        let manager = manager_with_names("rinko_test_service_render_image", &["AO-91"]).await;
+        let output_dir = std::env::temp_dir().join("rinko_test_service_render_image_images");
+        let _ = tokio::fs::remove_dir_all(&output_dir).await;
+        let render_config = RenderConfig {
+            output_dir: output_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let service = BotBackendService::new(manager, render_config);
+
+        let render = RenderImageRequest {
+            query: "ao-91".to_string(),
+            options: HashMap::from([("layout".to_string(), "compact".to_string())]),
+        };
+        let response = service.render_satellite_image(Request::new(render)).await.unwrap().into_inner();
+
+        assert!(response.success, "{}", response.message);
+        assert_eq!(response.content_type, "image/png");
+        assert!(response.image.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // Nothing was written for the media server
+        assert!(!output_dir.exists());
+
+        let render = RenderImageRequest {
+            query: "ao-91".to_string(),
+            options: HashMap::from([("layout".to_string(), "poster".to_string())]),
+        };
+        let response = service.render_satellite_image(Request::new(render)).await.unwrap().into_inner();
+        assert!(!response.success);
+        assert!(response.image.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_slow_request_returns_deadline_exceeded() {
+        let manager = manager_with_names("rinko_test_service_deadline", &["AO-91"]).await;
+        // Rasterizing on the blocking pool always takes longer than this
+        let service = BotBackendService::new(manager, RenderConfig::default())
+            .with_request_timeout(Duration::from_nanos(1));
+
+        let msg = UnifiedMessage {
+            content: "/q AO-91".to_string(),
+            ..Default::default()
+        };
+        let status = service
+            .report_message(Request::new(msg))
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+    }
+
+    #[tokio::test]
+    async fn test_renders_land_in_configured_output_dir() {
+        let manager = manager_with_names("rinko_test_service_output_dir", &["AO-91"]).await;
+        let output_dir = std::env::temp_dir().join("rinko_test_service_output_dir_images");
+        let _ = tokio::fs::remove_dir_all(&output_dir).await;
+        crate::module::sat::ensure_images_dir(&output_dir).await.unwrap();
+        let render_config = RenderConfig {
+            output_dir: output_dir.to_string_lossy().to_string(),
+            ..Default::default()
+        };
+        let service = BotBackendService::new(manager, render_config);
+
+        let msg = UnifiedMessage {
+            content: "/q AO-91".to_string(),
+            ..Default::default()
+        };
+        let response = service.report_message(Request::new(msg)).await.unwrap().into_inner();
+
+        assert_eq!(response.content_type, ContentType::Image as i32, "{}", response.message);
+        let path = std::path::PathBuf::from(response.message.trim_start_matches("file:///"));
+        let path = if path.is_absolute() { path } else { std::path::Path::new("/").join(path) };
+        assert_eq!(path.parent(), Some(output_dir.as_path()));
+        assert!(path.exists());
+    }
+}
@@ -0,0 +1,31 @@
+//! Outbound HTTP client settings shared by the frontend and backend
+//!
+//! `reqwest::Client` keeps its connection pool internally, so each process
+//! builds one client here and reuses it for every request.
+use std::time::Duration;
+
+const CONNECT_TIMEOUT_SECONDS: u64 = 10;
+/// Idle pooled connections are closed after this long
+const POOL_IDLE_TIMEOUT_SECONDS: u64 = 90;
+const POOL_MAX_IDLE_PER_HOST: usize = 8;
+const TCP_KEEPALIVE_SECONDS: u64 = 60;
+
+/// Build a client with the shared timeouts and pool settings
+///
+/// Every request goes through `proxy` when set; without one reqwest reads
+/// the proxy environment variables.
+pub fn build_client(
+    request_timeout: Duration,
+    proxy: Option<reqwest::Proxy>,
+) -> reqwest::Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder()
+        .timeout(request_timeout)
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECONDS))
+        .pool_idle_timeout(Duration::from_secs(POOL_IDLE_TIMEOUT_SECONDS))
+        .pool_max_idle_per_host(POOL_MAX_IDLE_PER_HOST)
+        .tcp_keepalive(Duration::from_secs(TCP_KEEPALIVE_SECONDS));
+    if let Some(proxy) = proxy {
+        builder = builder.proxy(proxy);
+    }
+    builder.build()
+}
@@ -1,3 +1,4 @@
+pub mod http_client;
 pub mod proto;
 pub mod types;
 
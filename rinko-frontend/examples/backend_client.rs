@@ -9,10 +9,11 @@ use tonic::{Request, Response, Status};
 use tracing_subscriber::EnvFilter;
 use uuid::Uuid;
 
-use rinko_frontend::backend::proto::{
+use rinko_common::proto::{
     bot_backend_server::{BotBackend, BotBackendServer},
-    BotCommand, HeartbeatRequest, HeartbeatResponse, MessageResponse, Platform, SubscribeRequest,
-    UnifiedMessage,
+    BotCommand, ContentType, HeartbeatRequest, HeartbeatResponse, ImageResponse, MessageResponse,
+    Platform, RenderImageRequest, ReportSubmission, SatelliteQueryBatch, StatusRequest,
+    StatusResponse, SubscribeRequest, UnifiedMessage,
 };
 
 #[derive(Default)]
@@ -43,11 +44,61 @@ impl BotBackend for MockBackend {
             success: true,
             message: reply,
             message_id: Uuid::now_v7().to_string(),
+            content_type: ContentType::Text as i32,
         };
 
         Ok(Response::new(response))
     }
 
+    async fn submit_report(
+        &self,
+        request: Request<ReportSubmission>,
+    ) -> Result<Response<MessageResponse>, Status> {
+        let report = request.into_inner();
+        tracing::info!(
+            "SubmitReport: satellite={}, status={}, grid={}",
+            report.satellite,
+            report.status,
+            report.grid_square
+        );
+
+        Ok(Response::new(MessageResponse {
+            success: true,
+            message: format!("Report for {} received", report.satellite),
+            message_id: Uuid::now_v7().to_string(),
+            content_type: ContentType::Text as i32,
+        }))
+    }
+
+    async fn query_satellites(
+        &self,
+        request: Request<SatelliteQueryBatch>,
+    ) -> Result<Response<MessageResponse>, Status> {
+        let batch = request.into_inner();
+        tracing::info!("QuerySatellites: queries={:?}", batch.queries);
+
+        Ok(Response::new(MessageResponse {
+            success: true,
+            message: format!("Lineup: {}", batch.queries.join(", ")),
+            message_id: Uuid::now_v7().to_string(),
+            content_type: ContentType::Text as i32,
+        }))
+    }
+
+    async fn render_satellite_image(
+        &self,
+        request: Request<RenderImageRequest>,
+    ) -> Result<Response<ImageResponse>, Status> {
+        let render = request.into_inner();
+        tracing::info!("RenderSatelliteImage: query={}", render.query);
+
+        Ok(Response::new(ImageResponse {
+            success: false,
+            message: "The mock backend does not render images".to_string(),
+            ..Default::default()
+        }))
+    }
+
     type SubscribeCommandsStream = ReceiverStream<Result<BotCommand, Status>>;
 
     async fn subscribe_commands(
@@ -87,6 +138,20 @@ impl BotBackend for MockBackend {
         Ok(Response::new(ReceiverStream::new(rx)))
     }
 
+    async fn get_status(
+        &self,
+        request: Request<StatusRequest>,
+    ) -> Result<Response<StatusResponse>, Status> {
+        tracing::info!("Status request: frontend_id={}", request.into_inner().frontend_id);
+
+        Ok(Response::new(StatusResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            started_at: Utc::now().timestamp(),
+            last_amsat_update: 0,
+            tracked_satellites: 0,
+        }))
+    }
+
     async fn heartbeat(
         &self,
         request: Request<HeartbeatRequest>,
@@ -101,6 +166,7 @@ impl BotBackend for MockBackend {
         Ok(Response::new(HeartbeatResponse {
             healthy: true,
             message: "OK".to_string(),
+            source_errors: Vec::new(),
         }))
     }
 
@@ -141,7 +207,7 @@ async fn main() -> Result<()> {
     tracing::info!("Mock backend listening on {}", addr);
 
     tonic::transport::Server::builder()
-        .add_service(BotBackendServer::new(MockBackend::default()))
+        .add_service(BotBackendServer::new(MockBackend))
         .serve(addr)
         .await?;
 
@@ -9,10 +9,16 @@ use rinko_common::proto::{
     bot_backend_client::BotBackendClient,
     UnifiedMessage as ProtoUnifiedMessage,
     MessageResponse,
+    ReportSubmission,
+    SatelliteQueryBatch,
+    RenderImageRequest,
+    ImageResponse,
     BotCommand,
     SubscribeRequest,
     HeartbeatRequest,
     HeartbeatResponse,
+    StatusRequest,
+    StatusResponse,
 };
 use rinko_common::Platform;
 use crate::utils::UnifiedMessage;
@@ -50,6 +56,27 @@ impl BackendClient {
         Ok(response.into_inner())
     }
 
+    /// Submit a user's satellite report to the backend
+    pub async fn submit_report(&mut self, submission: ReportSubmission) -> Result<MessageResponse> {
+        let response = self.client.submit_report(Request::new(submission)).await?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Query several satellites at once, answered with one merged image
+    pub async fn query_satellites(&mut self, batch: SatelliteQueryBatch) -> Result<MessageResponse> {
+        let response = self.client.query_satellites(Request::new(batch)).await?;
+
+        Ok(response.into_inner())
+    }
+
+    /// Render a query's image and receive the PNG bytes inline
+    pub async fn render_satellite_image(&mut self, request: RenderImageRequest) -> Result<ImageResponse> {
+        let response = self.client.render_satellite_image(Request::new(request)).await?;
+
+        Ok(response.into_inner())
+    }
+
     /// Subscribe to commands from backend (server streaming)
     pub async fn subscribe_commands(&mut self, platforms: Vec<Platform>) -> Result<tonic::Streaming<BotCommand>> {
         let proto_platforms: Vec<i32> = platforms
@@ -67,6 +94,17 @@ impl BackendClient {
         Ok(response.into_inner())
     }
 
+    /// Fetch the backend's version, uptime and satellite data freshness
+    pub async fn get_status(&mut self) -> Result<StatusResponse> {
+        let request = Request::new(StatusRequest {
+            frontend_id: self.frontend_id.clone(),
+        });
+
+        let response = self.client.get_status(request).await?;
+
+        Ok(response.into_inner())
+    }
+
     /// Send heartbeat to backend
     pub async fn heartbeat(&mut self, status: HashMap<String, String>) -> Result<HeartbeatResponse> {
         let request = Request::new(HeartbeatRequest {
@@ -1,10 +1,11 @@
 use anyhow::Result;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, RwLock};
 use tokio::time::{sleep, Duration};
 
 use crate::backend::client::BackendClient;
 use crate::config::BackendConfig;
+use crate::notify::Notification;
 
 /// Connection state
 #[derive(Debug, Clone, PartialEq)]
@@ -19,18 +20,26 @@ pub struct BackendConnectionManager {
     config: BackendConfig,
     client: Arc<RwLock<Option<BackendClient>>>,
     state: Arc<RwLock<ConnectionState>>,
+    notifications: broadcast::Sender<Notification>,
 }
 
 impl BackendConnectionManager {
     /// Create a new connection manager
     pub fn new(config: BackendConfig) -> Self {
+        let (notifications, _) = broadcast::channel(32);
         Self {
             config,
             client: Arc::new(RwLock::new(None)),
             state: Arc::new(RwLock::new(ConnectionState::Disconnected)),
+            notifications,
         }
     }
 
+    /// Receive notifications pushed by the backend (e.g. status changes)
+    pub fn subscribe_notifications(&self) -> broadcast::Receiver<Notification> {
+        self.notifications.subscribe()
+    }
+
     /// Get the current client (if connected)
     pub fn client(&self) -> Arc<RwLock<Option<BackendClient>>> {
         self.client.clone()
@@ -125,24 +134,32 @@ impl BackendConnectionManager {
                 
                 let state = self.state.read().await.clone();
                 
-                if state == ConnectionState::Connected {
-                    if let Some(client) = &mut *self.client.write().await {
-                        let mut status = std::collections::HashMap::new();
-                        status.insert("status".to_string(), "healthy".to_string());
-                        
-                        match client.heartbeat(status).await {
-                            Ok(response) => {
-                                if response.healthy {
-                                    tracing::debug!("Heartbeat sent successfully");
-                                } else {
-                                    tracing::warn!("Backend reported unhealthy: {}", response.message);
-                                }
+                if state == ConnectionState::Connected
+                    && let Some(client) = &mut *self.client.write().await
+                {
+                    let mut status = std::collections::HashMap::new();
+                    status.insert("status".to_string(), "healthy".to_string());
+                    
+                    match client.heartbeat(status).await {
+                        Ok(response) => {
+                            if response.healthy {
+                                tracing::debug!("Heartbeat sent successfully");
+                            } else {
+                                tracing::warn!("Backend reported unhealthy: {}", response.message);
                             }
-                            Err(e) => {
-                                tracing::error!("Heartbeat failed: {}", e);
-                                self.mark_disconnected().await;
+                            for error in &response.source_errors {
+                                tracing::debug!(
+                                    "Backend data source {} failing since {}: {}",
+                                    error.source,
+                                    error.timestamp,
+                                    error.message
+                                );
                             }
                         }
+                        Err(e) => {
+                            tracing::error!("Heartbeat failed: {}", e);
+                            self.mark_disconnected().await;
+                        }
                     }
                 }
             }
@@ -195,6 +212,40 @@ impl BackendConnectionManager {
                                             "send_message" => {
                                                 tracing::info!("Send message command: {:?}", command.parameters);
                                             }
+                                            "status_change" | "satellite_of_the_day" => {
+                                                // No receivers just means nobody is listening
+                                                if let Some(message) = command.parameters.get("message") {
+                                                    let _ = self.notifications.send(Notification::Text(message.clone()));
+                                                }
+                                                if let Some(image) = command.parameters.get("image") {
+                                                    let _ = self.notifications.send(Notification::Image(image.clone()));
+                                                }
+                                            }
+                                            "subscription_post" => {
+                                                let groups: Vec<String> = command
+                                                    .parameters
+                                                    .get("groups")
+                                                    .map(|groups| {
+                                                        groups
+                                                            .split(',')
+                                                            .filter(|g| !g.is_empty())
+                                                            .map(str::to_string)
+                                                            .collect()
+                                                    })
+                                                    .unwrap_or_default();
+                                                if let Some(text) = command.parameters.get("message") {
+                                                    let _ = self.notifications.send(Notification::GroupText {
+                                                        groups: groups.clone(),
+                                                        text: text.clone(),
+                                                    });
+                                                }
+                                                if let Some(path) = command.parameters.get("image") {
+                                                    let _ = self.notifications.send(Notification::GroupImage {
+                                                        groups,
+                                                        path: path.clone(),
+                                                    });
+                                                }
+                                            }
                                             "shutdown" => {
                                                 tracing::warn!("Received shutdown command from backend");
                                                 // TODO: Graceful shutdown
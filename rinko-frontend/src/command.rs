@@ -7,6 +7,10 @@ pub enum CommandType {
     Image,      // \img - image commands
     Execute,    // \exec - execution commands
     Help,       // \help - help commands
+    Report,     // \report - user satellite reports
+    Compare,    // \compare - two satellites side by side
+    Subscribe,  // \subscribe, \unsubscribe, \subscriptions - periodic group posts
+    Status,     // \status, \about - bot version, uptime and backend state
     Unknown,    // Unrecognized command
 }
 
@@ -18,6 +22,11 @@ impl CommandType {
             "\\img" | "/img" => CommandType::Image,
             "\\exec" | "/exec" => CommandType::Execute,
             "\\help" | "/help" => CommandType::Help,
+            "\\report" | "/report" => CommandType::Report,
+            "\\compare" | "/compare" | "\\cmp" | "/cmp" => CommandType::Compare,
+            "\\subscribe" | "/subscribe" | "\\sub" | "/sub" | "\\unsubscribe" | "/unsubscribe" | "\\unsub"
+            | "/unsub" | "\\subscriptions" | "/subscriptions" | "\\subs" | "/subs" => CommandType::Subscribe,
+            "\\status" | "/status" | "\\about" | "/about" => CommandType::Status,
             _ => CommandType::Unknown,
         }
     }
@@ -29,6 +38,10 @@ impl CommandType {
             CommandType::Image => "image",
             CommandType::Execute => "execute",
             CommandType::Help => "help",
+            CommandType::Report => "report",
+            CommandType::Compare => "compare",
+            CommandType::Subscribe => "subscribe",
+            CommandType::Status => "status",
             CommandType::Unknown => "unknown",
         }
     }
@@ -48,6 +61,7 @@ impl ParsedCommand {
     /// 
     /// # Examples
     /// ```
+    /// # use rinko_frontend::command::{CommandType, ParsedCommand};
     /// let cmd = ParsedCommand::parse("\\q iss status");
     /// assert_eq!(cmd.command_type, CommandType::Query);
     /// assert_eq!(cmd.arguments, "iss status");
@@ -118,6 +132,37 @@ impl ParsedCommand {
     }
 }
 
+/// Arguments of a report command: `<satellite> <status> <grid> <callsign>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportArgs {
+    pub satellite: String,
+    pub status: String,
+    pub grid_square: String,
+    pub callsign: String,
+}
+
+impl ReportArgs {
+    /// Usage hint shown when the arguments cannot be parsed
+    pub const USAGE: &'static str =
+        "Usage: /report <satellite> <heard|not-heard|telemetry|crew> <grid> <callsign>";
+
+    /// Split report command arguments
+    ///
+    /// Only the shape is checked here; the backend validates the values.
+    pub fn parse(arguments: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = arguments.split_whitespace().collect();
+        match parts.as_slice() {
+            [satellite, status, grid, callsign] => Ok(Self {
+                satellite: satellite.to_string(),
+                status: status.to_string(),
+                grid_square: grid.to_string(),
+                callsign: callsign.to_string(),
+            }),
+            _ => Err(Self::USAGE.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +189,36 @@ mod tests {
         assert!(!cmd.is_command());
     }
 
+    #[test]
+    fn test_report_command() {
+        let cmd = ParsedCommand::parse("/report ao-91 heard JN58 W1AW");
+        assert_eq!(cmd.command_type, CommandType::Report);
+
+        let args = ReportArgs::parse(&cmd.arguments).unwrap();
+        assert_eq!(args.satellite, "ao-91");
+        assert_eq!(args.status, "heard");
+        assert_eq!(args.grid_square, "JN58");
+        assert_eq!(args.callsign, "W1AW");
+
+        // The callsign is required: the submitter's platform ID is never shown instead
+        assert!(ReportArgs::parse("ao-91 heard JN58").is_err());
+        assert!(ReportArgs::parse("ao-91 heard").is_err());
+    }
+
+    #[test]
+    fn test_compare_command() {
+        let cmd = ParsedCommand::parse("/compare ao-91 so-50");
+        assert_eq!(cmd.command_type, CommandType::Compare);
+        assert_eq!(cmd.arguments, "ao-91 so-50");
+        assert_eq!(ParsedCommand::parse("\\cmp ao-91 so-50").command_type, CommandType::Compare);
+    }
+
+    #[test]
+    fn test_status_command() {
+        assert_eq!(ParsedCommand::parse("/status").command_type, CommandType::Status);
+        assert_eq!(ParsedCommand::parse("\\about").command_type, CommandType::Status);
+    }
+
     #[test]
     fn test_command_no_args() {
         let cmd = ParsedCommand::parse("\\help");
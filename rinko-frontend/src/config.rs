@@ -12,14 +12,100 @@ pub struct QQConfig {
     pub app_id: String,
     pub client_secret: String,       // also used as bot_secret for webhook signature verification
     pub access_token: String,
+    #[serde(default = "default_api_base_url")]
+    pub api_base_url: String,            // QQ OpenAPI endpoint (sandbox: "https://sandbox.api.sgroup.qq.com")
     #[serde(default)]
     pub media_base_url: Option<String>,  // Base URL for media server (e.g., "https://media.rinkosoft.me/media")
+    #[serde(default = "default_max_webhook_body_bytes")]
+    pub max_webhook_body_bytes: usize,   // larger webhook bodies are rejected with 413
+    #[serde(default = "default_max_webhook_age_seconds")]
+    pub max_webhook_age_seconds: u64,    // signed events older (or further ahead) than this are rejected
+    #[serde(default)]
+    pub notify_groups: Vec<String>,      // group openids receiving satellite status-change notifications
+    #[serde(default = "default_notify_concurrency")]
+    pub notify_concurrency: usize,       // notification sends to different groups in flight at once
+    #[serde(default)]
+    pub quiet_hours: std::collections::HashMap<String, crate::frontend::quiet_hours::QuietHours>, // group openid -> daily window without notifications
+    #[serde(default = "default_offline_image_max_age_hours")]
+    pub offline_image_max_age_hours: u64, // reuse a query's last image this long while the backend is offline (0 = off)
+    #[serde(default = "default_pipeline_timeout_seconds")]
+    pub pipeline_timeout_seconds: u64,   // overall budget for answering one message, within QQ's 5-minute reply window
+    #[serde(default = "default_command_cooldown_seconds")]
+    pub command_cooldown_seconds: u64,   // the same command from the same group is throttled this long (0 = off)
+    #[serde(default = "default_true")]
+    pub command_cooldown_notice: bool,   // reply "please wait Ns" to throttled commands instead of ignoring them
+    #[serde(default = "default_welcome_message")]
+    pub welcome_message: String,         // sent when the bot joins a group (empty = none)
+    #[serde(default)]
+    pub welcome_image: Option<String>,   // local image path sent after the welcome text
+    #[serde(default = "default_usage_stats_interval_minutes")]
+    pub usage_stats_interval_minutes: u64, // how often command usage counts are logged (0 = off)
+    #[serde(default)]
+    pub default_routing: crate::frontend::routing::RoutingPolicy, // "backend-first", "local-first", "backend-only" or "local-only"
+    #[serde(default)]
+    pub command_routing: std::collections::HashMap<String, crate::frontend::routing::RoutingPolicy>, // command type (e.g. "query") -> policy overriding default_routing
     #[serde(skip)]
     pub token_expires_in: u64,       // expire time in seconds
-    #[serde(skip)]
+    #[serde(skip, default = "crate::http_client::shared_client")]
     pub client: reqwest::Client,
     #[serde(skip)]
     pub token_fetched_at: Option<tokio::time::Instant>,
+    #[serde(skip)]
+    pub last_images: std::sync::Arc<crate::frontend::image_cache::LastImageCache>,
+    #[serde(skip)]
+    pub uploaded_media: std::sync::Arc<crate::frontend::media_cache::MediaUploadCache>,
+    #[serde(skip)]
+    pub command_cooldowns: std::sync::Arc<crate::frontend::cooldown::CommandCooldown>,
+    #[serde(skip)]
+    pub command_usage: std::sync::Arc<crate::frontend::usage_stats::CommandUsage>,
+}
+
+fn default_api_base_url() -> String {
+    crate::frontend::qq::QQ_AUTHORIZE_URL.to_string()
+}
+
+fn default_max_webhook_body_bytes() -> usize {
+    64 * 1024
+}
+
+fn default_max_webhook_age_seconds() -> u64 {
+    300
+}
+
+fn default_command_cooldown_seconds() -> u64 {
+    10
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_notify_concurrency() -> usize {
+    4
+}
+
+fn default_offline_image_max_age_hours() -> u64 {
+    12
+}
+
+fn default_pipeline_timeout_seconds() -> u64 {
+    240
+}
+
+fn default_usage_stats_interval_minutes() -> u64 {
+    60
+}
+
+pub(crate) fn default_welcome_message() -> String {
+    [
+        "\nHi, I'm Rinko! Satellite status at your service.",
+        "/q <satellite> - latest AMSAT reports (e.g. /q AO-91)",
+        "/compare <satellite> <satellite> - two satellites side by side",
+        "/subscribe <satellite> [hourly|daily] - regular status posts in this group",
+        "/report <satellite> <heard|not-heard|telemetry|crew> <grid> <callsign>",
+        "/help - more commands",
+    ]
+    .join("\n")
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,9 +136,81 @@ pub struct BotConfigs {
     pub qq: Option<QQConfig>,
     pub telegram: Option<TelegramConfig>,
     pub enterprise_wechat: Option<EnterpriseWeChatConfig>,
+    #[serde(default)]
+    pub notify_webhooks: Vec<String>,    // URLs receiving notifications as JSON, alongside [qq] notify_groups; images are sent as [qq] media_base_url links
     pub log_level: String,
 }
 
+impl BotConfigs {
+    /// Check settings that parse on their own but fail at runtime together
+    ///
+    /// Returns every problem found, each naming the offending key, so one
+    /// edit of the config file can fix them all.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if let Some(backend) = self.backend.as_ref().filter(|b| b.enable) {
+            if backend.url.trim().is_empty() {
+                problems.push("[backend] url is required when enable = true".to_string());
+            } else {
+                check_http_url("[backend] url", &backend.url, &mut problems);
+            }
+            if backend.frontend_id.trim().is_empty() {
+                problems.push("[backend] frontend_id is required when enable = true".to_string());
+            }
+            if backend.heartbeat_interval == 0 {
+                problems.push("[backend] heartbeat_interval must be at least 1 second".to_string());
+            }
+        }
+
+        if let Some(qq) = &self.qq {
+            if qq.app_id.trim().is_empty() {
+                problems.push("[qq] app_id is required".to_string());
+            }
+            if qq.client_secret.trim().is_empty() {
+                problems.push("[qq] client_secret is required to fetch tokens and verify webhooks".to_string());
+            }
+            check_http_url("[qq] api_base_url", &qq.api_base_url, &mut problems);
+            if let Some(media_base_url) = &qq.media_base_url {
+                check_http_url("[qq] media_base_url", media_base_url, &mut problems);
+            }
+            if qq.notify_concurrency == 0 {
+                problems.push("[qq] notify_concurrency must be at least 1".to_string());
+            }
+            if qq.pipeline_timeout_seconds == 0 {
+                problems.push("[qq] pipeline_timeout_seconds must be at least 1".to_string());
+            }
+        }
+
+        for url in &self.notify_webhooks {
+            check_http_url("notify_webhooks entry", url, &mut problems);
+        }
+
+        if !["trace", "debug", "info", "warn", "error"]
+            .iter()
+            .any(|level| level.eq_ignore_ascii_case(&self.log_level))
+        {
+            problems.push(format!(
+                "log_level \"{}\" must be one of trace, debug, info, warn, error",
+                self.log_level
+            ));
+        }
+
+        if problems.is_empty() { Ok(()) } else { Err(problems) }
+    }
+}
+
+fn check_http_url(key: &str, value: &str, problems: &mut Vec<String>) {
+    match reqwest::Url::parse(value) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") => {}
+        Ok(url) => problems.push(format!(
+            "{} \"{}\" must use http:// or https://, not {}://",
+            key, value, url.scheme()
+        )),
+        Err(e) => problems.push(format!("{} \"{}\" is not a valid URL: {}", key, value, e)),
+    }
+}
+
 pub static CONFIG: OnceLock<BotConfigs> = OnceLock::new();
     
 pub fn read_config() -> anyhow::Result<()> {
@@ -66,6 +224,10 @@ pub fn read_config() -> anyhow::Result<()> {
         }
     };
 
+    config.validate().map_err(|problems| {
+        anyhow::anyhow!("Invalid config file {}:\n  - {}", path, problems.join("\n  - "))
+    })?;
+
     CONFIG.set(config.clone()).unwrap();
 
     Ok(())
@@ -81,4 +243,89 @@ mod tests {
         let config = CONFIG.get().unwrap();
         println!("Loaded config: {:#?}", config);
     }
+
+    fn parse(toml_str: &str) -> BotConfigs {
+        toml::from_str(toml_str).unwrap()
+    }
+
+    #[test]
+    fn test_valid_config_passes() {
+        let config = parse(r#"
+            log_level = "info"
+            notify_webhooks = ["https://example.com/hook"]
+
+            [backend]
+            enable = true
+            url = "http://127.0.0.1:50051"
+            frontend_id = "qq-1"
+            heartbeat_interval = 30
+
+            [qq]
+            app_id = "123"
+            client_secret = "secret"
+            access_token = ""
+            media_base_url = "https://media.example.com/media"
+        "#);
+        assert_eq!(config.validate(), Ok(()));
+
+        // A disabled backend needs no address; levels are case-insensitive
+        let config = parse(r#"
+            log_level = "INFO"
+
+            [backend]
+            enable = false
+            url = ""
+            frontend_id = ""
+            heartbeat_interval = 0
+        "#);
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_config_lists_every_problem() {
+        let config = parse(r#"
+            log_level = "verbose"
+            notify_webhooks = ["ftp://example.com/hook"]
+
+            [backend]
+            enable = true
+            url = ""
+            frontend_id = "qq-1"
+            heartbeat_interval = 0
+
+            [qq]
+            app_id = "123"
+            client_secret = ""
+            access_token = ""
+            media_base_url = "media.example.com/media"
+            notify_concurrency = 0
+        "#);
+        let problems = config.validate().unwrap_err();
+        assert_eq!(problems, vec![
+            "[backend] url is required when enable = true".to_string(),
+            "[backend] heartbeat_interval must be at least 1 second".to_string(),
+            "[qq] client_secret is required to fetch tokens and verify webhooks".to_string(),
+            "[qq] media_base_url \"media.example.com/media\" is not a valid URL: relative URL without a base".to_string(),
+            "[qq] notify_concurrency must be at least 1".to_string(),
+            "notify_webhooks entry \"ftp://example.com/hook\" must use http:// or https://, not ftp://".to_string(),
+            "log_level \"verbose\" must be one of trace, debug, info, warn, error".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_malformed_backend_url_rejected() {
+        let config = parse(r#"
+            log_level = "info"
+
+            [backend]
+            enable = true
+            url = "127.0.0.1:50051"
+            frontend_id = ""
+            heartbeat_interval = 30
+        "#);
+        let problems = config.validate().unwrap_err();
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].starts_with("[backend] url \"127.0.0.1:50051\""), "{}", problems[0]);
+        assert_eq!(problems[1], "[backend] frontend_id is required when enable = true");
+    }
 }
\ No newline at end of file
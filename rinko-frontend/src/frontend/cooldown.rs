@@ -0,0 +1,67 @@
+//! Per-(group, command) cooldown against command spam
+//!
+//! Repeating the same command in the same group within the cooldown is
+//! throttled instead of answered again.
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// (group or channel, command) -> when it was last answered
+#[derive(Debug, Default)]
+pub struct CommandCooldown {
+    last_used: Mutex<HashMap<(String, String), DateTime<Utc>>>,
+}
+
+impl CommandCooldown {
+    /// Commands differing only in case or spacing share a cooldown
+    fn key(scope: &str, command: &str) -> (String, String) {
+        let command = command.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        (scope.to_string(), command)
+    }
+
+    /// Start the cooldown for a command, or return how long it still has
+    /// to run if it was used less than `cooldown` ago
+    ///
+    /// Expired entries are dropped on every call, so the map only holds
+    /// commands still cooling down.
+    pub fn check(
+        &self,
+        scope: &str,
+        command: &str,
+        cooldown: Duration,
+        now: DateTime<Utc>,
+    ) -> Option<Duration> {
+        let mut last_used = self.last_used.lock().unwrap();
+        last_used.retain(|_, used_at| now - *used_at < cooldown);
+
+        let key = Self::key(scope, command);
+        if let Some(used_at) = last_used.get(&key) {
+            return Some(cooldown - (now - *used_at));
+        }
+        last_used.insert(key, now);
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cooldown_per_group_and_command() {
+        let cooldown = CommandCooldown::default();
+        let window = Duration::seconds(10);
+        let now = Utc::now();
+
+        assert_eq!(cooldown.check("group-1", "/q iss", window, now), None);
+        assert_eq!(
+            cooldown.check("group-1", "/Q  ISS", window, now + Duration::seconds(3)),
+            Some(Duration::seconds(7))
+        );
+        // Other groups and other commands are independent
+        assert_eq!(cooldown.check("group-2", "/q iss", window, now), None);
+        assert_eq!(cooldown.check("group-1", "/q so-50", window, now), None);
+        // Usable again once the cooldown has passed
+        assert_eq!(cooldown.check("group-1", "/q iss", window, now + Duration::seconds(10)), None);
+    }
+}
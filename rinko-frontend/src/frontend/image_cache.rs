@@ -0,0 +1,124 @@
+//! Last successful satellite image per query
+//!
+//! While the backend is offline, a repeated query can still be answered
+//! with the image the backend rendered for it last time.
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Most queries remembered; the oldest image is dropped to make room
+const MAX_ENTRIES: usize = 256;
+
+/// An image the backend returned for a query
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedImage {
+    pub path: String,
+    pub rendered_at: DateTime<Utc>,
+}
+
+/// Query -> last image, shared by all webhook handlers
+///
+/// Images older than the reuse window are pruned on every `record`, and at
+/// most `MAX_ENTRIES` queries are kept.
+#[derive(Debug, Default)]
+pub struct LastImageCache {
+    entries: Mutex<HashMap<String, CachedImage>>,
+}
+
+impl LastImageCache {
+    /// Queries differing only in case or spacing share an entry
+    fn key(query: &str) -> String {
+        query.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+    }
+
+    /// Remember the image returned for a query, forgetting images older
+    /// than `max_age`
+    pub fn record(&self, query: &str, path: &str, max_age: Duration, now: DateTime<Utc>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, image| now - image.rendered_at <= max_age);
+        entries.insert(
+            Self::key(query),
+            CachedImage {
+                path: path.to_string(),
+                rendered_at: now,
+            },
+        );
+        if entries.len() > MAX_ENTRIES
+            && let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, image)| image.rendered_at)
+                .map(|(key, _)| key.clone())
+        {
+            entries.remove(&oldest);
+        }
+    }
+
+    /// The last image for a query, if it is at most `max_age` old and its
+    /// file has not been cleaned up since
+    pub fn get(&self, query: &str, max_age: Duration, now: DateTime<Utc>) -> Option<CachedImage> {
+        let mut entries = self.entries.lock().unwrap();
+        let key = Self::key(query);
+        let image = entries.get(&key).filter(|image| now - image.rendered_at <= max_age)?;
+        if !Path::new(&image.path).is_file() {
+            entries.remove(&key);
+            return None;
+        }
+        Some(image.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An existing image file under the temp dir
+    fn image_file(name: &str) -> String {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, b"png").unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_record_and_get() {
+        let cache = LastImageCache::default();
+        let now = Utc::now();
+        let path = image_file("rinko_test_last_image_ao91.png");
+        cache.record("AO-91", &path, Duration::hours(1), now);
+
+        let hit = cache.get("  ao-91 ", Duration::hours(1), now + Duration::minutes(30));
+        assert_eq!(hit.map(|i| i.path), Some(path));
+        assert!(cache.get("AO-91", Duration::hours(1), now + Duration::hours(2)).is_none());
+        assert!(cache.get("SO-50", Duration::hours(1), now).is_none());
+    }
+
+    #[test]
+    fn test_cleaned_up_file_dropped() {
+        let cache = LastImageCache::default();
+        let now = Utc::now();
+        let path = image_file("rinko_test_last_image_gone.png");
+        cache.record("AO-91", &path, Duration::hours(1), now);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(cache.get("AO-91", Duration::hours(1), now).is_none());
+        assert!(cache.entries.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_old_entries_pruned_and_size_capped() {
+        let cache = LastImageCache::default();
+        let now = Utc::now();
+        cache.record("AO-91", "/cache/sat_ao91.png", Duration::hours(1), now);
+        cache.record("SO-50", "/cache/sat_so50.png", Duration::hours(1), now + Duration::hours(2));
+        assert_eq!(cache.entries.lock().unwrap().len(), 1);
+
+        for i in 0..=MAX_ENTRIES {
+            let at = now + Duration::hours(2) + Duration::seconds(i as i64 + 1);
+            cache.record(&format!("query {}", i), "/cache/sat.png", Duration::hours(1), at);
+        }
+        let entries = cache.entries.lock().unwrap();
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert!(!entries.contains_key("so-50"));
+        assert!(!entries.contains_key("query 0"));
+    }
+}
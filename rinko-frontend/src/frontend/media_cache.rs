@@ -0,0 +1,130 @@
+//! Uploaded media `file_info` per group and URL
+//!
+//! QQ hands back a `file_info` with a TTL for every upload, so sending the
+//! same image to the same group again can reuse it instead of uploading.
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+/// Entries this close to expiry are uploaded again rather than reused
+const EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+/// Most uploads remembered; the oldest is dropped to make room
+const MAX_ENTRIES: usize = 1024;
+
+#[derive(Debug, Clone)]
+struct UploadedMedia {
+    file_info: String,
+    /// None for `file_info` QQ reports as long-lived (ttl 0)
+    expires_at: Option<Instant>,
+    recorded_at: Instant,
+}
+
+/// (group openid, media URL) -> uploaded `file_info`
+///
+/// Expired entries are pruned on every `record`, and at most `MAX_ENTRIES`
+/// are kept since image URLs change with every render.
+#[derive(Debug, Default)]
+pub struct MediaUploadCache {
+    entries: Mutex<HashMap<(String, String), UploadedMedia>>,
+}
+
+impl MediaUploadCache {
+    /// Remember an upload; `ttl_seconds` is the TTL QQ returned with it
+    pub fn record(&self, group_openid: &str, url: &str, file_info: &str, ttl_seconds: u32, now: Instant) {
+        let expires_at = (ttl_seconds > 0).then(|| now + Duration::from_secs(ttl_seconds as u64));
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, media| media.expires_at.is_none_or(|expires_at| expires_at > now));
+        entries.insert(
+            (group_openid.to_string(), url.to_string()),
+            UploadedMedia {
+                file_info: file_info.to_string(),
+                expires_at,
+                recorded_at: now,
+            },
+        );
+        if entries.len() > MAX_ENTRIES
+            && let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, media)| media.recorded_at)
+                .map(|(key, _)| key.clone())
+        {
+            entries.remove(&oldest);
+        }
+    }
+
+    /// A `file_info` for the URL that stays valid past the expiry margin
+    pub fn get(&self, group_openid: &str, url: &str, now: Instant) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        let key = (group_openid.to_string(), url.to_string());
+        let media = entries.get(&key)?;
+        match media.expires_at {
+            Some(expires_at) if expires_at.saturating_duration_since(now) <= EXPIRY_MARGIN => {
+                entries.remove(&key);
+                None
+            }
+            _ => Some(media.file_info.clone()),
+        }
+    }
+
+    /// Drop an entry QQ no longer accepts
+    pub fn invalidate(&self, group_openid: &str, url: &str) {
+        self.entries
+            .lock()
+            .unwrap()
+            .remove(&(group_openid.to_string(), url.to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reuse_until_near_expiry() {
+        let cache = MediaUploadCache::default();
+        let now = Instant::now();
+        cache.record("group", "https://media/sat_1.png", "info", 600, now);
+
+        assert_eq!(cache.get("group", "https://media/sat_1.png", now).as_deref(), Some("info"));
+        assert!(cache.get("other", "https://media/sat_1.png", now).is_none());
+        assert!(cache.get("group", "https://media/sat_1.png", now + Duration::from_secs(550)).is_none());
+    }
+
+    #[test]
+    fn test_zero_ttl_never_expires() {
+        let cache = MediaUploadCache::default();
+        let now = Instant::now();
+        cache.record("group", "url", "info", 0, now);
+        assert!(cache.get("group", "url", now + Duration::from_secs(86400)).is_some());
+
+        cache.invalidate("group", "url");
+        assert!(cache.get("group", "url", now).is_none());
+    }
+
+    #[test]
+    fn test_expired_entries_pruned_on_record() {
+        let cache = MediaUploadCache::default();
+        let now = Instant::now();
+        cache.record("group", "https://media/sat_1.png", "info", 600, now);
+        cache.record("group", "https://media/sat_forever.png", "info", 0, now);
+
+        cache.record("group", "https://media/sat_2.png", "info", 600, now + Duration::from_secs(601));
+        let entries = cache.entries.lock().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(!entries.contains_key(&("group".to_string(), "https://media/sat_1.png".to_string())));
+    }
+
+    #[test]
+    fn test_oldest_entry_evicted_at_capacity() {
+        let cache = MediaUploadCache::default();
+        let now = Instant::now();
+        for i in 0..=MAX_ENTRIES {
+            let url = format!("https://media/sat_{}.png", i);
+            cache.record("group", &url, "info", 0, now + Duration::from_secs(i as u64));
+        }
+
+        assert_eq!(cache.entries.lock().unwrap().len(), MAX_ENTRIES);
+        assert!(cache.get("group", "https://media/sat_0.png", now).is_none());
+        assert!(cache.get("group", "https://media/sat_1.png", now).is_some());
+    }
+}
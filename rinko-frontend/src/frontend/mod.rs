@@ -1,2 +1,9 @@
 pub mod qq;
-pub mod llonebot;
\ No newline at end of file
+pub mod llonebot;
+pub mod image_cache;
+pub mod media_cache;
+pub mod cooldown;
+pub mod quiet_hours;
+pub mod usage_stats;
+pub mod routing;
+pub mod status;
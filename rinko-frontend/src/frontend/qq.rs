@@ -1,10 +1,16 @@
 use crate::{config::QQConfig, utils::BotAdapter};
 use crate::utils::*;
-use crate::backend::connection_manager::BackendConnectionManager;
+use crate::backend::connection_manager::{BackendConnectionManager, ConnectionState};
+use crate::frontend::routing::{self, RouteStep};
+use crate::command::{CommandType, ParsedCommand, ReportArgs};
+use crate::frontend::image_cache::CachedImage;
+use crate::notify::NotificationSink;
 use rinko_common::proto::MessageResponse;
+use rinko_common::proto::ReportSubmission;
 use rinko_common::proto::ContentType;
 use uuid::Uuid;
 use async_trait::async_trait;
+use futures::StreamExt;
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::sync::RwLock;
@@ -12,7 +18,8 @@ use tokio::time::{sleep, Duration};
 use serde::{Deserialize, Serialize};
 use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use axum::{
-    extract::State,
+    body::Bytes,
+    extract::{DefaultBodyLimit, State},
     routing::post,
     Json,
     Router,
@@ -21,11 +28,13 @@ use axum::{
 };
 
 const QQ_ACCESS_TOKEN_URL: &str = "https://bots.qq.com/app/getAppAccessToken";
-const QQ_AUTHORIZE_URL: &str = "https://api.sgroup.qq.com";
+pub(crate) const QQ_AUTHORIZE_URL: &str = "https://api.sgroup.qq.com";
 
 #[derive(Deserialize)]
 #[allow(dead_code)]
 struct WebhookPayload {
+    #[serde(default)]
+    id: Option<String>,
     op: u8,
     d: serde_json::Value,
     #[serde(default)]
@@ -46,17 +55,204 @@ struct ValidationResponse {
     signature: String,
 }
 
+#[derive(Deserialize, Debug, Default)]
+struct GroupMessageAuthor {
+    #[serde(default)]
+    member_openid: String,
+}
+
 #[allow(unused)]
 #[derive(Deserialize, Debug)]
 struct GroupMessageEvent {
     id: String,
     group_openid: String,
     #[serde(default)]
+    author: GroupMessageAuthor,
+    #[serde(default)]
     content: String,
     #[serde(default)]
     timestamp: String,
 }
 
+#[derive(Deserialize, Debug, Default)]
+struct ChannelMessageAuthor {
+    #[serde(default)]
+    id: String,
+}
+
+#[allow(unused)]
+#[derive(Deserialize, Debug)]
+struct ChannelMessageEvent {
+    id: String,
+    channel_id: String,
+    guild_id: String,
+    #[serde(default)]
+    author: ChannelMessageAuthor,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    timestamp: String,
+}
+
+/// Where replies to an incoming message are sent
+#[derive(Debug, Clone, PartialEq)]
+enum ReplyTarget {
+    Group { group_openid: String },
+    Channel { channel_id: String, guild_id: String },
+}
+
+/// A message @-mentioning the bot, from a group or a guild channel
+#[derive(Debug)]
+struct AtMessage {
+    id: String,
+    target: ReplyTarget,
+    author_id: String,
+    content: String,
+}
+
+impl AtMessage {
+    /// Group or channel the message came from
+    fn scope(&self) -> &str {
+        match &self.target {
+            ReplyTarget::Group { group_openid } => group_openid,
+            ReplyTarget::Channel { channel_id, .. } => channel_id,
+        }
+    }
+}
+
+impl From<GroupMessageEvent> for AtMessage {
+    fn from(event: GroupMessageEvent) -> Self {
+        Self {
+            id: event.id,
+            target: ReplyTarget::Group { group_openid: event.group_openid },
+            author_id: event.author.member_openid,
+            content: event.content,
+        }
+    }
+}
+
+impl From<ChannelMessageEvent> for AtMessage {
+    fn from(event: ChannelMessageEvent) -> Self {
+        Self {
+            id: event.id,
+            target: ReplyTarget::Channel {
+                channel_id: event.channel_id,
+                guild_id: event.guild_id,
+            },
+            author_id: event.author.id,
+            content: strip_mentions(&event.content).to_string(),
+        }
+    }
+}
+
+/// Delivers notifications to QQ groups as active messages
+///
+/// Broadcasts go to a fixed set of groups; group notifications (e.g.
+/// subscription posts) go to the groups they name.
+pub struct QQGroupSink {
+    config: Arc<RwLock<QQConfig>>,
+    groups: Vec<String>,
+}
+
+impl QQGroupSink {
+    pub fn new(config: Arc<RwLock<QQConfig>>, groups: Vec<String>) -> Self {
+        Self { config, groups }
+    }
+
+    /// Run `send` for every group, at most `notify_concurrency` at once
+    ///
+    /// A slow or failing group does not hold up the others; each group's
+    /// outcome is returned, in completion order. Groups in their quiet
+    /// hours are skipped.
+    async fn send_to_groups<'a, F, Fut>(
+        config: &'a QQConfig,
+        groups: &'a [String],
+        send: F,
+    ) -> Vec<(String, anyhow::Result<()>)>
+    where
+        F: Fn(&'a QQConfig, &'a str) -> Fut,
+        Fut: std::future::Future<Output = anyhow::Result<SendMessageResponse>>,
+    {
+        let now = chrono::Utc::now();
+        let sends: Vec<_> = groups
+            .iter()
+            .filter(|group| match config.quiet_hours.get(group.as_str()) {
+                Some(quiet) if quiet.contains(now) => {
+                    tracing::debug!("Not notifying group {} during its quiet hours", group);
+                    false
+                }
+                _ => true,
+            })
+            .map(|group| {
+                let sent = send(config, group);
+                async move { (group.clone(), sent.await.map(|_| ())) }
+            })
+            .collect();
+        futures::stream::iter(sends)
+            .buffer_unordered(config.notify_concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Every group is tried; report the ones that failed together
+    fn all_sent(results: Vec<(String, anyhow::Result<()>)>) -> anyhow::Result<()> {
+        let failed: Vec<String> = results
+            .into_iter()
+            .filter_map(|(group, result)| result.err().map(|e| format!("group {}: {}", group, e)))
+            .collect();
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(failed.join("; ")))
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationSink for QQGroupSink {
+    fn name(&self) -> String {
+        format!("{} QQ group(s)", self.groups.len())
+    }
+
+    async fn send_text(&self, text: &str) -> anyhow::Result<()> {
+        self.send_group_text(&self.groups, text).await
+    }
+
+    async fn send_image(&self, local_path: &str) -> anyhow::Result<()> {
+        self.send_group_image(&self.groups, local_path).await
+    }
+
+    async fn send_group_text(&self, groups: &[String], text: &str) -> anyhow::Result<()> {
+        let config = self.config.read().await;
+        let results = QQGroupSink::send_to_groups(&config, groups, |config, group| {
+            config.send_group_message(group, text, None, None, None)
+        })
+        .await;
+        QQGroupSink::all_sent(results)
+    }
+
+    async fn send_group_image(&self, groups: &[String], local_path: &str) -> anyhow::Result<()> {
+        let config = self.config.read().await;
+        let results = QQGroupSink::send_to_groups(&config, groups, |config, group| {
+            config.send_group_image(group, local_path, None, None, None)
+        })
+        .await;
+        QQGroupSink::all_sent(results)
+    }
+}
+
+/// Drop the leading `<@!id>` mentions channel messages start with
+fn strip_mentions(content: &str) -> &str {
+    let mut rest = content.trim_start();
+    while rest.starts_with("<@") {
+        match rest.find('>') {
+            Some(end) => rest = rest[end + 1..].trim_start(),
+            None => break,
+        }
+    }
+    rest
+}
+
 #[derive(Serialize, Debug)]
 struct SendGroupMessageRequest {
     content: Option<String>,
@@ -71,6 +267,18 @@ struct SendGroupMessageRequest {
     media: Option<MediaInfo>,
 }
 
+#[derive(Serialize, Debug)]
+struct SendChannelMessageRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msg_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_id: Option<String>,
+}
+
 #[derive(Serialize, Debug)]
 struct MediaInfo {
     file_info: String,
@@ -102,22 +310,48 @@ pub struct SendMessageResponse {
 #[derive(Clone)]
 struct WebhookState {
     client_secret: String,  // used as bot_secret for signature verification
+    max_age_seconds: u64,   // replay window for X-Signature-Timestamp
     qq_config: Arc<RwLock<QQConfig>>,
     backend_manager: Option<Arc<BackendConnectionManager>>,
 }
 
+/// Build the webhook router
+///
+/// Bodies larger than `max_body_bytes` are rejected with 413 before the
+/// handler runs.
+fn webhook_router(state: Arc<WebhookState>, max_body_bytes: usize) -> Router {
+    Router::new()
+        .route("/webhook", post(handle_webhook))
+        .layer(DefaultBodyLimit::max(max_body_bytes))
+        .with_state(state)
+}
+
+/// Check the payload envelope before dispatching on it
+fn validate_payload(payload: &WebhookPayload) -> Result<(), &'static str> {
+    if !matches!(payload.op, 0 | 13) {
+        return Err("Unknown operation");
+    }
+    if !payload.d.is_object() {
+        return Err("Invalid event data");
+    }
+    Ok(())
+}
+
 /// Webhook handler for QQ bot events
+///
+/// The body is kept as raw bytes so the signature is verified over
+/// exactly what was sent.
 async fn handle_webhook(
     State(state): State<Arc<WebhookState>>,
     headers: HeaderMap,
-    body: String,
+    body: Bytes,
 ) -> Response {
     tracing::debug!("Received webhook request");
     tracing::debug!("Headers: {:#?}", headers);
-    tracing::debug!("Body: {:#?}", body);
+    tracing::debug!("Body: {}", String::from_utf8_lossy(&body));
 
     // Parse payload to check op code
-    let payload: WebhookPayload = match serde_json::from_str(&body) {
+    let payload: WebhookPayload = match serde_json::from_slice(&body) {
         Ok(p) => p,
         Err(e) => {
             tracing::error!("Failed to parse payload: {}", e);
@@ -125,6 +359,11 @@ async fn handle_webhook(
         }
     };
 
+    if let Err(reason) = validate_payload(&payload) {
+        tracing::warn!("Rejecting webhook payload (op={}): {}", payload.op, reason);
+        return (StatusCode::BAD_REQUEST, reason).into_response();
+    }
+
     // Handle different operation codes
     match payload.op {
         13 => {
@@ -155,12 +394,22 @@ async fn handle_webhook(
                 return (StatusCode::UNAUTHORIZED, "Invalid signature").into_response();
             }
 
+            // Reject replays of captured requests once the signature is known good
+            if let Err(e) = check_timestamp_freshness(timestamp, state.max_age_seconds, chrono::Utc::now().timestamp()) {
+                tracing::warn!("Rejecting webhook event: {}", e);
+                return (StatusCode::UNAUTHORIZED, "Stale signature timestamp").into_response();
+            }
+
             tracing::debug!("Signature verified successfully");
             
             // Handle the event
             if let Some(event_type) = &payload.t {
                 tracing::info!("Event type: {}", event_type);
-                let event_id = payload.d.get("id").and_then(|v| v.as_str()).map(|s| s.to_string());
+                // Message events carry their id in `d`; others only at the top level
+                let event_id = payload.d.get("id")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .or(payload.id);
                 handle_event(&state.qq_config, &state.backend_manager, event_type, &payload.d, event_id).await;
             }
 
@@ -215,7 +464,10 @@ async fn handle_event(
     match event_type {
         "READY" => tracing::info!("Bot is ready"),
         "GROUP_AT_MESSAGE_CREATE" => {
-            handle_group_at_message(qq_config, backend_manager, data, event_id).await;
+            handle_at_message::<GroupMessageEvent>(qq_config, backend_manager, event_type, data, event_id).await;
+        }
+        "AT_MESSAGE_CREATE" => {
+            handle_at_message::<ChannelMessageEvent>(qq_config, backend_manager, event_type, data, event_id).await;
         }
         "MESSAGE_CREATE" | "C2C_MESSAGE_CREATE" => {
             tracing::debug!("Message received: {:#?}", data);
@@ -225,104 +477,373 @@ async fn handle_event(
         "FRIEND_ADD" => tracing::info!("Friend added"),
         "GROUP_ADD_ROBOT" => {
             tracing::info!("Added to group: {:#?}", data);
-            // Could send a welcome message here
+            send_welcome(qq_config, data, event_id).await;
         }
         _ => tracing::debug!("Unhandled event type: {}", event_type),
     }
 }
 
-/// Handle GROUP_AT_MESSAGE_CREATE event
-async fn handle_group_at_message(
+/// Greet a group the bot was just added to
+///
+/// The event has no message to reply to, so the welcome is passive on
+/// `event_id` when QQ sent one and an active message otherwise.
+async fn send_welcome(
     qq_config: &Arc<RwLock<QQConfig>>,
-    backend_manager: &Option<Arc<BackendConnectionManager>>,
     data: &serde_json::Value,
     event_id: Option<String>,
 ) {
-    tracing::debug!("Group @ message received: {:#?}", data);
+    let Some(group_openid) = data.get("group_openid").and_then(|v| v.as_str()) else {
+        tracing::warn!("GROUP_ADD_ROBOT event without group_openid");
+        return;
+    };
+    if event_id.is_none() {
+        tracing::debug!("No event_id for welcome in {}, sending as active message", group_openid);
+    }
+
+    let config = qq_config.read().await;
+    let mut msg_seq = 1;
+    if !config.welcome_message.is_empty() {
+        if let Err(e) = config.send_group_message(
+            group_openid,
+            &config.welcome_message,
+            None,
+            event_id.clone(),
+            Some(msg_seq),
+        ).await {
+            tracing::error!("Failed to send welcome message: {}", e);
+        }
+        msg_seq += 1;
+    }
+    if let Some(image) = &config.welcome_image
+        && let Err(e) = config.send_group_image(group_openid, image, None, event_id, Some(msg_seq)).await
+    {
+        tracing::error!("Failed to send welcome image: {}", e);
+    }
+}
+
+/// Handle GROUP_AT_MESSAGE_CREATE and AT_MESSAGE_CREATE events
+async fn handle_at_message<E>(
+    qq_config: &Arc<RwLock<QQConfig>>,
+    backend_manager: &Option<Arc<BackendConnectionManager>>,
+    event_type: &str,
+    data: &serde_json::Value,
+    event_id: Option<String>,
+) where
+    E: serde::de::DeserializeOwned + Into<AtMessage>,
+{
+    tracing::debug!("{} received: {:#?}", event_type, data);
     
     // Parse message event
-    let msg_event = match serde_json::from_value::<GroupMessageEvent>(data.clone()) {
-        Ok(event) => event,
+    let msg_event: AtMessage = match serde_json::from_value::<E>(data.clone()) {
+        Ok(event) => event.into(),
         Err(e) => {
-            tracing::error!("Failed to parse GROUP_AT_MESSAGE_CREATE event: {}", e);
+            tracing::error!("Failed to parse {} event: {}", event_type, e);
             return;
         }
     };
 
     tracing::debug!(
-        "Parsed message - ID: {}, Group: {}, Content: '{}'",
+        "Parsed message - ID: {}, Target: {:?}, Content: '{}'",
         msg_event.id,
-        msg_event.group_openid,
+        msg_event.target,
         msg_event.content
     );
 
+    let budget = Duration::from_secs(qq_config.read().await.pipeline_timeout_seconds);
+    run_with_deadline(
+        budget,
+        &msg_event.id,
+        process_at_message(qq_config, backend_manager, &msg_event, event_id),
+    ).await;
+}
+
+/// Run a message's whole query→render→send pipeline within `budget`
+///
+/// Past the deadline the remaining steps are dropped, since replies that
+/// late would miss QQ's passive-reply window anyway. Returns whether the
+/// pipeline finished.
+async fn run_with_deadline(
+    budget: Duration,
+    message_id: &str,
+    pipeline: impl std::future::Future<Output = ()>,
+) -> bool {
+    match tokio::time::timeout(budget, pipeline).await {
+        Ok(()) => true,
+        Err(_) => {
+            tracing::error!(
+                "Aborted handling of message {} after {:?} deadline",
+                message_id,
+                budget
+            );
+            false
+        }
+    }
+}
+
+/// Parse, forward and answer an @ message
+async fn process_at_message(
+    qq_config: &Arc<RwLock<QQConfig>>,
+    backend_manager: &Option<Arc<BackendConnectionManager>>,
+    msg_event: &AtMessage,
+    event_id: Option<String>,
+) {
     let content_trimmed = msg_event.content.trim();
     
     // Parse command from message
-    // let parsed_cmd = ParsedCommand::parse(content_trimmed);
+    let parsed_cmd = ParsedCommand::parse(content_trimmed);
+
+    if parsed_cmd.command_type != CommandType::Unknown
+        && let Some(remaining) = cooldown_remaining(qq_config, msg_event, content_trimmed).await
+    {
+        let config = qq_config.read().await;
+        if config.command_cooldown_notice {
+            let seconds = (remaining.num_milliseconds() + 999) / 1000;
+            let notice = format!("\nPlease wait {}s before repeating this command", seconds);
+            if let Err(e) = config.reply_text(msg_event, &notice, event_id, 1).await {
+                tracing::error!("Failed to send cooldown notice: {}", e);
+            }
+        }
+        return;
+    }
+
+    if parsed_cmd.is_command() {
+        let config = qq_config.read().await;
+        config.command_usage.record(msg_event.scope(), parsed_cmd.command_type.as_str());
+    }
+
+    // Status combines the frontend's view of the backend with the backend's own
+    if parsed_cmd.command_type == CommandType::Status {
+        let text = crate::frontend::status::bot_status(backend_manager).await;
+        let config = qq_config.read().await;
+        if let Err(e) = config.reply_text(msg_event, &text, event_id, 1).await {
+            tracing::error!("Failed to send status reply: {}", e);
+        }
+        return;
+    }
+
+    // Report commands go to the backend as structured submissions
+    let mut report_args = if parsed_cmd.command_type == CommandType::Report {
+        match ReportArgs::parse(&parsed_cmd.arguments) {
+            Ok(args) => Some(args),
+            Err(usage) => {
+                let config = qq_config.read().await;
+                if let Err(e) = config.reply_text(msg_event, &usage, event_id, 1).await {
+                    tracing::error!("Failed to send usage reply: {}", e);
+                }
+                return;
+            }
+        }
+    } else {
+        None
+    };
     
-    // Report message to backend if connected
-    if let Some(manager) = backend_manager {
-        let state = manager.state().await;
-        
-        if state == crate::backend::connection_manager::ConnectionState::Connected {
-            let unified_msg = UnifiedMessage {
-                event_id: Uuid::now_v7(),
-                content: content_trimmed.to_string(),
-                platform: Platform::QQ,
-            };
-            
-            // Create metadata with command info and message context
-            let mut metadata = HashMap::new();
-            metadata.insert("group_openid".to_string(), msg_event.group_openid.clone());
-            metadata.insert("message_id".to_string(), msg_event.id.clone());
-            if let Some(ref eid) = event_id {
-                metadata.insert("event_id".to_string(), eid.clone());
+    let connected = match backend_manager {
+        Some(manager) => manager.state().await == ConnectionState::Connected,
+        None => false,
+    };
+    if !connected {
+        tracing::debug!("Backend offline, using local processing");
+    }
+    let policy = {
+        let config = qq_config.read().await;
+        routing::policy_for(config.default_routing, &config.command_routing, parsed_cmd.command_type.as_str())
+    };
+
+    let mut offline = !connected;
+    for step in policy.steps(connected) {
+        match step {
+            RouteStep::Backend => {
+                let Some(manager) = backend_manager else {
+                    continue;
+                };
+                let answered = forward_to_backend(
+                    qq_config,
+                    manager,
+                    msg_event,
+                    &parsed_cmd,
+                    report_args.take(),
+                    event_id.clone(),
+                )
+                .await;
+                if answered {
+                    return;
+                }
+                offline = true;
             }
-            
-            // Try to report to backend
-            let client_lock = manager.client();
-            if let Some(client) = &mut *client_lock.write().await {
-                match client.report_message(unified_msg, metadata.clone()).await {
-                    Ok(response) => {
-                        tracing::debug!(
-                            "Message reported to backend",
-                        );
-                        
-                        // If backend returns a response message, send it
-                        if !response.message.is_empty() && response.message != "OK" {
-                            let config = qq_config.read().await;
-                            let _ = config.send_message(response, &msg_event).await;
-                        }
-                        
-                        // Backend handled the message, return early
-                        return;
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to report message to backend: {}. Marking as disconnected.", e);
-                        manager.mark_disconnected().await;
-                    }
+            RouteStep::LocalCached => {
+                let config = qq_config.read().await;
+                let reply = local_reply(&config, &parsed_cmd, content_trimmed, chrono::Utc::now());
+                if matches!(reply, LocalReply::CachedImage(_)) {
+                    send_local_reply(&config, msg_event, reply, event_id, offline).await;
+                    return;
                 }
             }
-        } else {
-            tracing::debug!("Backend offline, using local processing");
+            RouteStep::Local => {
+                let config = qq_config.read().await;
+                let reply = local_reply(&config, &parsed_cmd, content_trimmed, chrono::Utc::now());
+                send_local_reply(&config, msg_event, reply, event_id, offline).await;
+                return;
+            }
         }
     }
-    
-    // Local fallback processing if backend is disabled or failed
+    tracing::info!("Not answering \"{}\": {:?} routing and the backend is unavailable", content_trimmed, policy);
+}
+
+/// Forward a message to the backend and send its answer
+///
+/// Returns false, after marking the backend disconnected, if it could not
+/// be reached.
+async fn forward_to_backend(
+    qq_config: &Arc<RwLock<QQConfig>>,
+    manager: &Arc<BackendConnectionManager>,
+    msg_event: &AtMessage,
+    parsed_cmd: &ParsedCommand,
+    report_args: Option<ReportArgs>,
+    event_id: Option<String>,
+) -> bool {
+    let unified_msg = UnifiedMessage {
+        event_id: Uuid::now_v7(),
+        content: msg_event.content.trim().to_string(),
+        platform: Platform::QQ,
+    };
+
+    // Create metadata with command info and message context
+    let mut metadata = HashMap::new();
+    match &msg_event.target {
+        ReplyTarget::Group { group_openid } => {
+            metadata.insert("group_openid".to_string(), group_openid.clone());
+        }
+        ReplyTarget::Channel { channel_id, guild_id } => {
+            metadata.insert("channel_id".to_string(), channel_id.clone());
+            metadata.insert("guild_id".to_string(), guild_id.clone());
+        }
+    }
+    metadata.insert("message_id".to_string(), msg_event.id.clone());
+    if let Some(ref eid) = event_id {
+        metadata.insert("event_id".to_string(), eid.clone());
+    }
+
+    // Try to report to backend
+    let client_lock = manager.client();
+    let Some(client) = &mut *client_lock.write().await else {
+        return false;
+    };
+    let result = match report_args {
+        Some(args) => {
+            let submission = ReportSubmission {
+                satellite: args.satellite,
+                status: args.status,
+                grid_square: args.grid_square,
+                callsign: args.callsign,
+                submitter: format!("qq:{}", msg_event.author_id),
+                platform: rinko_common::proto::Platform::Qq as i32,
+                metadata: metadata.clone(),
+            };
+            client.submit_report(submission).await
+        }
+        None => client.report_message(unified_msg, metadata.clone()).await,
+    };
+    match result {
+        Ok(response) => {
+            tracing::debug!("Message reported to backend");
+
+            // If backend returns a response message, send it
+            if !response.message.is_empty() && response.message != "OK" {
+                let config = qq_config.read().await;
+                // Only images of matched queries are worth showing offline
+                if parsed_cmd.command_type == CommandType::Query
+                    && response.success
+                    && response.content_type == ContentType::Image as i32
+                    && config.offline_image_max_age_hours > 0
+                {
+                    let local_path = response.message.strip_prefix("file:///").unwrap_or(&response.message);
+                    let max_age = chrono::Duration::hours(config.offline_image_max_age_hours as i64);
+                    config.last_images.record(&parsed_cmd.arguments, local_path, max_age, chrono::Utc::now());
+                }
+                let _ = config.send_message(response, msg_event).await;
+            }
+            true
+        }
+        Err(e) => {
+            tracing::warn!("Failed to report message to backend: {}. Marking as disconnected.", e);
+            manager.mark_disconnected().await;
+            false
+        }
+    }
+}
+
+/// Send a locally built reply; `offline` notes that the backend is down
+async fn send_local_reply(
+    config: &QQConfig,
+    msg_event: &AtMessage,
+    reply: LocalReply,
+    event_id: Option<String>,
+    offline: bool,
+) {
+    match reply {
+        LocalReply::CachedImage(image) => {
+            let note = format!(
+                "\n{}Last result from {}:",
+                if offline { "Rinko backend offline >_\n" } else { "" },
+                image.rendered_at.format("%H:%M UTC")
+            );
+            if let Err(e) = config.reply_text(msg_event, &note, event_id.clone(), 1).await {
+                tracing::error!("Failed to send reply: {}", e);
+            }
+            if let Err(e) = config.reply_image(msg_event, &image.path, event_id, 2).await {
+                tracing::error!("Failed to send cached image: {}", e);
+            }
+        }
+        LocalReply::Text(reply_content) => {
+            if let Err(e) = config.reply_text(msg_event, &reply_content, event_id, 1).await {
+                tracing::error!("Failed to send reply: {}", e);
+            }
+        }
+    }
+}
+
+/// Time left before this group may repeat the command, if it is cooling down
+async fn cooldown_remaining(
+    qq_config: &Arc<RwLock<QQConfig>>,
+    msg_event: &AtMessage,
+    command: &str,
+) -> Option<chrono::Duration> {
     let config = qq_config.read().await;
+    if config.command_cooldown_seconds == 0 {
+        return None;
+    }
+    let scope = msg_event.scope();
+    let cooldown = chrono::Duration::seconds(config.command_cooldown_seconds as i64);
+    let remaining = config.command_cooldowns.check(scope, command, cooldown, chrono::Utc::now());
+    if let Some(remaining) = remaining {
+        tracing::debug!("Throttled \"{}\" in {} for another {}s", command, scope, remaining.num_seconds());
+    }
+    remaining
+}
 
-    let reply_content = format!("\nRinko backend offline >_\nMessage received: {}", content_trimmed);
-    
-    if let Err(e) = config.send_group_message(
-        &msg_event.group_openid,
-        &reply_content,
-        Some(msg_event.id),
-        event_id,
-        Some(1),
-    ).await {
-        tracing::error!("Failed to send reply: {}", e);
+/// Reply sent when the backend cannot handle a message
+#[derive(Debug, PartialEq)]
+enum LocalReply {
+    /// Last image the backend rendered for the same satellite query
+    CachedImage(CachedImage),
+    Text(String),
+}
+
+/// Pick the offline reply: a recent cached image for satellite queries,
+/// otherwise a plain notice
+fn local_reply(
+    config: &QQConfig,
+    cmd: &ParsedCommand,
+    content: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> LocalReply {
+    if cmd.command_type == CommandType::Query && config.offline_image_max_age_hours > 0 {
+        let max_age = chrono::Duration::hours(config.offline_image_max_age_hours as i64);
+        if let Some(image) = config.last_images.get(&cmd.arguments, max_age, now) {
+            return LocalReply::CachedImage(image);
+        }
     }
+    LocalReply::Text(format!("\nRinko backend offline >_\nMessage received: {}", content))
 }
 
 /// Generate signature for configuration validation (op=13)
@@ -357,7 +878,7 @@ fn verify_runtime_signature(
     client_secret: &str,  // client_secret is used as bot_secret
     sig_hex: &str,
     timestamp: &str,
-    body: &str,
+    body: &[u8],
 ) -> anyhow::Result<()> {
     // 1. Generate public key from client_secret (used as bot_secret)
     // According to docs: seed = secret + secret (until >= 32 bytes)
@@ -382,7 +903,7 @@ fn verify_runtime_signature(
     // 3. Construct message: timestamp + body
     let mut message = Vec::new();
     message.extend_from_slice(timestamp.as_bytes());
-    message.extend_from_slice(body.as_bytes());
+    message.extend_from_slice(body);
 
     // 4. Verify signature
     verifying_key
@@ -392,6 +913,28 @@ fn verify_runtime_signature(
     Ok(())
 }
 
+/// Check that a signature timestamp (Unix seconds) is within `max_age_seconds` of `now`
+///
+/// Applies in both directions, so timestamps too far in the future are
+/// rejected as well.
+fn check_timestamp_freshness(timestamp: &str, max_age_seconds: u64, now: i64) -> anyhow::Result<()> {
+    let timestamp: i64 = timestamp
+        .trim()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Invalid signature timestamp '{}': {}", timestamp, e))?;
+
+    let skew = now.abs_diff(timestamp);
+    if skew > max_age_seconds {
+        anyhow::bail!(
+            "Signature timestamp {} is {}s away from now (max {}s)",
+            timestamp,
+            skew,
+            max_age_seconds
+        );
+    }
+    Ok(())
+}
+
 #[async_trait]
 impl BotAdapter for QQConfig {
     async fn process_message(&self) -> anyhow::Result<UnifiedMessage> {
@@ -413,7 +956,7 @@ impl BotAdapter for QQConfig {
 
 impl QQConfig {
     pub async fn init(&mut self) -> anyhow::Result<()> {
-        self.client = reqwest::Client::new();
+        self.client = crate::http_client::shared_client();
         self.get_access_token().await
     }
 
@@ -423,17 +966,19 @@ impl QQConfig {
         backend_manager: Option<Arc<BackendConnectionManager>>,
         port: u16,
     ) -> anyhow::Result<()> {
-        let client_secret = qq_config.read().await.client_secret.clone();
+        let (client_secret, max_body_bytes, max_age_seconds) = {
+            let cfg = qq_config.read().await;
+            (cfg.client_secret.clone(), cfg.max_webhook_body_bytes, cfg.max_webhook_age_seconds)
+        };
         
         let state = Arc::new(WebhookState {
             client_secret,
+            max_age_seconds,
             qq_config: qq_config.clone(),
             backend_manager,
         });
 
-        let app = Router::new()
-            .route("/webhook", post(handle_webhook))
-            .with_state(state);
+        let app = webhook_router(state, max_body_bytes);
 
         let addr = format!("127.0.0.1:{}", port);
         tracing::info!("Starting QQ webhook server on {}", addr);
@@ -528,7 +1073,7 @@ impl QQConfig {
         url: &str,
         srv_send_msg: bool,
     ) -> anyhow::Result<UploadMediaResponse> {
-        let api_url = format!("{}/v2/groups/{}/files", QQ_AUTHORIZE_URL, group_openid);
+        let api_url = format!("{}/v2/groups/{}/files", self.api_base_url, group_openid);
         
         let payload = UploadMediaRequest {
             file_type,
@@ -573,7 +1118,7 @@ impl QQConfig {
         event_id: Option<String>,
         msg_seq: Option<u32>,
     ) -> anyhow::Result<SendMessageResponse> {
-        let url = format!("{}/v2/groups/{}/messages", QQ_AUTHORIZE_URL, group_openid);
+        let url = format!("{}/v2/groups/{}/messages", self.api_base_url, group_openid);
         
         let payload = SendGroupMessageRequest {
             content: None,
@@ -607,6 +1152,20 @@ impl QQConfig {
         Ok(response)
     }
 
+    /// Public URL the media server serves a rendered file under
+    fn media_url(&self, local_path: &str) -> anyhow::Result<String> {
+        // Construct public URL using media_base_url from config
+        if let Some(base_url) = &self.media_base_url {
+            public_media_url(base_url, local_path)
+        } else {
+            // Fallback: use placeholder if media_base_url is not configured
+            tracing::warn!(
+                "media_base_url not configured in config.toml. Using placeholder URL."
+            );
+            Ok("https://metasequoiani.com/_astro/image.BnwkcnDf_Z2vIi4L.webp".to_string())
+        }
+    }
+
     /// Send image from local file to group
     /// This is a high-level function that handles the complete workflow
     /// 
@@ -628,36 +1187,41 @@ impl QQConfig {
         event_id: Option<String>,
         msg_seq: Option<u32>,
     ) -> anyhow::Result<SendMessageResponse> {
-        // Extract filename from local path
-        let filename = std::path::Path::new(local_path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .ok_or_else(|| anyhow::anyhow!("Invalid file path: {}", local_path))?;
-        
-        // Construct public URL using media_base_url from config
-        let image_url = if let Some(base_url) = &self.media_base_url {
-            format!("{}/{}", base_url.trim_end_matches('/'), filename)
-        } else {
-            // Fallback: use placeholder if media_base_url is not configured
-            tracing::warn!(
-                "media_base_url not configured in config.toml. Using placeholder URL."
-            );
-            "https://metasequoiani.com/_astro/image.BnwkcnDf_Z2vIi4L.webp".to_string()
-        };
+        let image_url = self.media_url(local_path)?;
         
-        tracing::info!(
-            "Sending image '{}' to group via URL: {}",
-            filename,
-            image_url
-        );
+        tracing::info!("Sending image to group via URL: {}", image_url);
         
-        // Step 1: Upload media and get file_info
+        // Step 1: Reuse a still-valid file_info for this image, or upload it
+        if let Some(file_info) = self.uploaded_media.get(group_openid, &image_url, tokio::time::Instant::now()) {
+            tracing::debug!("Reusing uploaded media for {}", image_url);
+            match self.send_group_media_message(
+                group_openid,
+                &file_info,
+                msg_id.clone(),
+                event_id.clone(),
+                msg_seq,
+            ).await {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    tracing::warn!("Cached media for {} rejected, uploading again: {}", image_url, e);
+                    self.uploaded_media.invalidate(group_openid, &image_url);
+                }
+            }
+        }
+
         let upload_response = self.upload_group_media(
             group_openid,
             1, // 1 = image
             &image_url,
             false, // Don't send directly, get file_info for flexible usage
         ).await?;
+        self.uploaded_media.record(
+            group_openid,
+            &image_url,
+            &upload_response.file_info,
+            upload_response.ttl,
+            tokio::time::Instant::now(),
+        );
 
         // Step 2: Send media message using file_info
         self.send_group_media_message(
@@ -685,7 +1249,7 @@ impl QQConfig {
         event_id: Option<String>,
         msg_seq: Option<u32>,
     ) -> anyhow::Result<SendMessageResponse> {
-        let url = format!("{}/v2/groups/{}/messages", QQ_AUTHORIZE_URL, group_openid);
+        let url = format!("{}/v2/groups/{}/messages", self.api_base_url, group_openid);
         
         let payload = SendGroupMessageRequest {
             content: Some(content.to_string()),
@@ -723,40 +1287,681 @@ impl QQConfig {
         Ok(response)
     }
 
-    async fn send_message(&self, resp: MessageResponse, msg_event: &GroupMessageEvent) -> anyhow::Result<()> {
+    /// Post to a guild channel
+    async fn post_channel_message(
+        &self,
+        channel_id: &str,
+        payload: SendChannelMessageRequest,
+    ) -> anyhow::Result<SendMessageResponse> {
+        let url = format!("{}/channels/{}/messages", self.api_base_url, channel_id);
+
+        tracing::debug!("Sending message to channel {}: {:#?}", channel_id, payload);
+
+        let resp = self.client
+            .post(&url)
+            .header("Authorization", format!("QQBot {}", self.access_token))
+            .json(&payload)
+            .timeout(std::time::Duration::from_secs(5))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let response: SendMessageResponse = resp.json().await?;
+        tracing::info!(
+            "Channel message sent successfully - ID: {}, Timestamp: {}",
+            response.id,
+            response.timestamp
+        );
+
+        Ok(response)
+    }
+
+    /// Send message to a guild channel
+    /// 
+    /// # Parameters
+    /// - `channel_id`: The id of the target sub-channel
+    /// - `content`: Message content
+    /// - `msg_id`: Optional message ID for passive reply
+    /// - `event_id`: Optional event ID for passive message
+    pub async fn send_channel_message(
+        &self,
+        channel_id: &str,
+        content: &str,
+        msg_id: Option<String>,
+        event_id: Option<String>,
+    ) -> anyhow::Result<SendMessageResponse> {
+        self.post_channel_message(channel_id, SendChannelMessageRequest {
+            content: Some(content.to_string()),
+            image: None,
+            msg_id,
+            event_id,
+        }).await
+    }
+
+    /// Send image from local file to a guild channel
+    ///
+    /// Channels take the image URL directly, so there is no upload step.
+    pub async fn send_channel_image(
+        &self,
+        channel_id: &str,
+        local_path: &str,
+        msg_id: Option<String>,
+        event_id: Option<String>,
+    ) -> anyhow::Result<SendMessageResponse> {
+        let image_url = self.media_url(local_path)?;
+        tracing::info!("Sending image to channel via URL: {}", image_url);
+
+        self.post_channel_message(channel_id, SendChannelMessageRequest {
+            content: None,
+            image: Some(image_url),
+            msg_id,
+            event_id,
+        }).await
+    }
+
+    /// Send a passive text reply to wherever the message came from
+    ///
+    /// Guild channels have no message sequence, so `msg_seq` only applies to groups.
+    async fn reply_text(
+        &self,
+        msg_event: &AtMessage,
+        content: &str,
+        event_id: Option<String>,
+        msg_seq: u32,
+    ) -> anyhow::Result<()> {
+        match &msg_event.target {
+            ReplyTarget::Group { group_openid } => {
+                self.send_group_message(group_openid, content, Some(msg_event.id.clone()), event_id, Some(msg_seq)).await?;
+            }
+            ReplyTarget::Channel { channel_id, .. } => {
+                self.send_channel_message(channel_id, content, Some(msg_event.id.clone()), event_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Send a passive image reply to wherever the message came from
+    async fn reply_image(
+        &self,
+        msg_event: &AtMessage,
+        local_path: &str,
+        event_id: Option<String>,
+        msg_seq: u32,
+    ) -> anyhow::Result<()> {
+        match &msg_event.target {
+            ReplyTarget::Group { group_openid } => {
+                self.send_group_image(group_openid, local_path, Some(msg_event.id.clone()), event_id, Some(msg_seq)).await?;
+            }
+            ReplyTarget::Channel { channel_id, .. } => {
+                self.send_channel_image(channel_id, local_path, Some(msg_event.id.clone()), event_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn send_message(&self, resp: MessageResponse, msg_event: &AtMessage) -> anyhow::Result<()> {
         match resp.content_type {
             ct if ct == ContentType::Text as i32 => {
-                self.send_group_message(
-                    &msg_event.group_openid,
-                    &resp.message,
-                    Some(msg_event.id.clone()),
-                    None,
-                    Some(1),
-                ).await?;
+                self.reply_text(msg_event, &resp.message, None, 1).await?;
             }
             ct if ct == ContentType::Image as i32 => {
                 // For image messages, the message field contains the file path
                 let local_path = resp.message.strip_prefix("file:///").unwrap_or(&resp.message);
-                self.send_group_image(
-                    &msg_event.group_openid,
-                    local_path,
-                    Some(msg_event.id.clone()),
-                    None,
-                    Some(1),
-                ).await?;
+                self.reply_image(msg_event, local_path, None, 1).await?;
             }
             _ => {
                 tracing::warn!("Unsupported content type: {}", resp.content_type);
                 // Fallback to sending as text
-                self.send_group_message(
-                    &msg_event.group_openid,
-                    "[Unsupported content type]",
-                    Some(msg_event.id.clone()),
-                    None,
-                    Some(1),
-                ).await?;
+                self.reply_text(msg_event, "[Unsupported content type]", None, 1).await?;
             }
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    const SECRET: &str = "test_secret";
+
+    fn test_config(max_body_bytes: usize) -> QQConfig {
+        QQConfig {
+            app_id: "123".to_string(),
+            client_secret: SECRET.to_string(),
+            access_token: String::new(),
+            api_base_url: QQ_AUTHORIZE_URL.to_string(),
+            media_base_url: None,
+            max_webhook_body_bytes: max_body_bytes,
+            max_webhook_age_seconds: 300,
+            notify_groups: Vec::new(),
+            notify_concurrency: 4,
+            quiet_hours: HashMap::new(),
+            offline_image_max_age_hours: 12,
+            pipeline_timeout_seconds: 240,
+            command_cooldown_seconds: 10,
+            command_cooldown_notice: true,
+            welcome_message: crate::config::default_welcome_message(),
+            welcome_image: None,
+            token_expires_in: 0,
+            client: crate::http_client::shared_client(),
+            token_fetched_at: None,
+            last_images: Default::default(),
+            uploaded_media: Default::default(),
+            command_cooldowns: Default::default(),
+            usage_stats_interval_minutes: 60,
+            default_routing: Default::default(),
+            command_routing: HashMap::new(),
+            command_usage: Default::default(),
+        }
+    }
+
+    fn test_router(max_body_bytes: usize) -> Router {
+        let qq_config = test_config(max_body_bytes);
+        let state = Arc::new(WebhookState {
+            client_secret: SECRET.to_string(),
+            max_age_seconds: 300,
+            qq_config: Arc::new(RwLock::new(qq_config)),
+            backend_manager: None,
+        });
+        webhook_router(state, max_body_bytes)
+    }
+
+    fn webhook_request(body: impl Into<Body>, headers: &[(&str, &str)]) -> Request<Body> {
+        let mut request = Request::post("/webhook");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.body(body.into()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_rejected() {
+        let body = format!(r#"{{"op":0,"d":{{"pad":"{}"}}}}"#, "x".repeat(2048));
+        let response = test_router(1024)
+            .oneshot(webhook_request(body, &[]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn test_validation_request_accepted() {
+        let body = r#"{"op":13,"d":{"plain_token":"abc","event_ts":"1725442341"}}"#;
+        let response = test_router(1024)
+            .oneshot(webhook_request(body, &[]))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["plain_token"], "abc");
+        assert_eq!(
+            json["signature"],
+            generate_validation_signature(SECRET, "1725442341", "abc").unwrap()
+        );
+    }
+
+    /// Send a signed READY event with the given signature timestamp
+    async fn send_signed_event(body: &str, timestamp: &str) -> StatusCode {
+        let signature = generate_validation_signature(SECRET, timestamp, body).unwrap();
+        let headers = [
+            ("X-Signature-Ed25519", signature.as_str()),
+            ("X-Signature-Timestamp", timestamp),
+        ];
+        test_router(1024)
+            .oneshot(webhook_request(body.to_string(), &headers))
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn test_signed_event_verified_over_raw_body() {
+        // Unusual spacing must survive untouched for the signature to match
+        let body = r#"{ "op": 0, "d": {"id": "evt"},  "t": "READY" }"#;
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let signature = generate_validation_signature(SECRET, &timestamp, body).unwrap();
+        let headers = [
+            ("X-Signature-Ed25519", signature.as_str()),
+            ("X-Signature-Timestamp", timestamp.as_str()),
+        ];
+
+        let response = test_router(1024)
+            .oneshot(webhook_request(body, &headers))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let tampered = body.replace("READY", "READY ");
+        let response = test_router(1024)
+            .oneshot(webhook_request(tampered, &headers))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_signature_timestamp_freshness() {
+        let body = r#"{"op":0,"d":{"id":"evt"},"t":"READY"}"#;
+        let now = chrono::Utc::now().timestamp();
+
+        assert_eq!(send_signed_event(body, &now.to_string()).await, StatusCode::NO_CONTENT);
+        assert_eq!(
+            send_signed_event(body, &(now - 3600).to_string()).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            send_signed_event(body, &(now + 3600).to_string()).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[test]
+    fn test_check_timestamp_freshness() {
+        assert!(check_timestamp_freshness("1000", 300, 1200).is_ok());
+        assert!(check_timestamp_freshness("1000", 300, 1301).is_err());
+        assert!(check_timestamp_freshness("1400", 300, 1000).is_err());
+        assert!(check_timestamp_freshness("soon", 300, 1000).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_malformed_envelope_rejected() {
+        for body in [r#"{"op":7,"d":{}}"#, r#"{"op":0,"d":"not an object"}"#] {
+            let response = test_router(1024)
+                .oneshot(webhook_request(body, &[]))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", body);
+        }
+    }
+
+    #[test]
+    fn test_offline_query_uses_cached_image() {
+        let config = test_config(1024);
+        let now = chrono::Utc::now();
+        let path = std::env::temp_dir().join("rinko_test_offline_sat_ao91.png");
+        std::fs::write(&path, b"png").unwrap();
+        let path = path.to_string_lossy().into_owned();
+        config.last_images.record("AO-91", &path, chrono::Duration::hours(12), now);
+        let query = ParsedCommand::parse("/q ao-91");
+
+        match local_reply(&config, &query, "/q ao-91", now + chrono::Duration::minutes(10)) {
+            LocalReply::CachedImage(image) => assert_eq!(image.path, path),
+            other => panic!("expected cached image, got {:?}", other),
+        }
+
+        // Unknown query, too old an image, or not a query: plain notice
+        let other = ParsedCommand::parse("/q so-50");
+        assert!(matches!(local_reply(&config, &other, "/q so-50", now), LocalReply::Text(_)));
+        let later = now + chrono::Duration::hours(13);
+        assert!(matches!(local_reply(&config, &query, "/q ao-91", later), LocalReply::Text(_)));
+        let help = ParsedCommand::parse("/help");
+        assert!(matches!(local_reply(&config, &help, "/help", now), LocalReply::Text(_)));
+    }
+
+    #[tokio::test]
+    async fn test_repeated_image_uploaded_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let uploads = Arc::new(AtomicUsize::new(0));
+        let sends = Arc::new(AtomicUsize::new(0));
+        let (upload_count, send_count) = (uploads.clone(), sends.clone());
+        let api = Router::new()
+            .route("/v2/groups/{group}/files", post(move || {
+                upload_count.fetch_add(1, Ordering::SeqCst);
+                async { Json(serde_json::json!({"file_uuid": "u1", "file_info": "info-1", "ttl": 3600})) }
+            }))
+            .route("/v2/groups/{group}/messages", post(move |Json(body): Json<serde_json::Value>| {
+                send_count.fetch_add(1, Ordering::SeqCst);
+                assert_eq!(body["media"]["file_info"], "info-1");
+                async { Json(serde_json::json!({"id": "m1", "timestamp": 0})) }
+            }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+
+        let mut config = test_config(1024);
+        config.api_base_url = format!("http://{}", addr);
+        config.media_base_url = Some("https://media.example/media".to_string());
+
+        for seq in 1..=2 {
+            config
+                .send_group_image("group", "/cache/rendered_images/sat_ao91.png", None, None, Some(seq))
+                .await
+                .unwrap();
+        }
+        assert_eq!(uploads.load(Ordering::SeqCst), 1);
+        assert_eq!(sends.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_notification_broadcast_continues_past_failing_group() {
+        use axum::http::StatusCode;
+
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        // Every send is held until all four are in flight, which only
+        // happens when they are sent side by side rather than one by one
+        let all_in_flight = Arc::new(tokio::sync::Barrier::new(4));
+        let api = Router::new().route("/v2/groups/{group}/messages", post(
+            move |axum::extract::Path(group): axum::extract::Path<String>| {
+                let recorded = recorded.clone();
+                let all_in_flight = all_in_flight.clone();
+                async move {
+                    all_in_flight.wait().await;
+                    if group == "group-bad" {
+                        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                    }
+                    recorded.lock().unwrap().push(group);
+                    Ok(Json(serde_json::json!({"id": "m1", "timestamp": 0})))
+                }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+
+        let mut config = test_config(1024);
+        config.api_base_url = format!("http://{}", addr);
+        let groups = ["group-1", "group-bad", "group-2", "group-3"].map(String::from).to_vec();
+        let sink = QQGroupSink::new(Arc::new(RwLock::new(config)), groups);
+
+        // A generous guard so a serial broadcast fails instead of hanging
+        let error = tokio::time::timeout(Duration::from_secs(30), sink.send_text("AO-91: Heard -> Not Heard"))
+            .await
+            .expect("group sends were not concurrent")
+            .unwrap_err()
+            .to_string();
+
+        assert!(error.starts_with("group group-bad:"), "{}", error);
+        let mut received = received.lock().unwrap().clone();
+        received.sort();
+        assert_eq!(received, vec!["group-1", "group-2", "group-3"]);
+    }
+
+    #[tokio::test]
+    async fn test_notification_suppressed_during_quiet_hours() {
+        use crate::frontend::quiet_hours::QuietHours;
+
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        let api = Router::new().route("/v2/groups/{group}/messages", post(
+            move |axum::extract::Path(group): axum::extract::Path<String>| {
+                recorded.lock().unwrap().push(group);
+                async { Json(serde_json::json!({"id": "m1", "timestamp": 0})) }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+
+        // A window around the current time in Tokyo (UTC+9, no DST) and one that has passed
+        let local = (chrono::Utc::now() + chrono::Duration::hours(9)).time();
+        let hhmm = |t: chrono::NaiveTime| t.format("%H:%M").to_string();
+        let quiet_now = QuietHours::parse(
+            &hhmm(local - chrono::Duration::hours(1)),
+            &hhmm(local + chrono::Duration::hours(1)),
+            "Asia/Tokyo",
+        )
+        .unwrap();
+        let quiet_earlier = QuietHours::parse(
+            &hhmm(local - chrono::Duration::hours(3)),
+            &hhmm(local - chrono::Duration::hours(2)),
+            "Asia/Tokyo",
+        )
+        .unwrap();
+
+        let mut config = test_config(1024);
+        config.api_base_url = format!("http://{}", addr);
+        config.quiet_hours.insert("group-asleep".to_string(), quiet_now);
+        config.quiet_hours.insert("group-awake".to_string(), quiet_earlier);
+        let groups = ["group-asleep", "group-awake", "group-1"].map(String::from).to_vec();
+        let sink = QQGroupSink::new(Arc::new(RwLock::new(config)), groups);
+
+        sink.send_text("AO-91: Heard -> Not Heard").await.unwrap();
+        let mut received = received.lock().unwrap().clone();
+        received.sort();
+        assert_eq!(received, vec!["group-1", "group-awake"]);
+    }
+
+    #[tokio::test]
+    async fn test_repeated_command_is_throttled() {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = bodies.clone();
+        let api = Router::new().route("/v2/groups/{group}/messages", post(
+            move |Json(body): Json<serde_json::Value>| {
+                recorded.lock().unwrap().push(body["content"].as_str().unwrap_or_default().to_string());
+                async { Json(serde_json::json!({"id": "m1", "timestamp": 0})) }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+
+        let mut config = test_config(1024);
+        config.api_base_url = format!("http://{}", addr);
+        let qq_config = Arc::new(RwLock::new(config));
+        let message = |content: &str| AtMessage {
+            id: "msg-1".to_string(),
+            target: ReplyTarget::Group { group_openid: "group-1".to_string() },
+            author_id: "user-1".to_string(),
+            content: content.to_string(),
+        };
+
+        process_at_message(&qq_config, &None, &message("/q iss"), None).await;
+        process_at_message(&qq_config, &None, &message("/Q ISS"), None).await;
+        {
+            let bodies = bodies.lock().unwrap();
+            assert_eq!(bodies.len(), 2);
+            assert!(bodies[0].contains("Message received: /q iss"), "{}", bodies[0]);
+            assert!(bodies[1].contains("Please wait 10s"), "{}", bodies[1]);
+        }
+
+        // Silently ignored when the notice is off
+        qq_config.write().await.command_cooldown_notice = false;
+        process_at_message(&qq_config, &None, &message("/q iss"), None).await;
+        assert_eq!(bodies.lock().unwrap().len(), 2);
+
+        // Only the answered command is counted; plain chat is not a command
+        process_at_message(&qq_config, &None, &message("/help"), None).await;
+        process_at_message(&qq_config, &None, &message("hello"), None).await;
+        let usage = qq_config.read().await.command_usage.snapshot();
+        assert_eq!(usage.totals.get("query"), Some(&1));
+        assert_eq!(usage.totals.get("help"), Some(&1));
+        assert_eq!(usage.totals.len(), 2);
+        assert_eq!(usage.groups["group-1"], usage.totals);
+    }
+
+    #[tokio::test]
+    async fn test_backend_only_command_dropped_while_offline() {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = bodies.clone();
+        let api = Router::new().route("/v2/groups/{group}/messages", post(
+            move |Json(body): Json<serde_json::Value>| {
+                recorded.lock().unwrap().push(body["content"].as_str().unwrap_or_default().to_string());
+                async { Json(serde_json::json!({"id": "m1", "timestamp": 0})) }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+
+        let mut config = test_config(1024);
+        config.api_base_url = format!("http://{}", addr);
+        config.command_cooldown_seconds = 0;
+        config.command_routing.insert("query".to_string(), routing::RoutingPolicy::BackendOnly);
+        let qq_config = Arc::new(RwLock::new(config));
+        let message = |content: &str| AtMessage {
+            id: "msg-1".to_string(),
+            target: ReplyTarget::Group { group_openid: "group-1".to_string() },
+            author_id: "user-1".to_string(),
+            content: content.to_string(),
+        };
+
+        process_at_message(&qq_config, &None, &message("/q iss"), None).await;
+        assert!(bodies.lock().unwrap().is_empty());
+
+        // Other commands keep the backend-first default and are answered locally
+        process_at_message(&qq_config, &None, &message("/help"), None).await;
+        assert_eq!(bodies.lock().unwrap().len(), 1);
+
+        qq_config.write().await.default_routing = routing::RoutingPolicy::LocalOnly;
+        qq_config.write().await.command_routing.clear();
+        process_at_message(&qq_config, &None, &message("/q iss"), None).await;
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert!(bodies[1].contains("Message received: /q iss"), "{}", bodies[1]);
+    }
+
+    #[tokio::test]
+    async fn test_status_reply_without_backend() {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = bodies.clone();
+        let api = Router::new().route("/v2/groups/{group}/messages", post(
+            move |Json(body): Json<serde_json::Value>| {
+                recorded.lock().unwrap().push(body["content"].as_str().unwrap_or_default().to_string());
+                async { Json(serde_json::json!({"id": "m1", "timestamp": 0})) }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+
+        let mut config = test_config(1024);
+        config.api_base_url = format!("http://{}", addr);
+        let qq_config = Arc::new(RwLock::new(config));
+        let message = AtMessage {
+            id: "msg-1".to_string(),
+            target: ReplyTarget::Group { group_openid: "group-1".to_string() },
+            author_id: "user-1".to_string(),
+            content: "/status".to_string(),
+        };
+        let manager = Some(Arc::new(BackendConnectionManager::new(crate::config::BackendConfig {
+            enable: true,
+            url: "http://127.0.0.1:1".to_string(),
+            frontend_id: "qq-1".to_string(),
+            heartbeat_interval: 30,
+        })));
+
+        process_at_message(&qq_config, &manager, &message, None).await;
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 1);
+        assert!(bodies[0].contains(&format!("Rinko v{}", env!("CARGO_PKG_VERSION"))), "{}", bodies[0]);
+        assert!(bodies[0].contains("Backend: disconnected"), "{}", bodies[0]);
+    }
+
+    #[tokio::test]
+    async fn test_slow_pipeline_aborted_at_deadline() {
+        let started = tokio::time::Instant::now();
+        let sent = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let sent_flag = sent.clone();
+        let pipeline = async move {
+            sleep(Duration::from_millis(300)).await; // slow backend
+            sleep(Duration::from_millis(300)).await; // slow upload
+            sent_flag.store(true, std::sync::atomic::Ordering::SeqCst);
+        };
+
+        assert!(!run_with_deadline(Duration::from_millis(100), "msg", pipeline).await);
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(100) && elapsed < Duration::from_millis(300), "{:?}", elapsed);
+        assert!(!sent.load(std::sync::atomic::Ordering::SeqCst));
+
+        assert!(run_with_deadline(Duration::from_millis(100), "msg", async {}).await);
+    }
+
+    /// Serve the channel messages endpoint, recording each request body
+    async fn mock_channel_api() -> (String, Arc<std::sync::Mutex<Vec<serde_json::Value>>>) {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = bodies.clone();
+        let api = Router::new().route("/channels/{channel}/messages", post(
+            move |axum::extract::Path(channel): axum::extract::Path<String>, Json(body): Json<serde_json::Value>| {
+                assert_eq!(channel, "chan-1");
+                recorded.lock().unwrap().push(body);
+                async { Json(serde_json::json!({"id": "m1", "timestamp": 0})) }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+        (format!("http://{}", addr), bodies)
+    }
+
+    #[tokio::test]
+    async fn test_channel_message_payload() {
+        let (base_url, bodies) = mock_channel_api().await;
+        let mut config = test_config(1024);
+        config.api_base_url = base_url;
+        config.media_base_url = Some("https://media.example/media/".to_string());
+
+        config.send_channel_message("chan-1", "hello", Some("msg-1".to_string()), None).await.unwrap();
+        config
+            .send_channel_image("chan-1", "/cache/rendered_images/sat_ao91.png", Some("msg-1".to_string()), None)
+            .await
+            .unwrap();
+
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies[0], serde_json::json!({"content": "hello", "msg_id": "msg-1"}));
+        assert_eq!(
+            bodies[1],
+            serde_json::json!({"image": "https://media.example/media/sat_ao91.png", "msg_id": "msg-1"})
+        );
+    }
+
+    #[test]
+    fn test_channel_event_to_at_message() {
+        let data = serde_json::json!({
+            "id": "msg-1",
+            "channel_id": "chan-1",
+            "guild_id": "guild-1",
+            "author": {"id": "user-1", "username": "op"},
+            "content": "<@!1234> /q ao-91",
+        });
+        let msg: AtMessage = serde_json::from_value::<ChannelMessageEvent>(data).unwrap().into();
+        assert_eq!(msg.content, "/q ao-91");
+        assert_eq!(msg.author_id, "user-1");
+        assert_eq!(
+            msg.target,
+            ReplyTarget::Channel { channel_id: "chan-1".to_string(), guild_id: "guild-1".to_string() }
+        );
+        assert_eq!(strip_mentions("hi <@!1>"), "hi <@!1>");
+    }
+
+    #[tokio::test]
+    async fn test_welcome_sent_on_group_add() {
+        let bodies = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = bodies.clone();
+        let api = Router::new().route("/v2/groups/{group}/messages", post(
+            move |axum::extract::Path(group): axum::extract::Path<String>, Json(body): Json<serde_json::Value>| {
+                recorded.lock().unwrap().push((group, body));
+                async { Json(serde_json::json!({"id": "m1", "timestamp": 0})) }
+            },
+        ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, api).await.unwrap() });
+
+        let mut config = test_config(1024);
+        config.api_base_url = format!("http://{}", addr);
+        let welcome = config.welcome_message.clone();
+        let qq_config = Arc::new(RwLock::new(config));
+        let data = serde_json::json!({"group_openid": "group-1", "op_member_openid": "op", "timestamp": 0});
+
+        handle_event(&qq_config, &None, "GROUP_ADD_ROBOT", &data, Some("GROUP_ADD_ROBOT:1".to_string())).await;
+        handle_event(&qq_config, &None, "GROUP_ADD_ROBOT", &data, None).await;
+
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0].0, "group-1");
+        assert_eq!(bodies[0].1["content"], welcome.as_str());
+        assert_eq!(bodies[0].1["event_id"], "GROUP_ADD_ROBOT:1");
+        assert!(bodies[0].1.get("msg_id").is_none());
+        // Without an event_id it goes out as an active message
+        assert!(bodies[1].1.get("event_id").is_none());
+        assert!(welcome.contains("/q"));
+    }
+}
@@ -0,0 +1,143 @@
+//! Per-group quiet hours for proactive broadcasts
+//!
+//! Notifications are not pushed to a group during its quiet hours; replies
+//! to commands are unaffected.
+use chrono::{DateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+/// Daily window, in the group's local time, without proactive messages
+///
+/// Parsed when the config is loaded, so a malformed window stops startup
+/// instead of silently never applying. The group's timezone is an IANA name,
+/// so the window follows daylight saving time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "QuietHoursConfig", into = "QuietHoursConfig")]
+pub struct QuietHours {
+    start: NaiveTime,
+    end: NaiveTime,
+    timezone: Tz,
+}
+
+/// `[qq.quiet_hours]` entry as written in the config file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QuietHoursConfig {
+    start: String,                       // "HH:MM", e.g. "23:00"
+    end: String,                         // "HH:MM"; earlier than start = window crosses midnight
+    #[serde(default = "default_timezone")]
+    timezone: String,                    // the group's IANA timezone, e.g. "Asia/Shanghai"
+}
+
+fn default_timezone() -> String {
+    "Asia/Shanghai".to_string()
+}
+
+impl TryFrom<QuietHoursConfig> for QuietHours {
+    type Error = String;
+
+    fn try_from(config: QuietHoursConfig) -> Result<Self, String> {
+        QuietHours::parse(&config.start, &config.end, &config.timezone)
+    }
+}
+
+impl From<QuietHours> for QuietHoursConfig {
+    fn from(quiet: QuietHours) -> Self {
+        Self {
+            start: quiet.start.format("%H:%M").to_string(),
+            end: quiet.end.format("%H:%M").to_string(),
+            timezone: quiet.timezone.name().to_string(),
+        }
+    }
+}
+
+impl QuietHours {
+    /// Window from "HH:MM" times and an IANA timezone such as "Asia/Shanghai"
+    pub fn parse(start: &str, end: &str, timezone: &str) -> Result<Self, String> {
+        let time = |value: &str| {
+            NaiveTime::parse_from_str(value, "%H:%M")
+                .map_err(|_| format!("quiet hours time \"{}\" must be HH:MM", value))
+        };
+        let timezone = timezone.parse::<Tz>().map_err(|_| {
+            format!("quiet hours timezone \"{}\" must be an IANA name like Asia/Shanghai", timezone)
+        })?;
+        Ok(Self { start: time(start)?, end: time(end)?, timezone })
+    }
+
+    /// Whether `now` falls inside the window
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        let local = now.with_timezone(&self.timezone).time();
+        if self.start <= self.end {
+            self.start <= local && local < self.end
+        } else {
+            local >= self.start || local < self.end
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(rfc3339).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_overnight_window_in_group_timezone() {
+        let quiet = QuietHours::parse("23:00", "07:00", "Asia/Shanghai").unwrap();
+        // 23:30 and 06:59 BJT
+        assert!(quiet.contains(at("2026-02-16T15:30:00Z")));
+        assert!(quiet.contains(at("2026-02-16T22:59:00Z")));
+        // 07:00 and 22:59 BJT
+        assert!(!quiet.contains(at("2026-02-16T23:00:00Z")));
+        assert!(!quiet.contains(at("2026-02-16T14:59:00Z")));
+    }
+
+    #[test]
+    fn test_same_day_window_and_invalid_config() {
+        let quiet = QuietHours::parse("01:00", "05:00", "America/New_York").unwrap();
+        assert!(quiet.contains(at("2026-02-16T08:00:00Z"))); // 03:00 EST
+        assert!(!quiet.contains(at("2026-02-16T11:00:00Z"))); // 06:00 EST
+
+        assert!(QuietHours::parse("25:00", "05:00", "America/New_York").is_err());
+        assert!(QuietHours::parse("01:00", "05:00", "+08:00").is_err());
+    }
+
+    #[test]
+    fn test_window_follows_daylight_saving_time() {
+        let quiet = QuietHours::parse("01:00", "05:00", "America/New_York").unwrap();
+        // 03:00 local is 08:00Z in winter (EST) and 07:00Z in summer (EDT)
+        assert!(quiet.contains(at("2026-07-16T07:00:00Z")));
+        assert!(!quiet.contains(at("2026-07-16T09:30:00Z"))); // 05:30 EDT
+        assert!(quiet.contains(at("2026-01-16T09:30:00Z"))); // 04:30 EST
+    }
+
+    #[test]
+    fn test_invalid_window_fails_config_load() {
+        #[derive(Deserialize)]
+        struct Groups {
+            quiet_hours: std::collections::HashMap<String, QuietHours>,
+        }
+
+        let groups: Groups = toml::from_str(
+            r#"
+            [quiet_hours.group-1]
+            start = "23:00"
+            end = "07:00"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(groups.quiet_hours["group-1"], QuietHours::parse("23:00", "07:00", "Asia/Shanghai").unwrap());
+
+        let error = toml::from_str::<Groups>(
+            r#"
+            [quiet_hours.group-1]
+            start = "23:00"
+            end = "7am"
+            "#,
+        )
+        .err()
+        .unwrap();
+        assert!(error.to_string().contains("\"7am\" must be HH:MM"), "{}", error);
+    }
+}
@@ -0,0 +1,89 @@
+//! Where a message is answered: by the backend, locally, or both in turn
+//!
+//! Local handling covers what the frontend can answer on its own: a
+//! query's last rendered image, or the "backend offline" notice. Each
+//! command type can have its own policy, e.g. local-only for everything
+//! during backend maintenance, or local-first for queries so a recent
+//! cached image is reused without a backend round trip.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Order in which the backend and local handling are tried
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RoutingPolicy {
+    /// Backend when connected, local reply if it is offline or fails
+    #[default]
+    BackendFirst,
+    /// A cached local answer if there is one, otherwise as `BackendFirst`
+    LocalFirst,
+    /// Backend only; nothing is sent if it is offline or fails
+    BackendOnly,
+    /// Never contact the backend
+    LocalOnly,
+}
+
+/// One attempt at answering a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteStep {
+    /// Forward to the backend; done if it answers
+    Backend,
+    /// Send a cached local answer (e.g. a query's last image); done if any
+    LocalCached,
+    /// Send the local reply, falling back to the offline notice; always done
+    Local,
+}
+
+impl RoutingPolicy {
+    /// Steps to try in order, given whether the backend is connected
+    pub fn steps(self, backend_connected: bool) -> &'static [RouteStep] {
+        use RouteStep::*;
+        match (self, backend_connected) {
+            (RoutingPolicy::BackendFirst, true) => &[Backend, Local],
+            (RoutingPolicy::LocalFirst, true) => &[LocalCached, Backend, Local],
+            (RoutingPolicy::BackendOnly, true) => &[Backend],
+            (RoutingPolicy::BackendOnly, false) => &[],
+            (RoutingPolicy::BackendFirst | RoutingPolicy::LocalFirst | RoutingPolicy::LocalOnly, _) => &[Local],
+        }
+    }
+}
+
+/// Policy for a command type: its override, else the default
+pub fn policy_for(
+    default: RoutingPolicy,
+    overrides: &HashMap<String, RoutingPolicy>,
+    command_type: &str,
+) -> RoutingPolicy {
+    overrides.get(command_type).copied().unwrap_or(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use RouteStep::*;
+
+    #[test]
+    fn test_policy_steps() {
+        let cases = [
+            (RoutingPolicy::BackendFirst, true, &[Backend, Local][..]),
+            (RoutingPolicy::BackendFirst, false, &[Local][..]),
+            (RoutingPolicy::LocalFirst, true, &[LocalCached, Backend, Local][..]),
+            (RoutingPolicy::LocalFirst, false, &[Local][..]),
+            (RoutingPolicy::BackendOnly, true, &[Backend][..]),
+            (RoutingPolicy::BackendOnly, false, &[][..]),
+            (RoutingPolicy::LocalOnly, true, &[Local][..]),
+            (RoutingPolicy::LocalOnly, false, &[Local][..]),
+        ];
+        for (policy, connected, expected) in cases {
+            assert_eq!(policy.steps(connected), expected, "{:?} connected={}", policy, connected);
+        }
+    }
+
+    #[test]
+    fn test_policy_for_command() {
+        let overrides: HashMap<String, RoutingPolicy> =
+            toml::from_str(r#"query = "local-first""#).unwrap();
+        assert_eq!(policy_for(RoutingPolicy::BackendOnly, &overrides, "query"), RoutingPolicy::LocalFirst);
+        assert_eq!(policy_for(RoutingPolicy::BackendOnly, &overrides, "report"), RoutingPolicy::BackendOnly);
+    }
+}
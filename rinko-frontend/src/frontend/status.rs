@@ -0,0 +1,105 @@
+//! Reply to "/status": bot version, backend state and satellite data freshness
+use crate::backend::connection_manager::{BackendConnectionManager, ConnectionState};
+use chrono::{DateTime, TimeZone, Utc};
+use rinko_common::proto::StatusResponse;
+use std::sync::Arc;
+
+/// Gather the backend's state and status, then format the reply
+///
+/// Details beyond the connection state are only shown while the backend
+/// answers the status request.
+pub async fn bot_status(backend_manager: &Option<Arc<BackendConnectionManager>>) -> String {
+    let Some(manager) = backend_manager else {
+        return status_text(None, None, Utc::now());
+    };
+    let state = manager.state().await;
+    let mut backend = None;
+    if state == ConnectionState::Connected
+        && let Some(client) = &mut *manager.client().write().await
+    {
+        match client.get_status().await {
+            Ok(status) => backend = Some(status),
+            Err(e) => tracing::warn!("Failed to fetch backend status: {}", e),
+        }
+    }
+    status_text(Some(state), backend.as_ref(), Utc::now())
+}
+
+/// Format the status reply; `state` is `None` when no backend is configured
+pub fn status_text(
+    state: Option<ConnectionState>,
+    backend: Option<&StatusResponse>,
+    now: DateTime<Utc>,
+) -> String {
+    let mut lines = vec![format!("\nRinko v{}", env!("CARGO_PKG_VERSION"))];
+
+    let state = match state {
+        None => "disabled",
+        Some(ConnectionState::Connected) => "connected",
+        Some(ConnectionState::Connecting) => "connecting",
+        Some(ConnectionState::Disconnected) => "disconnected",
+    };
+    match backend {
+        Some(status) => {
+            let uptime = Utc
+                .timestamp_opt(status.started_at, 0)
+                .single()
+                .map(|started| format_duration(now - started))
+                .unwrap_or_else(|| "?".to_string());
+            lines.push(format!("Backend: {} (v{}, up {})", state, status.version, uptime));
+            let last_update = match Utc.timestamp_opt(status.last_amsat_update, 0).single() {
+                Some(time) if status.last_amsat_update > 0 => format!(
+                    "{} ({} ago)",
+                    time.format("%Y-%m-%d %H:%M UTC"),
+                    format_duration(now - time)
+                ),
+                _ => "not yet".to_string(),
+            };
+            lines.push(format!("AMSAT update: {}", last_update));
+            lines.push(format!("Tracked satellites: {}", status.tracked_satellites));
+        }
+        None => lines.push(format!("Backend: {}", state)),
+    }
+
+    lines.join("\n")
+}
+
+/// Coarse duration such as "2d 3h", "3h 12m" or "5m"
+fn format_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_text() {
+        let now = Utc.with_ymd_and_hms(2026, 10, 17, 12, 0, 0).unwrap();
+        let backend = StatusResponse {
+            version: "0.9.0".to_string(),
+            started_at: (now - chrono::Duration::minutes(26 * 60 + 5)).timestamp(),
+            last_amsat_update: Utc.with_ymd_and_hms(2026, 10, 17, 11, 48, 0).unwrap().timestamp(),
+            tracked_satellites: 42,
+        };
+
+        let text = status_text(Some(ConnectionState::Connected), Some(&backend), now);
+        assert!(text.contains(&format!("Rinko v{}", env!("CARGO_PKG_VERSION"))), "{}", text);
+        assert!(text.contains("Backend: connected (v0.9.0, up 1d 2h)"), "{}", text);
+        assert!(text.contains("AMSAT update: 2026-10-17 11:48 UTC (12m ago)"), "{}", text);
+        assert!(text.contains("Tracked satellites: 42"), "{}", text);
+
+        let text = status_text(Some(ConnectionState::Disconnected), None, now);
+        assert!(text.contains(env!("CARGO_PKG_VERSION")), "{}", text);
+        assert!(text.ends_with("Backend: disconnected"), "{}", text);
+        assert!(status_text(None, None, now).ends_with("Backend: disabled"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Per-command usage counters for operators
+//!
+//! Handled commands are counted by type, overall and per group. Only the
+//! first `MAX_TRACKED_GROUPS` groups get their own breakdown; later groups
+//! are pooled under `OTHER_GROUPS` so the map stays bounded.
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Groups with their own breakdown before the rest are pooled
+pub const MAX_TRACKED_GROUPS: usize = 50;
+
+/// Breakdown key for groups past `MAX_TRACKED_GROUPS`
+pub const OTHER_GROUPS: &str = "other";
+
+/// Command counts since startup
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UsageSnapshot {
+    /// Command type -> times handled
+    pub totals: BTreeMap<String, u64>,
+    /// Group or channel -> command type -> times handled
+    pub groups: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl UsageSnapshot {
+    /// One-line summary for the log, e.g. "query=12 help=3"
+    pub fn summary(&self) -> String {
+        self.totals
+            .iter()
+            .map(|(command, count)| format!("{}={}", command, count))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Thread-safe command counters
+#[derive(Debug, Default)]
+pub struct CommandUsage {
+    counts: Mutex<UsageSnapshot>,
+}
+
+impl CommandUsage {
+    /// Count one handled command of `command` type in `scope`
+    pub fn record(&self, scope: &str, command: &str) {
+        let mut counts = self.counts.lock().unwrap();
+        *counts.totals.entry(command.to_string()).or_default() += 1;
+
+        let tracked = counts.groups.contains_key(scope)
+            || counts.groups.keys().filter(|g| *g != OTHER_GROUPS).count() < MAX_TRACKED_GROUPS;
+        let group = if tracked { scope } else { OTHER_GROUPS };
+        *counts
+            .groups
+            .entry(group.to_string())
+            .or_default()
+            .entry(command.to_string())
+            .or_default() += 1;
+    }
+
+    /// Copy of the current counts
+    pub fn snapshot(&self) -> UsageSnapshot {
+        self.counts.lock().unwrap().clone()
+    }
+
+    /// Log the counts every `interval` until the process exits
+    pub fn start_report_task(self: Arc<Self>, interval: Duration) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let snapshot = self.snapshot();
+                if snapshot.totals.is_empty() {
+                    continue;
+                }
+                tracing::info!(
+                    "Command usage: {} ({} group(s))",
+                    snapshot.summary(),
+                    snapshot.groups.len()
+                );
+                match serde_json::to_string(&snapshot) {
+                    Ok(json) => tracing::debug!("Command usage by group: {}", json),
+                    Err(e) => tracing::warn!("Failed to serialize command usage: {}", e),
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_breakdown_is_bounded() {
+        let usage = CommandUsage::default();
+        for i in 0..MAX_TRACKED_GROUPS + 5 {
+            usage.record(&format!("group-{}", i), "query");
+        }
+        // Groups already tracked keep their own counts
+        usage.record("group-0", "help");
+
+        let snapshot = usage.snapshot();
+        assert_eq!(snapshot.totals["query"], MAX_TRACKED_GROUPS as u64 + 5);
+        assert_eq!(snapshot.groups.len(), MAX_TRACKED_GROUPS + 1);
+        assert_eq!(snapshot.groups[OTHER_GROUPS]["query"], 5);
+        assert_eq!(snapshot.groups["group-0"]["help"], 1);
+        assert_eq!(snapshot.summary(), format!("help=1 query={}", MAX_TRACKED_GROUPS + 5));
+    }
+}
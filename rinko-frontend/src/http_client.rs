@@ -0,0 +1,21 @@
+//! Shared outbound HTTP client
+//!
+//! `reqwest::Client` keeps its connection pool internally, so every QQ API
+//! call goes through this one lazily built client to reuse kept-alive
+//! connections instead of reconnecting per request.
+use std::sync::LazyLock;
+use std::time::Duration;
+
+const REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+static CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
+    rinko_common::http_client::build_client(Duration::from_secs(REQUEST_TIMEOUT_SECONDS), None)
+        .expect("Failed to build shared HTTP client")
+});
+
+/// A handle to the process-wide HTTP client
+///
+/// Clones share the same connection pool.
+pub fn shared_client() -> reqwest::Client {
+    CLIENT.clone()
+}
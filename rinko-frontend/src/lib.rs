@@ -2,5 +2,7 @@ pub mod logging;
 pub mod config;
 pub mod frontend;
 pub mod utils;
+pub mod http_client;
 pub mod backend;
-// pub mod command;
\ No newline at end of file
+pub mod command;
+pub mod notify;
\ No newline at end of file
@@ -14,12 +14,13 @@ pub struct LoggerGuard(WorkerGuard);
 pub fn init_logging(log_dir: impl AsRef<Path>, prefix: &str, level: &str) -> LoggerGuard {
     let log_dir = log_dir.as_ref().to_path_buf();
 
-    let level = match level {
-        "trace" => level,
-        "debug" => level,
-        "info" => level,
-        "warn" => level,
-        "error" => level,
+    let level = level.to_ascii_lowercase();
+    let level = match level.as_str() {
+        "trace" => "trace",
+        "debug" => "debug",
+        "info" => "info",
+        "warn" => "warn",
+        "error" => "error",
         _ => {
             tracing::warn!("Invalid log level '{}', defaulting to 'info'", level);
             "info"
@@ -29,8 +30,8 @@ pub fn init_logging(log_dir: impl AsRef<Path>, prefix: &str, level: &str) -> Log
     let builder = EnvFilter::builder()
         .with_default_directive(level.parse().unwrap());
 
-    let console_filter = builder.clone().parse_lossy(&std::env::var("RUST_LOG").unwrap_or_default());
-    let file_filter = builder.parse_lossy(&std::env::var("RUST_LOG").unwrap_or_default());
+    let console_filter = builder.clone().parse_lossy(std::env::var("RUST_LOG").unwrap_or_default());
+    let file_filter = builder.parse_lossy(std::env::var("RUST_LOG").unwrap_or_default());
 
     let file_appender = RollingFileAppender::builder()
         .rotation(Rotation::DAILY)
@@ -80,15 +81,16 @@ fn cleanup_old_logs(log_dir: &Path, prefix: &str, max_age: Duration) -> std::io:
         let entry = entry?;
         let path = entry.path();
 
-        if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
-            if file_name.starts_with(prefix) && file_name.ends_with(".log") {
-                let metadata = fs::metadata(&path)?;
-                if let Ok(modified) = metadata.modified() {
-                    if now.duration_since(modified).unwrap_or_default() > max_age {
-                        fs::remove_file(&path)?;
-                        tracing::info!("Old log file deleted: {}", file_name);
-                    }
-                }
+        if let Some(file_name) = path.file_name().and_then(|n| n.to_str())
+            && file_name.starts_with(prefix)
+            && file_name.ends_with(".log")
+        {
+            let metadata = fs::metadata(&path)?;
+            if let Ok(modified) = metadata.modified()
+                && now.duration_since(modified).unwrap_or_default() > max_age
+            {
+                fs::remove_file(&path)?;
+                tracing::info!("Old log file deleted: {}", file_name);
             }
         }
     }
@@ -2,6 +2,8 @@ use rinko_frontend::logging;
 use rinko_frontend::config;
 use rinko_frontend::config::QQConfig;
 use rinko_frontend::backend::BackendConnectionManager;
+use rinko_frontend::frontend::qq::QQGroupSink;
+use rinko_frontend::notify::{NotificationSinks, WebhookSink};
 use rinko_frontend::utils::Platform;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -59,6 +61,30 @@ async fn main() -> anyhow::Result<()> {
             QQConfig::start_token_renewal_task(qq_cfg_shared.clone());
             tracing::info!("QQ token auto-renewal task started.");
 
+            let usage_interval = qq_cfg_shared.read().await.usage_stats_interval_minutes;
+            if usage_interval > 0 {
+                let usage = qq_cfg_shared.read().await.command_usage.clone();
+                usage.start_report_task(std::time::Duration::from_secs(usage_interval * 60));
+                tracing::info!("Command usage logged every {} minute(s)", usage_interval);
+            }
+
+            // Forward backend notifications to QQ groups and any configured webhooks.
+            // The QQ sink is always added so subscription posts reach their groups
+            // even without broadcast notify_groups.
+            let mut sinks = NotificationSinks::default();
+            let notify_groups = qq_cfg_shared.read().await.notify_groups.clone();
+            sinks.add(Arc::new(QQGroupSink::new(qq_cfg_shared.clone(), notify_groups)));
+            let media_base_url = qq_cfg_shared.read().await.media_base_url.clone();
+            for url in &bot_config.notify_webhooks {
+                sinks.add(Arc::new(WebhookSink::new(url, media_base_url.as_deref())));
+            }
+            if let Some(manager) = &backend_manager
+                && !sinks.is_empty()
+            {
+                tracing::info!("Notifications enabled for {} sink(s)", sinks.len());
+                sinks.spawn_forwarder(manager.subscribe_notifications());
+            }
+
             // Start webhook server
             let qq_cfg_for_webhook = qq_cfg_shared.clone();
             let backend_for_webhook = backend_manager.clone();
@@ -0,0 +1,229 @@
+//! Proactive notifications (e.g. satellite status changes)
+//!
+//! Alerts pushed by the backend are fanned out to every configured
+//! [`NotificationSink`], so they are not tied to QQ groups.
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+
+/// A notification pushed by the backend
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification {
+    Text(String),
+    /// Local path of a rendered image
+    Image(String),
+    /// Text for the listed groups only (e.g. subscription posts)
+    GroupText { groups: Vec<String>, text: String },
+    /// Image for the listed groups only
+    GroupImage { groups: Vec<String>, path: String },
+}
+
+/// A destination for proactive notifications
+#[async_trait]
+pub trait NotificationSink: Send + Sync {
+    /// Short name used in logs
+    fn name(&self) -> String;
+    async fn send_text(&self, text: &str) -> anyhow::Result<()>;
+    async fn send_image(&self, local_path: &str) -> anyhow::Result<()>;
+
+    /// Send text to specific groups; sinks without groups ignore it
+    async fn send_group_text(&self, _groups: &[String], _text: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Send an image to specific groups; sinks without groups ignore it
+    async fn send_group_image(&self, _groups: &[String], _local_path: &str) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Every sink a notification is delivered to
+#[derive(Clone, Default)]
+pub struct NotificationSinks {
+    sinks: Vec<Arc<dyn NotificationSink>>,
+}
+
+impl NotificationSinks {
+    pub fn add(&mut self, sink: Arc<dyn NotificationSink>) {
+        self.sinks.push(sink);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sinks.len()
+    }
+
+    /// Deliver to every sink; one failing sink does not stop the others
+    pub async fn notify(&self, notification: &Notification) {
+        for sink in &self.sinks {
+            let result = match notification {
+                Notification::Text(text) => sink.send_text(text).await,
+                Notification::Image(path) => sink.send_image(path).await,
+                Notification::GroupText { groups, text } => sink.send_group_text(groups, text).await,
+                Notification::GroupImage { groups, path } => sink.send_group_image(groups, path).await,
+            };
+            if let Err(e) = result {
+                tracing::error!("Failed to notify {}: {}", sink.name(), e);
+            }
+        }
+    }
+
+    /// Forward everything received on `notifications` until the channel closes
+    pub fn spawn_forwarder(
+        self,
+        mut notifications: broadcast::Receiver<Notification>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                let notification = match notifications.recv().await {
+                    Ok(notification) => notification,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Skipped {} notifications", skipped);
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+                self.notify(&notification).await;
+            }
+        })
+    }
+}
+
+/// Posts notifications as JSON to an HTTP endpoint
+///
+/// Bodies are `{"text": ...}` or `{"image": <public media URL>}`. Images
+/// need `media_base_url`, since the endpoint cannot read local files.
+pub struct WebhookSink {
+    url: String,
+    media_base_url: Option<String>,
+    client: reqwest::Client,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str, media_base_url: Option<&str>) -> Self {
+        Self {
+            url: url.to_string(),
+            media_base_url: media_base_url.map(str::to_string),
+            client: crate::http_client::shared_client(),
+        }
+    }
+
+    async fn post(&self, body: serde_json::Value) -> anyhow::Result<()> {
+        self.client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationSink for WebhookSink {
+    fn name(&self) -> String {
+        format!("webhook {}", self.url)
+    }
+
+    async fn send_text(&self, text: &str) -> anyhow::Result<()> {
+        self.post(serde_json::json!({ "text": text })).await
+    }
+
+    async fn send_image(&self, local_path: &str) -> anyhow::Result<()> {
+        let Some(base_url) = &self.media_base_url else {
+            anyhow::bail!("[qq] media_base_url is not configured, cannot publish image {}", local_path);
+        };
+        let url = crate::utils::public_media_url(base_url, local_path)?;
+        self.post(serde_json::json!({ "image": url })).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct MockSink {
+        received: Mutex<Vec<Notification>>,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl NotificationSink for MockSink {
+        fn name(&self) -> String {
+            "mock".to_string()
+        }
+
+        async fn send_text(&self, text: &str) -> anyhow::Result<()> {
+            self.received.lock().unwrap().push(Notification::Text(text.to_string()));
+            if self.fail { anyhow::bail!("unreachable") } else { Ok(()) }
+        }
+
+        async fn send_image(&self, local_path: &str) -> anyhow::Result<()> {
+            self.received.lock().unwrap().push(Notification::Image(local_path.to_string()));
+            if self.fail { anyhow::bail!("unreachable") } else { Ok(()) }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forwarder_delivers_to_every_sink() {
+        let failing = Arc::new(MockSink { fail: true, ..Default::default() });
+        let working = Arc::new(MockSink::default());
+        let mut sinks = NotificationSinks::default();
+        sinks.add(failing.clone());
+        sinks.add(working.clone());
+
+        let (tx, rx) = broadcast::channel(8);
+        let forwarder = sinks.spawn_forwarder(rx);
+        let alerts = vec![
+            Notification::Text("AO-91: Heard -> Not Heard".to_string()),
+            Notification::Image("/cache/rendered_images/sat_ao91.png".to_string()),
+        ];
+        for alert in &alerts {
+            tx.send(alert.clone()).unwrap();
+        }
+        // Group posts only reach sinks that address groups
+        tx.send(Notification::GroupText {
+            groups: vec!["group-1".to_string()],
+            text: "AO-91 status update".to_string(),
+        })
+        .unwrap();
+        drop(tx);
+        forwarder.await.unwrap();
+
+        assert_eq!(*failing.received.lock().unwrap(), alerts);
+        assert_eq!(*working.received.lock().unwrap(), alerts);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_image_posts_public_url() {
+        use axum::{Json, Router, routing::post};
+
+        let bodies = Arc::new(Mutex::new(Vec::new()));
+        let recorded = bodies.clone();
+        let app = Router::new().route("/hook", post(move |Json(body): Json<serde_json::Value>| {
+            recorded.lock().unwrap().push(body);
+            async {}
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let path = "/cache/rendered_images/sat_ao91.png";
+        let sink = WebhookSink::new(&url, Some("https://media.example/media/"));
+        sink.send_image(path).await.unwrap();
+        assert_eq!(
+            *bodies.lock().unwrap(),
+            vec![serde_json::json!({ "image": "https://media.example/media/sat_ao91.png" })]
+        );
+
+        // Without a media server there is nothing the endpoint could fetch
+        let sink = WebhookSink::new(&url, None);
+        assert!(sink.send_image(path).await.is_err());
+        assert_eq!(bodies.lock().unwrap().len(), 1);
+    }
+}
@@ -18,6 +18,18 @@ pub trait BotAdapter {
     async fn send_message(&self, msg: &UnifiedMessage) -> anyhow::Result<()>;
 }
 
+/// Public URL the media server serves a rendered file under
+///
+/// The media server exposes the render directory flat, so only the file
+/// name of `local_path` is kept.
+pub fn public_media_url(base_url: &str, local_path: &str) -> anyhow::Result<String> {
+    let filename = std::path::Path::new(local_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| anyhow::anyhow!("Invalid file path: {}", local_path))?;
+    Ok(format!("{}/{}", base_url.trim_end_matches('/'), filename))
+}
+
 pub struct BotManager {
     // dynamic dispatch for different bot adapters
     pub adapters: Vec<Box<dyn BotAdapter + Send + Sync>>,
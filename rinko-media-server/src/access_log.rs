@@ -0,0 +1,154 @@
+//! Structured per-request access log
+//!
+//! Emits one event per response under the `access` target with method,
+//! path, status, body size, duration and client IP, so logs can be
+//! ingested without parsing the trace layer's spans.
+use axum::{
+    body::HttpBody,
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::Level;
+
+/// Parse a configured log level name ("trace" ... "error")
+pub fn parse_level(level: &str) -> anyhow::Result<Level> {
+    level
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid access_log_level '{}'", level))
+}
+
+/// Access log settings shared by all requests
+pub struct AccessLogState {
+    pub level: Level,
+    /// Peers allowed to report the client address in `X-Forwarded-For`
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+/// Client address as seen by the first untrusted hop
+///
+/// `X-Forwarded-For` is only read when the connecting peer is a trusted
+/// proxy. Its entries are walked from the right, skipping further trusted
+/// proxies, so a client cannot spoof its address by sending the header.
+fn client_ip(request: &Request, trusted_proxies: &[IpAddr]) -> String {
+    let Some(peer) = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+    else {
+        return "-".to_string();
+    };
+    if !trusted_proxies.contains(&peer) {
+        return peer.to_string();
+    }
+
+    let forwarded: Vec<&str> = request
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .collect();
+    forwarded
+        .iter()
+        .rev()
+        .find(|hop| !hop.parse().is_ok_and(|ip| trusted_proxies.contains(&ip)))
+        .or(forwarded.first())
+        .map_or_else(|| peer.to_string(), |hop| hop.to_string())
+}
+
+/// Middleware logging every request at the configured level
+pub async fn access_log(
+    State(state): State<Arc<AccessLogState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let client_ip = client_ip(&request, &state.trusted_proxies);
+
+    let response = next.run(request).await;
+
+    let status = response.status().as_u16();
+    // Content-Length when known (files), else the exact body size if any
+    let bytes = response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok())
+        .or_else(|| response.body().size_hint().exact())
+        .unwrap_or(0);
+    let cache_hit = response.status() == StatusCode::NOT_MODIFIED;
+    let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+    macro_rules! log_at {
+        ($level:expr) => {
+            tracing::event!(
+                target: "access",
+                $level,
+                %method,
+                %path,
+                status,
+                bytes,
+                cache_hit,
+                duration_ms,
+                %client_ip,
+                "request served"
+            )
+        };
+    }
+    match state.level {
+        Level::TRACE => log_at!(Level::TRACE),
+        Level::DEBUG => log_at!(Level::DEBUG),
+        Level::INFO => log_at!(Level::INFO),
+        Level::WARN => log_at!(Level::WARN),
+        Level::ERROR => log_at!(Level::ERROR),
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn request_from(peer: &str, forwarded: Option<&str>) -> Request {
+        let mut request = Request::builder().uri("/media/sat.png");
+        if let Some(forwarded) = forwarded {
+            request = request.header("x-forwarded-for", forwarded);
+        }
+        let mut request = request.body(Body::empty()).unwrap();
+        let peer: SocketAddr = format!("{}:40000", peer).parse().unwrap();
+        request.extensions_mut().insert(ConnectInfo(peer));
+        request
+    }
+
+    #[test]
+    fn test_client_ip_trusts_only_configured_proxies() {
+        let proxies: Vec<IpAddr> = vec!["127.0.0.1".parse().unwrap(), "10.0.0.2".parse().unwrap()];
+
+        // Direct clients cannot pick their logged address
+        let request = request_from("198.51.100.9", Some("203.0.113.7"));
+        assert_eq!(client_ip(&request, &proxies), "198.51.100.9");
+        assert_eq!(client_ip(&request, &[]), "198.51.100.9");
+
+        let request = request_from("127.0.0.1", Some("203.0.113.7"));
+        assert_eq!(client_ip(&request, &proxies), "203.0.113.7");
+        assert_eq!(client_ip(&request, &[]), "127.0.0.1");
+
+        // A spoofed leftmost entry is skipped; chained trusted proxies are too
+        let request = request_from("127.0.0.1", Some("192.0.2.1, 203.0.113.7, 10.0.0.2"));
+        assert_eq!(client_ip(&request, &proxies), "203.0.113.7");
+
+        let request = request_from("127.0.0.1", None);
+        assert_eq!(client_ip(&request, &proxies), "127.0.0.1");
+    }
+}
@@ -0,0 +1,478 @@
+//! HTTP routes and middleware for the media server
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::{Request, State},
+    middleware::{self, Next},
+    routing::get,
+    Router,
+    http::{HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response},
+};
+use http_body::{Frame, SizeHint};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tower::util::Either;
+use tower::ServiceBuilder;
+use tower_http::services::{ServeDir, ServeFile};
+use tower_http::trace::TraceLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+use crate::access_log::{access_log, parse_level, AccessLogState};
+use crate::config::Config;
+use crate::etag::{etag_middleware, EtagState};
+use crate::listing::{list_media, ListingState};
+
+/// Health check endpoint
+async fn health_check() -> impl IntoResponse {
+    (StatusCode::OK, "OK")
+}
+
+/// Stats endpoint - returns basic server information
+async fn stats() -> impl IntoResponse {
+    let stats = serde_json::json!({
+        "status": "running",
+        "service": "rinko-media-server",
+        "version": env!("CARGO_PKG_VERSION"),
+    });
+    (StatusCode::OK, serde_json::to_string(&stats).unwrap())
+}
+
+/// Build the CORS layer from config, or `None` when CORS is disabled
+fn cors_layer(config: &Config) -> anyhow::Result<Option<CorsLayer>> {
+    if !config.enable_cors {
+        return Ok(None);
+    }
+
+    let origins = if config.cors_allowed_origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        let origins = config
+            .cors_allowed_origins
+            .iter()
+            .map(|o| {
+                HeaderValue::from_str(o)
+                    .map_err(|e| anyhow::anyhow!("Invalid CORS origin '{}': {}", o, e))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        AllowOrigin::list(origins)
+    };
+
+    let methods = config
+        .cors_allowed_methods
+        .iter()
+        .map(|m| {
+            Method::from_bytes(m.to_ascii_uppercase().as_bytes())
+                .map_err(|e| anyhow::anyhow!("Invalid CORS method '{}': {}", m, e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods(methods)
+            .allow_headers(Any),
+    ))
+}
+
+/// Response body that holds a concurrency permit until it is sent or dropped
+struct PermitBody {
+    inner: Body,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl HttpBody for PermitBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        Pin::new(&mut self.inner).poll_frame(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Reject requests with 503 while all permits are in use
+///
+/// The permit moves into the response body, so a large file keeps its slot
+/// until it has been streamed rather than only until the handler returns.
+async fn concurrency_limit(
+    State(permits): State<Arc<Semaphore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match permits.try_acquire_owned() {
+        Ok(permit) => next
+            .run(request)
+            .await
+            .map(|inner| Body::new(PermitBody { inner, _permit: permit })),
+        Err(_) => {
+            tracing::warn!("Concurrency limit reached, rejecting {}", request.uri());
+            (StatusCode::SERVICE_UNAVAILABLE, "Server busy, retry later").into_response()
+        }
+    }
+}
+
+/// Limit how many requests `app` serves at once (0 = unlimited)
+fn with_concurrency_limit(app: Router, limit: usize) -> Router {
+    if limit == 0 {
+        return app;
+    }
+    app.layer(middleware::from_fn_with_state(
+        Arc::new(Semaphore::new(limit)),
+        concurrency_limit,
+    ))
+}
+
+/// Build the application router
+pub fn build_app(config: &Config) -> anyhow::Result<Router> {
+    let serve_dir = ServeDir::new(&config.media_directory)
+        .precompressed_br()
+        .precompressed_gzip();
+    let media_service = match &config.fallback_image {
+        Some(fallback) => {
+            if !std::path::Path::new(fallback).is_file() {
+                tracing::warn!("Fallback image does not exist: {}", fallback);
+            }
+            Either::Left(serve_dir.fallback(ServeFile::new(fallback)))
+        }
+        None => Either::Right(serve_dir),
+    };
+
+    let mut app = Router::new()
+        // Health check endpoint
+        .route("/health", get(health_check))
+        // Stats endpoint
+        .route("/stats", get(stats))
+        // Static files service - serves files under /media/*
+        .nest_service(
+            &format!("/{}", config.url_prefix),
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    EtagState::new(&config.media_directory).with_immutable(config.immutable_cache),
+                    etag_middleware,
+                ))
+                .service(media_service),
+        );
+
+    // Optional media listing for operators
+    if config.enable_listing {
+        let Some(token) = config.listing_token.clone().filter(|t| !t.expose().is_empty()) else {
+            anyhow::bail!("enable_listing requires listing_token to be set");
+        };
+        let state = Arc::new(ListingState {
+            media_directory: config.media_directory.clone().into(),
+            token,
+        });
+        app = app.route("/list", get(list_media).with_state(state));
+    }
+
+    // Add middleware
+    app = with_concurrency_limit(app, config.max_concurrent_requests);
+    if let Some(cors) = cors_layer(config)? {
+        app = app.layer(cors);
+    }
+
+    let access_log_state = Arc::new(AccessLogState {
+        level: parse_level(&config.access_log_level)?,
+        trusted_proxies: config.trusted_proxies.clone(),
+    });
+    Ok(app
+        .layer(middleware::from_fn_with_state(access_log_state, access_log))
+        .layer(TraceLayer::new_for_http()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::{header, Request};
+    use tower::ServiceExt;
+
+    fn test_config(extra: &str) -> Config {
+        toml::from_str(&format!("media_directory = \"media\"\n{}", extra)).unwrap()
+    }
+
+    async fn allow_origin_for(config: &Config, origin: &str) -> Option<HeaderValue> {
+        let response = build_app(config)
+            .unwrap()
+            .oneshot(
+                Request::builder()
+                    .uri("/health")
+                    .header(header::ORIGIN, origin)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        response
+            .headers()
+            .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+            .cloned()
+    }
+
+    /// Config serving a fresh temp directory containing `sat.png`
+    fn media_config(dir_name: &str) -> Config {
+        let dir = std::env::temp_dir().join(dir_name);
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("sat.png"), b"not really a png").unwrap();
+        toml::from_str(&format!("media_directory = {:?}", dir.to_string_lossy())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_conditional_fetch() {
+        let config = media_config("rinko_media_test_etag");
+
+        let response = build_app(&config)
+            .unwrap()
+            .oneshot(Request::builder().uri("/media/sat.png").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(header::LAST_MODIFIED));
+        let etag = response.headers().get(header::ETAG).unwrap().clone();
+
+        let response = build_app(&config)
+            .unwrap()
+            .oneshot(
+                Request::builder()
+                    .uri("/media/sat.png")
+                    .header(header::IF_NONE_MATCH, etag.clone())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+        assert!(!response.headers().contains_key(header::CACHE_CONTROL));
+    }
+
+    #[tokio::test]
+    async fn test_immutable_cache_only_for_existing_files() {
+        let mut config = media_config("rinko_media_test_immutable");
+        config.immutable_cache = true;
+        let get = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+
+        let response = build_app(&config).unwrap().oneshot(get("/media/sat.png")).await.unwrap();
+        assert_eq!(
+            response.headers()[header::CACHE_CONTROL],
+            "public, max-age=31536000, immutable"
+        );
+
+        let response = build_app(&config).unwrap().oneshot(get("/media/gone.png")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert!(!response.headers().contains_key(header::CACHE_CONTROL));
+    }
+
+    async fn list_request(config: &Config, token: Option<&str>) -> axum::response::Response {
+        let mut request = Request::builder().uri("/list");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+        }
+        build_app(config)
+            .unwrap()
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_listing_disabled_by_default() {
+        let config = media_config("rinko_media_test_list_disabled");
+        let response = list_request(&config, Some("secret")).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_listing_enabled() {
+        let mut config = media_config("rinko_media_test_list_enabled");
+        config.enable_listing = true;
+        config.listing_token = Some("secret".into());
+
+        let response = list_request(&config, None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = list_request(&config, Some("secret")).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let files: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(files.as_array().unwrap().len(), 1);
+        assert_eq!(files[0]["name"], "sat.png");
+        assert_eq!(files[0]["size"], 16);
+        assert!(files[0]["modified"].as_u64().unwrap() > 0);
+
+        config.listing_token = None;
+        assert!(build_app(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_serves_fallback() {
+        let mut config = media_config("rinko_media_test_fallback");
+        let missing = || Request::builder().uri("/media/expired.png").body(Body::empty()).unwrap();
+
+        let response = build_app(&config).unwrap().oneshot(missing()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let fallback = std::env::temp_dir().join("rinko_media_test_fallback_expired.png");
+        std::fs::write(&fallback, b"expired placeholder").unwrap();
+        config.fallback_image = Some(fallback.to_string_lossy().into_owned());
+
+        let response = build_app(&config).unwrap().oneshot(missing()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        assert!(!response.headers().contains_key(header::ETAG));
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], b"expired placeholder");
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_limit_rejects_excess() {
+        // Two handlers and the test meet at each barrier
+        let entered = Arc::new(tokio::sync::Barrier::new(3));
+        let release = Arc::new(tokio::sync::Barrier::new(3));
+        let (handler_entered, handler_release) = (entered.clone(), release.clone());
+        let app = with_concurrency_limit(
+            Router::new().route(
+                "/slow",
+                get(move || async move {
+                    handler_entered.wait().await;
+                    handler_release.wait().await;
+                    "done"
+                }),
+            ),
+            2,
+        );
+
+        let slow_request = || {
+            app.clone()
+                .oneshot(Request::builder().uri("/slow").body(Body::empty()).unwrap())
+        };
+
+        // Saturate both permits
+        let first = tokio::spawn(slow_request());
+        let second = tokio::spawn(slow_request());
+        entered.wait().await;
+
+        let excess = slow_request().await.unwrap();
+        assert_eq!(excess.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        release.wait().await;
+        assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::OK);
+        assert_eq!(second.await.unwrap().unwrap().status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_permit_held_until_body_sent() {
+        let app = with_concurrency_limit(Router::new().route("/file", get(|| async { "data" })), 1);
+        let request = || {
+            app.clone()
+                .oneshot(Request::builder().uri("/file").body(Body::empty()).unwrap())
+        };
+
+        // The handler has returned, but its body has not been read yet
+        let pending = request().await.unwrap();
+        assert_eq!(request().await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+
+        let bytes = axum::body::to_bytes(pending.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], b"data");
+        assert_eq!(request().await.unwrap().status(), StatusCode::OK);
+    }
+
+    /// Writer appending formatted log output to a shared buffer
+    #[derive(Clone, Default)]
+    struct LogBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_access_log_record() {
+        let mut config = media_config("rinko_media_test_access_log");
+        config.trusted_proxies = vec!["127.0.0.1".parse().unwrap()];
+        let logs = LogBuffer::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let response = build_app(&config)
+            .unwrap()
+            .oneshot(
+                Request::builder()
+                    .uri("/media/sat.png")
+                    .header("x-forwarded-for", "203.0.113.7")
+                    .extension(axum::extract::ConnectInfo(std::net::SocketAddr::from((
+                        [127, 0, 0, 1],
+                        40000,
+                    ))))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let output = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let record = output
+            .lines()
+            .find(|line| line.contains("request served"))
+            .expect("no access log record");
+        assert!(record.contains("INFO"), "{}", record);
+        assert!(record.contains("path=/media/sat.png"), "{}", record);
+        assert!(record.contains("status=200"), "{}", record);
+        assert!(record.contains("bytes=16"), "{}", record);
+        assert!(record.contains("cache_hit=false"), "{}", record);
+        assert!(record.contains("client_ip=203.0.113.7"), "{}", record);
+    }
+
+    #[test]
+    fn test_invalid_access_log_level() {
+        let config = test_config(r#"access_log_level = "loud""#);
+        assert!(build_app(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cors_allowlist() {
+        let config = test_config(r#"cors_allowed_origins = ["https://allowed.example"]"#);
+
+        assert_eq!(
+            allow_origin_for(&config, "https://allowed.example").await,
+            Some(HeaderValue::from_static("https://allowed.example"))
+        );
+        assert_eq!(allow_origin_for(&config, "https://evil.example").await, None);
+    }
+
+    #[tokio::test]
+    async fn test_cors_wildcard_and_disabled() {
+        let config = test_config("");
+        assert_eq!(
+            allow_origin_for(&config, "https://any.example").await,
+            Some(HeaderValue::from_static("*"))
+        );
+
+        let config = test_config("enable_cors = false");
+        assert_eq!(allow_origin_for(&config, "https://any.example").await, None);
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -22,9 +23,72 @@ pub struct Config {
     #[serde(default)]
     pub domain: Option<String>,
 
-    /// Enable CORS for cross-origin requests
+    /// Enable CORS for cross-origin requests (no CORS headers when false)
     #[serde(default = "default_enable_cors")]
     pub enable_cors: bool,
+
+    /// Origins allowed by CORS; `"*"` allows any origin
+    #[serde(default = "default_cors_allowed_origins")]
+    pub cors_allowed_origins: Vec<String>,
+
+    /// HTTP methods allowed by CORS
+    #[serde(default = "default_cors_allowed_methods")]
+    pub cors_allowed_methods: Vec<String>,
+
+    /// Maximum requests served at once; excess requests get 503 (0 = unlimited)
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+
+    /// Expose `GET /list` with a JSON listing of the media directory
+    #[serde(default)]
+    pub enable_listing: bool,
+
+    /// Bearer token required by `GET /list` (must be set to enable listing)
+    #[serde(default)]
+    pub listing_token: Option<Secret>,
+
+    /// Placeholder image served (with 200) when a requested media file is
+    /// missing, e.g. cleaned up between render and send
+    #[serde(default)]
+    pub fallback_image: Option<String>,
+
+    /// Mark served media as immutable (`Cache-Control: immutable`); only
+    /// safe when the backend names renders by content hash
+    #[serde(default)]
+    pub immutable_cache: bool,
+
+    /// Level of the per-request access log ("trace" ... "error")
+    #[serde(default = "default_access_log_level")]
+    pub access_log_level: String,
+
+    /// Reverse proxies whose `X-Forwarded-For` header is believed; requests
+    /// from any other peer are logged with the connecting address
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+}
+
+/// A credential that is redacted from `Debug` output (and so from logs)
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    /// The actual value, for comparing against a request
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret(value.to_string())
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("\"<redacted>\"")
+    }
 }
 
 fn default_port() -> u16 {
@@ -43,6 +107,22 @@ fn default_enable_cors() -> bool {
     true
 }
 
+fn default_cors_allowed_origins() -> Vec<String> {
+    vec!["*".to_string()]
+}
+
+fn default_max_concurrent_requests() -> usize {
+    256
+}
+
+fn default_access_log_level() -> String {
+    "info".to_string()
+}
+
+fn default_cors_allowed_methods() -> Vec<String> {
+    vec!["GET".to_string(), "HEAD".to_string()]
+}
+
 impl Config {
     /// Load configuration from a TOML file
     pub fn load(path: &str) -> anyhow::Result<Self> {
@@ -54,4 +134,45 @@ impl Config {
 
         Ok(config)
     }
+
+    /// Address to listen on: all interfaces when `bind_all`, else localhost
+    pub fn bind_address(&self) -> SocketAddr {
+        let ip = if self.bind_all {
+            Ipv4Addr::UNSPECIFIED
+        } else {
+            Ipv4Addr::LOCALHOST
+        };
+        SocketAddr::from((ip, self.port))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_address_follows_bind_all() {
+        let mut config: Config = toml::from_str(r#"media_directory = "media""#).unwrap();
+        config.port = 8080;
+
+        config.bind_all = true;
+        assert_eq!(config.bind_address(), "0.0.0.0:8080".parse().unwrap());
+
+        config.bind_all = false;
+        assert_eq!(config.bind_address(), "127.0.0.1:8080".parse().unwrap());
+    }
+
+    #[test]
+    fn test_listing_token_redacted_from_debug() {
+        let config: Config = toml::from_str(
+            r#"
+            media_directory = "media"
+            listing_token = "hunter2"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.listing_token.as_ref().unwrap().expose(), "hunter2");
+        assert!(!format!("{:?}", config).contains("hunter2"));
+    }
 }
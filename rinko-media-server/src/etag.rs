@@ -0,0 +1,219 @@
+//! Strong `ETag` headers and `If-None-Match` handling for media files
+//!
+//! `ServeDir` already sends `Last-Modified` and answers `If-Modified-Since`;
+//! this middleware adds a content-hash `ETag` so clients can revalidate
+//! with `If-None-Match` and get a `304` instead of the full image. When
+//! enabled, existing files are also marked immutable for a year.
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
+
+/// `Cache-Control` sent for media when immutable caching is enabled
+const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+/// Most ETags kept; the least recently served file is forgotten first
+const MAX_CACHED_ETAGS: usize = 4096;
+
+/// ETag of one file as of its modification time
+struct CachedEtag {
+    modified: SystemTime,
+    etag: HeaderValue,
+    last_used: Instant,
+}
+
+/// Computes and caches ETags for files under the media directory
+#[derive(Clone)]
+pub struct EtagState {
+    root: PathBuf,
+    immutable: bool,
+    /// path -> ETag; recomputed when the file changes, dropped when it is
+    /// gone, and capped at `max_entries`
+    cache: Arc<Mutex<HashMap<PathBuf, CachedEtag>>>,
+    max_entries: usize,
+}
+
+impl EtagState {
+    pub fn new(root: impl AsRef<Path>) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            immutable: false,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+            max_entries: MAX_CACHED_ETAGS,
+        }
+    }
+
+    /// Also send `Cache-Control: immutable` for existing files
+    pub fn with_immutable(mut self, immutable: bool) -> Self {
+        self.immutable = immutable;
+        self
+    }
+
+    /// Caching headers for an existing file with the given ETag
+    fn cache_headers(&self, etag: HeaderValue) -> Vec<(header::HeaderName, HeaderValue)> {
+        let mut headers = vec![(header::ETAG, etag)];
+        if self.immutable {
+            headers.push((
+                header::CACHE_CONTROL,
+                HeaderValue::from_static(IMMUTABLE_CACHE_CONTROL),
+            ));
+        }
+        headers
+    }
+
+    /// Resolve a request path (relative to the media prefix) to a file
+    ///
+    /// Only plain path segments are accepted; anything else (parent
+    /// directories, percent-encoded names) is left to `ServeDir` untagged.
+    fn resolve(&self, uri_path: &str) -> Option<PathBuf> {
+        let relative = Path::new(uri_path.trim_start_matches('/'));
+        if uri_path.contains('%')
+            || relative.as_os_str().is_empty()
+            || !relative.components().all(|c| matches!(c, Component::Normal(_)))
+        {
+            return None;
+        }
+        Some(self.root.join(relative))
+    }
+
+    /// Strong ETag for the file at `uri_path`, or `None` if it is not a file
+    async fn etag_for(&self, uri_path: &str) -> Option<HeaderValue> {
+        let path = self.resolve(uri_path)?;
+        let modified = match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => metadata.modified().ok(),
+            _ => None,
+        };
+        let Some(modified) = modified else {
+            // Cleaned up (or never a file): nothing to keep an ETag for
+            self.cache.lock().unwrap().remove(&path);
+            return None;
+        };
+
+        if let Some(cached) = self.cache.lock().unwrap().get_mut(&path)
+            && cached.modified == modified
+        {
+            cached.last_used = Instant::now();
+            return Some(cached.etag.clone());
+        }
+
+        let content = tokio::fs::read(&path).await.ok()?;
+        let etag = HeaderValue::from_str(&format!("\"{}\"", hex::encode(Sha256::digest(&content))))
+            .ok()?;
+        let mut cache = self.cache.lock().unwrap();
+        cache.insert(
+            path,
+            CachedEtag {
+                modified,
+                etag: etag.clone(),
+                last_used: Instant::now(),
+            },
+        );
+        if cache.len() > self.max_entries
+            && let Some(oldest) = cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(path, _)| path.clone())
+        {
+            cache.remove(&oldest);
+        }
+        Some(etag)
+    }
+}
+
+/// Whether an `If-None-Match` header value matches `etag`
+///
+/// Uses weak comparison, as RFC 9110 requires for `If-None-Match`.
+fn etag_matches(if_none_match: &str, etag: &HeaderValue) -> bool {
+    let etag = etag.to_str().unwrap_or_default();
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.trim_start_matches("W/") == etag
+    })
+}
+
+/// Middleware adding `ETag` to media responses and answering `304`
+pub async fn etag_middleware(
+    State(state): State<EtagState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !matches!(*request.method(), Method::GET | Method::HEAD) {
+        return next.run(request).await;
+    }
+
+    let Some(etag) = state.etag_for(request.uri().path()).await else {
+        return next.run(request).await;
+    };
+
+    let not_modified = request
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| etag_matches(v, &etag));
+    if not_modified {
+        let mut response = StatusCode::NOT_MODIFIED.into_response();
+        response.headers_mut().extend(state.cache_headers(etag));
+        return response;
+    }
+
+    let mut response = next.run(request).await;
+    if response.status().is_success() {
+        response.headers_mut().extend(state.cache_headers(etag));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_etag_matches() {
+        let etag = HeaderValue::from_static("\"abc\"");
+        assert!(etag_matches("\"abc\"", &etag));
+        assert!(etag_matches("\"x\", W/\"abc\"", &etag));
+        assert!(etag_matches("*", &etag));
+        assert!(!etag_matches("\"abd\"", &etag));
+    }
+
+    #[test]
+    fn test_resolve_rejects_traversal() {
+        let state = EtagState::new("/srv/media");
+        assert_eq!(
+            state.resolve("/sat_1.png"),
+            Some(PathBuf::from("/srv/media/sat_1.png"))
+        );
+        assert_eq!(state.resolve("/../secret"), None);
+        assert_eq!(state.resolve("/"), None);
+        assert_eq!(state.resolve("/a%2Fb.png"), None);
+    }
+
+    #[tokio::test]
+    async fn test_cache_forgets_removed_files_and_is_capped() {
+        let dir = std::env::temp_dir().join("rinko_media_test_etag_cache");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["a.png", "b.png", "c.png"] {
+            std::fs::write(dir.join(name), name).unwrap();
+        }
+        let state = EtagState { max_entries: 2, ..EtagState::new(&dir) };
+        let cached = |name: &str| state.cache.lock().unwrap().contains_key(&dir.join(name));
+
+        assert!(state.etag_for("/a.png").await.is_some());
+        assert!(state.etag_for("/b.png").await.is_some());
+        assert!(state.etag_for("/a.png").await.is_some());
+        assert!(state.etag_for("/c.png").await.is_some());
+        // b was served least recently
+        assert!(cached("a.png") && cached("c.png") && !cached("b.png"));
+
+        std::fs::remove_file(dir.join("a.png")).unwrap();
+        assert!(state.etag_for("/a.png").await.is_none());
+        assert!(!cached("a.png"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,92 @@
+//! Opt-in `GET /list` endpoint showing what is in the media directory
+use axum::{
+    extract::State,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use crate::config::Secret;
+
+/// Settings for the listing endpoint
+pub struct ListingState {
+    pub media_directory: PathBuf,
+    /// Bearer token required in the `Authorization` header
+    pub token: Secret,
+}
+
+/// A single file in the listing
+#[derive(Debug, Serialize)]
+struct MediaFile {
+    name: String,
+    size: u64,
+    /// Modification time as Unix seconds
+    modified: u64,
+}
+
+/// GET /list - JSON listing of files in the media directory
+pub async fn list_media(
+    State(state): State<Arc<ListingState>>,
+    headers: HeaderMap,
+) -> Response {
+    let authorized = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|v| token_matches(v, state.token.expose()));
+    if !authorized {
+        return (StatusCode::UNAUTHORIZED, "Unauthorized").into_response();
+    }
+
+    match read_listing(&state.media_directory).await {
+        Ok(files) => Json(files).into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list media directory: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to list media directory").into_response()
+        }
+    }
+}
+
+/// Compare tokens in constant time
+///
+/// Both sides are hashed first so neither the contents nor the length of
+/// the expected token leak through timing.
+fn token_matches(given: &str, expected: &str) -> bool {
+    Sha256::digest(given.as_bytes())
+        .ct_eq(&Sha256::digest(expected.as_bytes()))
+        .into()
+}
+
+/// Regular files directly under `dir`, sorted by name
+async fn read_listing(dir: &Path) -> std::io::Result<Vec<MediaFile>> {
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        if !metadata.is_file() {
+            continue;
+        }
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        files.push(MediaFile {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            size: metadata.len(),
+            modified,
+        });
+    }
+
+    files.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(files)
+}